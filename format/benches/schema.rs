@@ -0,0 +1,224 @@
+//! benchmarks for compiling and querying a schema with the size of NISO-STS.
+//! The WHAS source is generated from the shipped Fonto schema of NISO-STS: every element
+//! gets a type with its attributes and content model. Run with `cargo +nightly bench`.
+#![feature(test)]
+
+extern crate test;
+
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use test::Bencher;
+use whale_schema::{Exporter, FontoSchemaExporter, Schema};
+
+const NISO_STS: &str = include_str!("../src/formats/fonto/niso-sts.json");
+
+/// path of the generated WHAS schema, which is written once per run
+fn schema_path() -> &'static PathBuf {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+    PATH.get_or_init(|| {
+        let path = std::env::temp_dir().join("whas-bench-niso-sts.whas");
+        std::fs::write(&path, niso_sts_whas()).expect("could not write generated schema");
+        path
+    })
+}
+
+fn compiled() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| Schema::from_file(schema_path()).expect("could not compile schema"))
+}
+
+#[bench]
+fn bench_compile(b: &mut Bencher) {
+    let path = schema_path();
+    b.iter(|| Schema::from_file(path).unwrap());
+}
+
+#[bench]
+fn bench_elements_root(b: &mut Bencher) {
+    let schema = compiled();
+    b.iter(|| schema.get_elements_root().len());
+}
+
+#[bench]
+fn bench_lookup_by_name(b: &mut Bencher) {
+    let schema = compiled();
+    let element_names = schema
+        .elements()
+        .values()
+        .map(|el| el.name().clone())
+        .collect::<Vec<_>>();
+    let type_names = schema
+        .all_type_names()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    b.iter(|| {
+        let elements = element_names
+            .iter()
+            .map(|name| schema.get_elements_by_name(name).len())
+            .sum::<usize>();
+        let groups = type_names
+            .iter()
+            .filter(|name| schema.get_group_by_name(name).is_some())
+            .count();
+
+        elements + groups
+    });
+}
+
+#[bench]
+fn bench_export_fonto(b: &mut Bencher) {
+    let schema = compiled();
+    b.iter(|| {
+        FontoSchemaExporter::default()
+            .export_schema(schema)
+            .unwrap()
+    });
+}
+
+//
+// GENERATING THE SCHEMA
+//
+
+/// translate the Fonto schema to WHAS
+fn niso_sts_whas() -> String {
+    let fonto: Value = serde_json::from_str(NISO_STS).unwrap();
+    let mut out = String::new();
+
+    for (prefix, elements) in [("T", &fonto["elements"]), ("L", &fonto["localElements"])] {
+        for (i, element) in elements.as_array().unwrap().iter().enumerate() {
+            let mut names = HashSet::new();
+
+            for attr_ref in element["attributeRefs"].as_array().unwrap() {
+                let attr = &fonto["attributes"][attr_ref.as_u64().unwrap() as usize];
+                let name = ident(attr["localName"].as_str().unwrap());
+
+                if names.insert(name.clone()) {
+                    let opt = if attr["use"] == "required" { "" } else { "?" };
+                    writeln!(out, "@{}{}", name, opt).unwrap();
+                }
+            }
+
+            let model =
+                &fonto["contentModels"][element["contentModelRef"].as_u64().unwrap() as usize];
+            let mixed = if element["isMixed"] == true { "x" } else { "" };
+
+            writeln!(
+                out,
+                "{} {}",
+                type_name(prefix, i, element),
+                block(mixed, model, &fonto)
+            )
+            .unwrap();
+        }
+    }
+
+    for (i, element) in fonto["elements"].as_array().unwrap().iter().enumerate() {
+        writeln!(
+            out,
+            "#{}: {}",
+            ident(element["localName"].as_str().unwrap()),
+            type_name("T", i, element)
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+fn block(mods: &str, model: &Value, fonto: &Value) -> String {
+    let occurrence = match model["type"].as_str().unwrap() {
+        "choice" => "?",
+        _ => "",
+    };
+
+    let items = model["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|item| match item["type"].as_str().unwrap() {
+            "element" => {
+                let name = item["localName"].as_str().unwrap();
+                let (i, element) = fonto["elements"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, el)| el["localName"] == name)
+                    .unwrap();
+
+                format!(
+                    "#{}{}: {}",
+                    ident(name),
+                    duplicity(item),
+                    type_name("T", i, element)
+                )
+            }
+            "localElement" => {
+                let i = item["elementRef"].as_u64().unwrap() as usize;
+                let element = &fonto["localElements"][i];
+                let name = element["localName"].as_str().unwrap();
+
+                format!(
+                    "#{}{}: {}",
+                    ident(name),
+                    duplicity(item),
+                    type_name("L", i, element)
+                )
+            }
+            _ => format!("...{}", block("", item, fonto)),
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}{}{{ {} }}", mods, occurrence, items.join(", "))
+}
+
+fn duplicity(item: &Value) -> String {
+    let min = item["minOccurs"].as_u64().unwrap_or(1);
+
+    match (min, item["maxOccurs"].as_u64()) {
+        (1, Some(1)) => String::new(),
+        (0, Some(1)) => "?".to_string(),
+        (0, None) => "*".to_string(),
+        (_, None) => "+".to_string(),
+        (min, Some(max)) => format!("[{}..{}]", min, max),
+    }
+}
+
+/// a type name that is unique per Fonto element definition
+fn type_name(prefix: &str, i: usize, element: &Value) -> String {
+    let name = element["localName"]
+        .as_str()
+        .unwrap()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    format!("{}{}{}", prefix, i, name)
+}
+
+/// lowercase identifier with dashes between words, like WHAS requires
+fn ident(name: &str) -> String {
+    let ident = name
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    match ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => ident,
+        false => format!("x-{}", ident),
+    }
+}
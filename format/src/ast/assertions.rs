@@ -0,0 +1,85 @@
+use super::*;
+
+/// assert if @type = 'figure' then #caption
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assertion))]
+pub struct Assertion {
+    pub expr: AssertExpr,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_expr))]
+pub enum AssertExpr {
+    If(AssertIf),
+    Or(AssertOr),
+}
+
+/// if @type = 'figure' then #caption
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_if))]
+pub struct AssertIf {
+    pub condition: AssertOr,
+    pub then: AssertOr,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_or))]
+pub struct AssertOr(pub Vec<AssertAnd>);
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_and))]
+pub struct AssertAnd(pub Vec<AssertNot>);
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_not))]
+pub enum AssertNot {
+    Negation(AssertNegation),
+    Comparison(AssertComparison),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_negation))]
+pub struct AssertNegation(pub Box<AssertNot>);
+
+/// an operand, optionally compared to another one
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_comparison))]
+pub struct AssertComparison {
+    pub left: AssertOperand,
+    pub right: Option<AssertComparisonRhs>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_comparison_rhs))]
+pub struct AssertComparisonRhs {
+    pub op: AssertOp,
+    pub operand: AssertOperand,
+}
+
+/// =, !=, <, <=, > or >=
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_op))]
+pub struct AssertOp {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    pub token: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_operand))]
+pub enum AssertOperand {
+    Count(AssertCount),
+    Paren(AssertParen),
+    Attribute(IdentAttr),
+    Element(IdentElement),
+    Number(Number),
+    Literal(AttrItemStr),
+}
+
+/// count(#item)
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_count))]
+pub struct AssertCount(pub IdentElement);
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::assert_paren))]
+pub struct AssertParen(pub Box<AssertExpr>);
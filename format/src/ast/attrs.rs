@@ -3,7 +3,7 @@ use std::fmt;
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
 #[pest_ast(rule(Rule::ident_attr))]
-pub struct IdentAttr(pub IdentLowercase);
+pub struct IdentAttr(pub IdentName);
 
 impl AsRef<str> for IdentAttr {
     fn as_ref(&self) -> &str {
@@ -59,7 +59,7 @@ impl AttrDef {
 
 #[derive(Debug, Eq, PartialEq, Default, Clone, FromPest)]
 #[pest_ast(rule(Rule::attributes))]
-pub struct Attributes(pub Vec<AttrDef>);
+pub struct Attributes(pub Vec<AttrDef>, pub Vec<Comment>);
 
 impl Deref for Attributes {
     type Target = Vec<AttrDef>;
@@ -69,6 +69,67 @@ impl Deref for Attributes {
     }
 }
 
+impl Attributes {
+    /// the attribute definitions with their comments. The dangling comments, like those
+    /// right above the first attribute and those between the attributes and the element or
+    /// type that they are defined on, document that element or type
+    pub fn documented(&self) -> Documented<&AttrDef> {
+        let header = self.header_comments();
+
+        let mut documented = Documented::attach(
+            self.0
+                .iter()
+                .enumerate()
+                .flat_map(|(i, attr)| {
+                    let leading = match i {
+                        0 => &[],
+                        _ => attr.comments.as_slice(),
+                    };
+
+                    leading
+                        .iter()
+                        .cloned()
+                        .map(itertools::Either::Left)
+                        .chain([itertools::Either::Right(attr)])
+                        .chain(
+                            attr.comment
+                                .iter()
+                                .map(|line| itertools::Either::Left(Comment::Line(line.clone()))),
+                        )
+                })
+                .chain(self.1.iter().cloned().map(itertools::Either::Left)),
+        );
+
+        documented.dangling.splice(0..0, header.iter().cloned());
+        documented
+    }
+
+    /// comments that the first attribute picks up, but that come before the definition the
+    /// attributes are part of: trailing comments of the line before and detached comments.
+    /// They are attached by the list that contains the definition
+    pub fn preceding_comments(&self) -> &[Comment] {
+        let comments = self.first_comments();
+        let count = comments
+            .iter()
+            .rposition(|comment| comment.placement() != CommentPlacement::Leading)
+            .map_or(0, |i| i + 1);
+
+        &comments[..count]
+    }
+
+    /// the comments right above the first attribute, which document the whole definition
+    fn header_comments(&self) -> &[Comment] {
+        &self.first_comments()[self.preceding_comments().len()..]
+    }
+
+    fn first_comments(&self) -> &[Comment] {
+        self.0
+            .first()
+            .map(|attr| attr.comments.as_slice())
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
 #[pest_ast(rule(Rule::attr_item_str))]
 pub struct AttrItemStr {
@@ -76,6 +137,16 @@ pub struct AttrItemStr {
     pub value: String,
 }
 
+impl AttrItemStr {
+    /// the literal value without its enclosing delimiters
+    pub fn unquoted(&self) -> &str {
+        let mut chars = self.value.chars();
+        chars.next();
+        chars.next_back();
+        chars.as_str()
+    }
+}
+
 impl ToString for AttrItemStr {
     fn to_string(&self) -> String {
         self.value.clone()
@@ -8,6 +8,45 @@ pub struct Block {
     pub mods: BlockMods,
     /// all sub-items of the block
     pub items: Vec<BlockItem>,
+    /// how often the block repeats as a whole, once by default
+    pub duplicity: Option<ModDuplicity>,
+}
+
+impl Block {
+    /// the block items with their comments. The dangling comments document the block itself
+    pub fn documented(&self) -> Documented<&BlockItem> {
+        Documented::attach(self.items.iter().flat_map(|item| {
+            let (preceding, item) = match item {
+                BlockItem::Comment(comment) => (vec![], itertools::Either::Left(comment.clone())),
+                BlockItem::Element(element) => (
+                    element.attributes.preceding_comments().to_vec(),
+                    itertools::Either::Right(item),
+                ),
+                BlockItem::Attribute(attr) => (attr.comments.clone(), itertools::Either::Right(item)),
+                item => (vec![], itertools::Either::Right(item)),
+            };
+
+            // the comment at the end of the line of an attribute is about the attribute
+            let trailing = match item {
+                itertools::Either::Right(BlockItem::Attribute(attr)) => attr.comment.clone(),
+                _ => None,
+            };
+
+            preceding
+                .into_iter()
+                .map(itertools::Either::Left)
+                .chain([item])
+                .chain(trailing.map(|line| itertools::Either::Left(Comment::Line(line))))
+        }))
+    }
+
+    /// attributes declared on the block itself, rather than on one of its elements
+    pub fn attributes(&self) -> impl Iterator<Item = &AttrDef> {
+        self.items.iter().filter_map(|item| match item {
+            BlockItem::Attribute(attr) => Some(attr),
+            _ => None,
+        })
+    }
 }
 
 impl Deref for Block {
@@ -23,11 +62,17 @@ impl Deref for Block {
 pub enum BlockItem {
     /// this block item is a nested element
     Element(Element),
+    /// the top-level element with the name is used here
+    ElementRef(ElementRef),
+    /// attribute of the element with the block as its content
+    Attribute(AttrDef),
     /// another block definition is flattened into this definition
     SplatBlock(SplatBlock),
     SplatType(SplatType),
     /// a generic variable is splat into the block
     SplatGenericArg(SplatGenericVar),
+    /// rule on the attributes and children of the element
+    Assertion(Assertion),
     /// comment node
     Comment(Comment),
 }
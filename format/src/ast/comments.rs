@@ -18,11 +18,23 @@ impl ToString for Comment {
     }
 }
 
+impl Comment {
+    pub fn placement(&self) -> CommentPlacement {
+        match self {
+            Comment::Line(line) => line.placement,
+            Comment::Markdown(md) => md.placement,
+            Comment::Wild(wild) => wild.placement,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, FromPest, Clone)]
 #[pest_ast(rule(Rule::comment_line))]
 pub struct CommentLine {
     #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
     pub value: String,
+    #[pest_ast(outer(with(CommentPlacement::from_span)))]
+    pub placement: CommentPlacement,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
@@ -30,6 +42,8 @@ pub struct CommentLine {
 pub struct CommentMarkdown {
     #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
     pub value: String,
+    #[pest_ast(outer(with(CommentPlacement::from_span)))]
+    pub placement: CommentPlacement,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
@@ -37,4 +51,95 @@ pub struct CommentMarkdown {
 pub struct CommentWild {
     #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
     pub value: String,
+    #[pest_ast(outer(with(CommentPlacement::from_span)))]
+    pub placement: CommentPlacement,
+}
+
+/// where a comment is written relative to the definitions around it, which determines
+/// what it documents
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum CommentPlacement {
+    /// on its own line(s) right above a definition, which it documents
+    Leading,
+    /// at the end of the line of a definition, which it documents
+    Trailing,
+    /// followed by an empty line, so it documents the surrounding block or file
+    Detached,
+}
+
+impl CommentPlacement {
+    fn from_span(span: Span) -> Self {
+        let start = span.start_pos();
+        let (_, column) = start.line_col();
+        let before = start.line_of().chars().take(column - 1).collect::<String>();
+
+        if !before.trim().is_empty() {
+            return Self::Trailing;
+        }
+
+        // an empty line between the comment and whatever follows
+        let after = &span.get_input()[span.end()..];
+        let mut lines = after.split('\n');
+        let rest_of_line = lines.next().unwrap_or_default();
+        let next_line = lines.next();
+
+        match next_line {
+            Some(line) if rest_of_line.trim().is_empty() && line.trim().is_empty() => {
+                Self::Detached
+            }
+            _ => Self::Leading,
+        }
+    }
+}
+
+/// items of a list in which comments are items themselves, with the comments that document them
+#[derive(Debug)]
+pub struct Documented<T> {
+    /// every item with its leading and trailing comments, in order
+    pub items: Vec<(T, Vec<Comment>)>,
+
+    /// comments that do not belong to any of the items, but to what contains the list
+    pub dangling: Vec<Comment>,
+}
+
+impl<T> Documented<T> {
+    /// attach the comments in the list to the items:
+    /// - trailing comments document the item they follow
+    /// - leading comments document the item below them
+    /// - detached comments, and comments without an item to attach to, are dangling
+    pub fn attach(list: impl IntoIterator<Item = itertools::Either<Comment, T>>) -> Self {
+        let mut items: Vec<(T, Vec<Comment>)> = vec![];
+        let mut dangling = vec![];
+        let mut leading = vec![];
+
+        for entry in list {
+            match entry {
+                itertools::Either::Left(comment) => match comment.placement() {
+                    CommentPlacement::Trailing if leading.is_empty() => match items.last_mut() {
+                        Some((_, comments)) => comments.push(comment),
+                        None => dangling.push(comment),
+                    },
+                    CommentPlacement::Detached => {
+                        dangling.append(&mut leading);
+                        dangling.push(comment);
+                    }
+                    _ => leading.push(comment),
+                },
+                itertools::Either::Right(item) => items.push((item, std::mem::take(&mut leading))),
+            }
+        }
+
+        dangling.append(&mut leading);
+
+        Self { items, dangling }
+    }
+
+    /// comments of the item for which the predicate holds
+    pub fn comments_of(&self, predicate: impl Fn(&T) -> bool) -> Vec<Comment> {
+        self.items
+            .iter()
+            .find(|(item, _)| predicate(item))
+            .map(|(_, comments)| comments.clone())
+            .unwrap_or_default()
+    }
 }
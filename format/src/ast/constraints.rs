@@ -0,0 +1,81 @@
+use super::*;
+
+/// identity constraint on the values in the descendants of an element
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint))]
+pub enum Constraint {
+    Unique(ConstraintUnique),
+    KeyRef(ConstraintKeyRef),
+    Key(ConstraintKey),
+}
+
+/// unique #chapter/@id
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint_unique))]
+pub struct ConstraintUnique {
+    pub path: ConstraintPath,
+}
+
+/// key chapter-id: #chapter/@id
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint_key))]
+pub struct ConstraintKey {
+    pub name: IdentLowercase,
+    pub path: ConstraintPath,
+}
+
+/// keyref #xref/@target: chapter-id
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint_keyref))]
+pub struct ConstraintKeyRef {
+    pub path: ConstraintPath,
+    pub key: IdentLowercase,
+}
+
+impl Constraint {
+    pub fn path(&self) -> &ConstraintPath {
+        match self {
+            Constraint::Unique(unique) => &unique.path,
+            Constraint::KeyRef(keyref) => &keyref.path,
+            Constraint::Key(key) => &key.path,
+        }
+    }
+}
+
+/// the elements to select and the attribute or child element that holds their value
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint_path))]
+pub struct ConstraintPath {
+    pub selector: Vec<IdentElement>,
+    pub field: ConstraintField,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::constraint_field))]
+pub enum ConstraintField {
+    Attribute(IdentAttr),
+    Element(IdentElement),
+}
+
+/// rule #chapter/#title: `string-length(.) <= 80` "chapter titles are short"
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::schematron_rule))]
+pub struct SchematronRule {
+    pub context: Option<SchematronContext>,
+    pub test: SchematronTest,
+    pub message: SchematronMessage,
+}
+
+/// path from the element to the descendants the rule applies to
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::schematron_context))]
+pub struct SchematronContext(pub Vec<IdentElement>);
+
+/// XPath expression that holds for valid documents
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::schematron_test))]
+pub struct SchematronTest(pub AttrItemStr);
+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::schematron_message))]
+pub struct SchematronMessage(pub AttrItemStr);
@@ -3,7 +3,7 @@ use std::fmt::Display;
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
 #[pest_ast(rule(Rule::ident_element))]
-pub struct IdentElement(pub IdentLowercase);
+pub struct IdentElement(pub IdentName);
 
 impl AsRef<str> for IdentElement {
     fn as_ref(&self) -> &str {
@@ -43,6 +43,35 @@ impl Element {
     pub fn duplicity(&self) -> Option<&ModDuplicity> {
         self.assignment().mod_dup.as_ref()
     }
+
+    /// the head element this element can substitute for
+    pub fn substitutes(&self) -> Option<&IdentElement> {
+        self.assignment().substitution.as_ref().map(|sub| &sub.0)
+    }
+
+    /// the inline block of the element, when it has one instead of a type
+    pub fn block(&self) -> Option<&Block> {
+        match &self.item {
+            ElementItem::WithType(_) => None,
+            ElementItem::WithBlock(ElementWithBlock { block, .. }) => Some(block),
+        }
+    }
+
+    /// identity constraints declared after the type of the element
+    pub fn constraints(&self) -> &[Constraint] {
+        match &self.item {
+            ElementItem::WithType(ElementWithType { constraints, .. }) => constraints,
+            ElementItem::WithBlock(ElementWithBlock { constraints, .. }) => constraints,
+        }
+    }
+
+    /// schematron rules declared after the identity constraints
+    pub fn rules(&self) -> &[SchematronRule] {
+        match &self.item {
+            ElementItem::WithType(ElementWithType { rules, .. }) => rules,
+            ElementItem::WithBlock(ElementWithBlock { rules, .. }) => rules,
+        }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
@@ -50,6 +79,26 @@ impl Element {
 pub struct ElementAssign {
     pub element: IdentElement,
     pub mod_dup: Option<ModDuplicity>,
+    pub substitution: Option<Substitution>,
+}
+
+/// < #head
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::substitution))]
+pub struct Substitution(pub IdentElement);
+
+/// ref #element*?+
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::element_ref))]
+pub struct ElementRef {
+    pub element: IdentElement,
+    pub mod_dup: Option<ModDuplicity>,
+}
+
+impl ElementRef {
+    pub fn name(&self) -> &str {
+        self.element.as_ref()
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
@@ -64,6 +113,8 @@ pub enum ElementItem {
 pub struct ElementWithType {
     pub assign: ElementAssign,
     pub typing: Typing,
+    pub constraints: Vec<Constraint>,
+    pub rules: Vec<SchematronRule>,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
@@ -71,4 +122,6 @@ pub struct ElementWithType {
 pub struct ElementWithBlock {
     pub assign: ElementAssign,
     pub block: Block,
+    pub constraints: Vec<Constraint>,
+    pub rules: Vec<SchematronRule>,
 }
@@ -9,6 +9,14 @@ pub struct IdentLowercase {
     pub value: String,
 }
 
+/// exact XML name of an element or attribute
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::ident_name))]
+pub struct IdentName {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    pub value: String,
+}
+
 #[derive(Debug, Eq, PartialEq, FromPest, Ord, Clone, PartialOrd)]
 #[pest_ast(rule(Rule::ident_capitalized))]
 pub struct IdentCapitalized {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::sourced::{FileSystemSources, SourceProvider};
 use wax::Glob;
 
 #[derive(Debug, Eq, PartialEq, FromPest)]
@@ -9,6 +10,7 @@ pub enum Import {
 
     /// import "./other.whas"
     /// import * from "./other.whas"
+    /// import "./other.whas" as other
     Inline(ImportInline),
 }
 
@@ -27,6 +29,14 @@ impl Import {
         }
     }
 
+    /// whether the schemas that import this one can use the imported types too
+    pub fn is_reexport(&self) -> bool {
+        match self {
+            Import::Inline(inline) => inline.reexport.is_some(),
+            Import::Extended(extended) => extended.reexport.is_some(),
+        }
+    }
+
     pub fn is_absolute(&self) -> bool {
         self.path().is_absolute()
     }
@@ -35,6 +45,22 @@ impl Import {
         !self.is_absolute()
     }
 
+    /// the alias that qualifies the imported types, like 'dc' for `import "./dc.whas" as dc`
+    pub fn alias(&self) -> Option<&str> {
+        match self {
+            Import::Inline(inline) => inline.alias.as_ref().map(|alias| alias.0.value.as_str()),
+            Import::Extended(_) => None,
+        }
+    }
+
+    /// the explicitly selected types, none for wildcard imports
+    pub fn selected_items(&self) -> Vec<ImportSelectorItem> {
+        match self.is_wildcard() {
+            true => vec![],
+            false => self.selector().explicit_items(),
+        }
+    }
+
     pub fn selector(&self) -> &ImportSelector {
         match self {
             Import::Inline(inline) => inline.selector.as_ref().unwrap(),
@@ -50,6 +76,22 @@ impl Import {
         }
     }
 
+    /// the files that the import refers to, which are all matching files for glob patterns
+    pub fn absolute_paths(&self, reference_dir: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
+        files_at(reference_dir, self.path())
+    }
+
+    /// whether the path starts at the importing schema, like "./other.whas" or "../other.whas"
+    pub fn is_explicitly_relative(&self) -> bool {
+        self.path().starts_with(".") || self.path().starts_with("..")
+    }
+
+    /// the package name and the path within it, like ("shared", "xlink") for "@shared/xlink"
+    pub fn package(&self) -> Option<(&str, &Path)> {
+        let (package, path) = self.path().to_str()?.strip_prefix('@')?.split_once('/')?;
+        Some((package, Path::new(path)))
+    }
+
     pub fn absolute_dir(&self, reference_dir: impl AsRef<Path>) -> PathBuf {
         self.absolute_path(reference_dir)
             .parent()
@@ -58,40 +100,37 @@ impl Import {
     }
 
     pub fn validate(&self, reference_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.validate_in(reference_dir, &FileSystemSources)
+    }
+
+    /// like `validate`, finding the imported schemas in the sources
+    pub fn validate_in(
+        &self,
+        reference_dir: impl AsRef<Path>,
+        sources: &dyn SourceProvider,
+    ) -> anyhow::Result<()> {
         let path_str = self.path().to_str().unwrap_or("");
 
         // Check if this is a glob pattern
         if path_str.contains('*') {
-            // Normalize the pattern by removing leading ./ if present
-            let normalized_pattern = path_str.strip_prefix("./").unwrap_or(path_str);
-
-            // Use the relative pattern and walk from the reference directory
-            let glob = Glob::new(normalized_pattern)
-                .context(format!("invalid glob pattern: {}", normalized_pattern))?;
-
-            // Walk the directory and validate each matching file
-            let matches: Vec<_> = glob.walk(reference_dir.as_ref())
-                .filter_map(Result::ok)
-                .collect();
+            let matches = sources.files_at(reference_dir.as_ref(), self.path())?;
 
             if matches.is_empty() {
                 return Err(anyhow::anyhow!(
                     "no files found matching glob pattern: {} in directory: {}",
-                    normalized_pattern,
+                    path_str.strip_prefix("./").unwrap_or(path_str),
                     reference_dir.as_ref().display()
                 ));
             }
 
-            for entry in matches {
-                let file_path = entry.path();
-                if file_path.is_file() {
-                    // Just verify the file can be parsed - don't recursively validate imports
-                    // (that would cause stack overflow with cyclic imports)
-                    let content = std::fs::read_to_string(file_path)
-                        .context(format!("error reading schema: {}", file_path.display()))?;
-                    SchemaFile::parse(&content)
-                        .context(format!("error parsing schema: {}", file_path.display()))?;
-                }
+            for file_path in matches {
+                // Just verify the file can be parsed - don't recursively validate imports
+                // (that would cause stack overflow with cyclic imports)
+                let content = sources
+                    .read(&file_path)
+                    .context(format!("error reading schema: {}", file_path.display()))?;
+                SchemaFile::parse(&content)
+                    .context(format!("error parsing schema: {}", file_path.display()))?;
             }
 
             Ok(())
@@ -99,15 +138,24 @@ impl Import {
             // Regular file path - use existing logic
             let abspath = self.absolute_path(&reference_dir);
 
-            self.try_read_schema(Some(&reference_dir))
+            self.try_read_schema_in(Some(&reference_dir), sources)
                 .context(format!("error reading schema: {}", abspath.display()))?
-                .validate_imports(self.absolute_dir(&reference_dir))
+                .validate_imports_in(self.absolute_dir(&reference_dir), sources)
         }
     }
 
     pub fn try_read_schema(
         &self,
         reference_dir: Option<impl AsRef<Path>>,
+    ) -> anyhow::Result<SchemaFile> {
+        self.try_read_schema_in(reference_dir, &FileSystemSources)
+    }
+
+    /// like `try_read_schema`, reading the schema from the sources
+    pub fn try_read_schema_in(
+        &self,
+        reference_dir: Option<impl AsRef<Path>>,
+        sources: &dyn SourceProvider,
     ) -> anyhow::Result<SchemaFile> {
         let path_str = self.path().to_str().unwrap_or("");
 
@@ -123,7 +171,7 @@ impl Import {
             .map(|rd| rd.as_ref().to_path_buf())
             .unwrap_or_default();
         let abspath = self.absolute_path(reference_dir);
-        SchemaFile::new_file(&abspath)
+        SchemaFile::from_sources(&abspath, sources)
             .context(format!("error reading schema: {}", abspath.display()))
     }
 
@@ -167,11 +215,37 @@ impl Import {
     // }
 }
 
+/// the files at the path in the dir, which are all matching files for glob patterns
+pub fn files_at(dir: impl AsRef<Path>, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let path_str = path.to_str().unwrap_or("");
+
+    if !path_str.contains('*') {
+        return Ok(vec![dir.as_ref().join(path)]);
+    }
+
+    let normalized_pattern = path_str.strip_prefix("./").unwrap_or(path_str);
+    let glob = Glob::new(normalized_pattern)
+        .context(format!("invalid glob pattern: {}", normalized_pattern))?;
+
+    let mut paths = glob
+        .walk(dir.as_ref())
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().to_path_buf())
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+
+    // the order of the directory walk is not stable
+    paths.sort();
+    Ok(paths)
+}
+
 #[derive(Debug, Eq, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::import_inline))]
 pub struct ImportInline {
+    pub reexport: Option<KeywordExport>,
     pub selector: Option<ImportSelector>,
     pub path: ImportPath,
+    pub alias: Option<ImportAlias>,
 }
 
 impl ImportInline {
@@ -187,6 +261,7 @@ impl ImportInline {
 #[derive(Debug, Eq, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::import_extended))]
 pub struct ImportExtended {
+    pub reexport: Option<KeywordExport>,
     pub path: ImportPath,
     pub selector: ImportSelector,
 }
@@ -219,6 +294,13 @@ impl ImportSelector {
     }
 
     pub fn explicit_type_names(&self) -> Vec<TypeWithoutGeneric> {
+        self.explicit_items()
+            .into_iter()
+            .map(|item| item.typename)
+            .collect()
+    }
+
+    pub fn explicit_items(&self) -> Vec<ImportSelectorItem> {
         match self {
             ImportSelector::Any(_) => vec![],
             ImportSelector::Types(ImportSelectorBlock(explicits)) => {
@@ -230,7 +312,35 @@ impl ImportSelector {
 
 #[derive(Debug, Eq, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::import_selector_block))]
-pub struct ImportSelectorBlock(Option<Vec<TypeWithoutGeneric>>);
+pub struct ImportSelectorBlock(Option<Vec<ImportSelectorItem>>);
+
+/// Title as DcTitle
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::import_selector_item))]
+pub struct ImportSelectorItem {
+    pub typename: TypeWithoutGeneric,
+    pub rename: Option<IdentTypeNonPrimitive>,
+}
+
+impl ImportSelectorItem {
+    /// name of the type in the imported schema
+    pub fn name(&self) -> &str {
+        self.typename.0.as_ref()
+    }
+
+    /// name of the type in the importing schema
+    pub fn local_name(&self) -> &str {
+        self.rename
+            .as_ref()
+            .map(|rename| rename.as_ref())
+            .unwrap_or(self.name())
+    }
+}
+
+/// as dc
+#[derive(Debug, Eq, PartialEq, FromPest)]
+#[pest_ast(rule(Rule::import_alias))]
+pub struct ImportAlias(pub IdentLowercase);
 
 #[derive(Debug, Eq, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::import_path))]
@@ -6,3 +6,19 @@ pub struct Keyword {
     #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
     pub token: String,
 }
+
+/// private, for type definitions that other schemas cannot import
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::keyword_private))]
+pub struct KeywordPrivate {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    pub token: String,
+}
+
+/// export, for imports that the schemas importing this one can use too
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::keyword_export))]
+pub struct KeywordExport {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    pub token: String,
+}
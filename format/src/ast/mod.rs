@@ -18,9 +18,11 @@ pub use {
 pub(crate) use {crate::ast, crate::default, crate::model};
 
 mod argvars;
+mod assertions;
 mod attrs;
 mod blocks;
 mod comments;
+mod constraints;
 mod elements;
 mod facets;
 mod file;
@@ -37,9 +39,9 @@ mod types;
 mod typings;
 
 pub use {
-    argvars::*, attrs::*, blocks::*, comments::*, elements::*, facets::*, file::*, idents::*,
-    imports::*, keywords::*, primitives::*, regex::*, schemas::*, splats::*, symbols::*,
-    typedefs::*, types::*, typings::*,
+    argvars::*, assertions::*, attrs::*, blocks::*, comments::*, constraints::*, elements::*,
+    facets::*, file::*, idents::*, imports::*, keywords::*, primitives::*, regex::*, schemas::*,
+    splats::*, symbols::*, typedefs::*, types::*, typings::*,
 };
 
 // todo: adjust this so we can store the spans in the AST nodes,
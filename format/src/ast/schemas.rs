@@ -1,6 +1,8 @@
 use super::*;
 use pseudonym::alias;
 use tap::Pipe;
+use itertools::Itertools;
+use crate::sourced::{FileSystemSources, SourceProvider};
 
 #[derive(Debug, Eq, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::schema))]
@@ -25,50 +27,143 @@ pub struct SchemaFile {
 impl SchemaFile {
     #[alias(from_file)]
     pub fn new_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let ctx_msg = format!("reading schema from {}", path.as_ref().display());
-        let mut path = Self::resolve_file_path(&path)?;
+        Self::from_sources(path, &FileSystemSources)
+    }
+
+    /// like `new_file`, reading the schema and its imports from the sources
+    pub fn from_sources(path: impl AsRef<Path>, sources: &dyn SourceProvider) -> anyhow::Result<Self> {
+        let path = sources.resolve_file_path(path.as_ref())?;
 
-        let schema = Self::parse(std::fs::read_to_string(&path).context(ctx_msg)?.as_str())?;
+        let schema = Self::parse(&sources.read(&path)?)?;
 
         let parent_dir = path.parent().unwrap_or_else(|| Path::new(""));
-        schema.validate_imports(parent_dir)?;
+        schema.validate_imports_in(parent_dir, sources)?;
 
         Ok(schema)
     }
 
     pub fn parse(input: &str) -> anyhow::Result<Self> {
-        let mut parsed = WHASParser::parse(Rule::schema, input)?;
+        let mut parsed = match WHASParser::parse(Rule::schema, input) {
+            Ok(parsed) => parsed,
+            Err(error) => return Err(Self::syntax_errors(input, error)),
+        };
 
         // Convert the Pest AST to your Rust structs
         Ok(SchemaFile::from_pest(&mut parsed)?)
     }
 
+    /// all syntax errors of the input, instead of only the first one. After an item that
+    /// fails, parsing continues at the next line after the failure that starts an item, in
+    /// the first column. A failure at a spot that already failed is reported once
+    fn syntax_errors(input: &str, first: pest::error::Error<Rule>) -> anyhow::Error {
+        let mut errors: Vec<pest::error::Error<Rule>> = vec![];
+        let mut pos = 0;
+
+        while let Some(start) = input[pos..]
+            .find(|c: char| !c.is_whitespace())
+            .map(|skipped| pos + skipped)
+        {
+            let header = [Rule::namespace, Rule::import]
+                .into_iter()
+                .find_map(|rule| WHASParser::parse(rule, &input[start..]).ok());
+
+            let item = match header {
+                Some(header) => Ok(header),
+                None => WHASParser::parse(Rule::schema_item, &input[start..]),
+            };
+
+            pos = match item.map(|mut item| item.next().map_or(0, |pair| pair.as_span().end())) {
+                Ok(end) if end > 0 => start + end,
+                Ok(_) => next_item_start(input, start),
+                Err(error) => {
+                    let error = error_at(input, start, error);
+                    let failed = error_pos(&error.location);
+
+                    if !errors.iter().any(|seen| error_pos(&seen.location) == failed) {
+                        errors.push(error);
+                    }
+
+                    next_item_start(input, failed.max(start))
+                }
+            };
+        }
+
+        match errors.len() {
+            0 | 1 => first.into(),
+            count => anyhow!(
+                "{} syntax errors:\n{}",
+                count,
+                errors.iter().map(ToString::to_string).join("\n")
+            ),
+        }
+    }
+
     /// resolve file path relative to where this schemafile is defined
     pub fn resolve_file_path(path: impl AsRef<Path>) -> anyhow::Result<PathBuf> {
-        let path = path.as_ref();
-        let with_ext = path.with_extension("whas");
-        Ok(if path.exists() {
-            path.to_path_buf()
-        } else if with_ext.exists() {
-            with_ext
-        } else {
-            Err(anyhow::anyhow!(
-                "file not found at paths: {}, {}",
-                path.display(),
-                with_ext.display()
-            ))?
-        })
+        FileSystemSources.resolve_file_path(path.as_ref())
     }
 
     pub fn has_imports(&self) -> bool {
         !self.imports.is_empty()
     }
 
+    /// the top-level items with their comments. The dangling comments document the schema
+    /// itself, like the comments at the top of the file when imports follow them
+    pub fn documented(&self) -> Documented<&SchemaItem> {
+        let (header, leading) = match self.has_imports() {
+            true => (self.doc.clone(), vec![]),
+            false => (vec![], self.doc.clone()),
+        };
+
+        let mut documented =
+            Documented::attach(leading.into_iter().map(itertools::Either::Left).chain(
+                self.items.iter().flat_map(|item| {
+                    let (preceding, item) = match item {
+                        SchemaItem::Comment(comment) => {
+                            (vec![], itertools::Either::Left(comment.clone()))
+                        }
+                        SchemaItem::Element(element) => (
+                            element.attributes.preceding_comments().to_vec(),
+                            itertools::Either::Right(item),
+                        ),
+                        SchemaItem::TypeDefinition(typedef) => (
+                            typedef.attributes().preceding_comments().to_vec(),
+                            itertools::Either::Right(item),
+                        ),
+                    };
+
+                    preceding
+                        .into_iter()
+                        .map(itertools::Either::Left)
+                        .chain([item])
+                }),
+            ));
+
+        documented.dangling.splice(0..0, header);
+        documented
+    }
+
+    /// comments that document a top-level type definition of this file
+    pub fn type_comments(&self, typedef: &TypeDef) -> Vec<Comment> {
+        self.documented().comments_of(
+            |item| matches!(item, SchemaItem::TypeDefinition(def) if std::ptr::eq(def, typedef)),
+        )
+    }
+
     // make sure imports can be parsed.
     // the referemce is dir is the location of this Schema, relative to which the imports are resolved
     pub fn validate_imports(&self, reference_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.validate_imports_in(reference_dir, &FileSystemSources)
+    }
+
+    /// like `validate_imports`, finding the imports in the sources
+    pub fn validate_imports_in(
+        &self,
+        reference_dir: impl AsRef<Path>,
+        sources: &dyn SourceProvider,
+    ) -> anyhow::Result<()> {
         for import in &self.imports {
-            import.validate(&reference_dir)?;
+            import.validate_in(&reference_dir, sources)?;
         }
         Ok(())
     }
@@ -144,3 +239,41 @@ pub struct Namespace {
     #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
     pub value: String,
 }
+
+/// the offset where an error starts
+fn error_pos(location: &pest::error::InputLocation) -> usize {
+    match location {
+        pest::error::InputLocation::Pos(pos) => *pos,
+        pest::error::InputLocation::Span((pos, _)) => *pos,
+    }
+}
+
+/// the error of parsing the input from the start, at its position in the whole input
+fn error_at(input: &str, start: usize, error: pest::error::Error<Rule>) -> pest::error::Error<Rule> {
+    let offset = error_pos(&error.location);
+
+    match pest::Position::new(input, start + offset) {
+        Some(position) => pest::error::Error::new_from_pos(error.variant, position),
+        None => error,
+    }
+}
+
+/// the start of the first line after the one at the start, that begins with an item
+/// rather than with whitespace or the closing brace of a block
+fn next_item_start(input: &str, start: usize) -> usize {
+    let mut pos = start;
+
+    while let Some(newline) = input[pos..].find('\n') {
+        pos += newline + 1;
+
+        if input[pos..]
+            .chars()
+            .next()
+            .is_some_and(|c| !c.is_whitespace() && c != '}')
+        {
+            return pos;
+        }
+    }
+
+    input.len()
+}
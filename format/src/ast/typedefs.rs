@@ -27,6 +27,14 @@ impl TypeDef {
         self.ident_nonprim().as_ref() == name.as_ref()
     }
 
+    /// private types can only be used by the schema that defines them
+    pub fn is_private(&self) -> bool {
+        match self {
+            TypeDef::Inline(item) => item.private.is_some(),
+            TypeDef::Block(item) => item.private.is_some(),
+        }
+    }
+
     pub fn attributes(&self) -> Attributes {
         match self {
             TypeDef::Inline(_) => default(), // no attributes support
@@ -69,15 +77,19 @@ impl TypeDef {
                             ast::TypeNameBase::Regular(TypeWithoutGeneric(IdentType::NonPrimitive(
                                 nonprim,
                             ))) => {
-                                println!("resolving subtype {:?}...\n", ty);
+                                log::debug!("resolving subtype {:?}...", ty);
                                 return schema
                                     .find_type(nonprim)
-                                    .ok_or(anyhow!("could not find Type declaration for '{}' when resolving type {:#?}", nonprim, typing))?
+                                    .ok_or_else(|| anyhow!("could not find Type declaration for '{}' when resolving type {:#?}", nonprim, typing))?
                                     .simple_type(schema);
                             }
                             ast::TypeNameBase::Generic(_generic_ty) => {
                                 todo!()
                             }
+                            // lists are simple types, whatever their items are
+                            ast::TypeNameBase::List(list) => {
+                                return Ok(Some(list.clone().into()));
+                            }
                         }
                     }
                 }
@@ -125,8 +137,10 @@ impl AsRef<IdentTypeNonPrimitive> for TypeDef {
 #[derive(Debug, Eq, Clone, PartialEq, FromPest)]
 #[pest_ast(rule(Rule::typedef_inline))]
 pub struct TypeDefInline {
+    pub private: Option<KeywordPrivate>,
     pub typename: IdentTypeNonPrimitive,
     pub vars: Option<TypeDefVars>,
+    pub inheritance: Option<Inheritance>,
     pub typing: TypeDefInlineTyping,
 }
 
@@ -186,6 +200,7 @@ pub struct Inheritance {
 #[pest_ast(rule(Rule::typedef_block))]
 pub struct TypeDefBlock {
     pub attributes: Attributes,
+    pub private: Option<KeywordPrivate>,
     pub typename: IdentTypeNonPrimitive,
     pub vars: Option<TypeDefVars>,
     pub inheritance: Option<Inheritance>,
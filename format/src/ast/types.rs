@@ -51,12 +51,20 @@ pub struct TypeWithVars {
     pub args: Option<TypeDefVars>,
 }
 
+/// list of simple values of the item type
+/// [Decimal]
+/// [Token<1..20>]
+#[derive(Debug, Eq, PartialEq, Clone, FromPest)]
+#[pest_ast(rule(Rule::type_list))]
+pub struct TypeList(pub Box<TypeName>);
+
 /// TypeName with optional facets
 /// Examples:
 /// - String (regular)
 /// - String<5..20> (with facets)
 /// - List(String) (generic)
 /// - List(String<5..20>) (generic with faceted type arg)
+/// - [Decimal]<length: 2> (list with list facets)
 #[derive(Debug, Eq, PartialEq, Clone, FromPest)]
 #[pest_ast(rule(Rule::typename))]
 pub struct TypeName {
@@ -69,6 +77,7 @@ pub struct TypeName {
 pub enum TypeNameBase {
     Regular(TypeWithoutGeneric),
     Generic(TypeWithGeneric),
+    List(TypeList),
 }
 
 impl TypeName {
@@ -96,6 +105,15 @@ impl TypeName {
         match &self.base {
             TypeNameBase::Regular(t) => t.0.clone(),
             TypeNameBase::Generic(t) => IdentType::NonPrimitive(t.typename.clone()),
+            TypeNameBase::List(TypeList(item)) => item.base_ident(),
+        }
+    }
+
+    /// the base type as written, like `Int` or `[Int]` for a list
+    pub fn base_name(&self) -> String {
+        match &self.base {
+            TypeNameBase::List(TypeList(item)) => format!("[{}]", item.base_name()),
+            _ => self.base_ident().to_string(),
         }
     }
 
@@ -112,4 +130,5 @@ pub enum TypeSimple {
     Regex(TypeRegex),
     Compound(SimpleTypingInline),
     Union(TypeUnion),
+    List(TypeList),
 }
@@ -1,7 +1,8 @@
 use crate::formats::FontoSchemaCompilerVersion;
 use crate::formats::FontoVersion;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use log::warn;
+use std::path::PathBuf;
 use tap::Tap;
 
 /// Whale Schema Compiler
@@ -10,15 +11,22 @@ use tap::Tap;
 ///     - Fonto Schema .json
 ///     - XML Schema XSD
 #[derive(Parser, Debug)]
-#[command(version, about, long_about)]
+#[command(version, about, long_about, args_conflicts_with_subcommands = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// path to entrypoint WHAS schema
-    pub input: String,
+    pub input: Option<String>,
 
     /// compile to a Fonto schema
     #[arg(short, long, default_value_t = true)]
     pub fonto: bool,
 
+    /// do not write the Fonto schema
+    #[arg(long)]
+    pub no_fonto: bool,
+
     /// by default we compile for the toolset of v8.8 but with this flag we
     /// can specify it further. It's important because it will change the version numbering
     /// int he generated JSON schema. When the Fonto instance is incompatible with it,
@@ -30,9 +38,89 @@ pub struct Args {
     #[arg(short, long, default_value_t = true)]
     pub xsd: bool,
 
+    /// do not write the XSD schema
+    #[arg(long)]
+    pub no_xsd: bool,
+
+    /// export the rules and assertions of the schema to a Schematron schema (.sch) as well
+    #[arg(long)]
+    pub schematron: bool,
+
+    /// embed the Schematron rules of elements in the xs:appinfo of the XSD
+    #[arg(long)]
+    pub embed_schematron: bool,
+
+    /// warn about element and attribute names that are not lowercase with dashes
+    #[arg(long)]
+    pub lint: bool,
+
     /// output directory to export generated assets in
     #[arg(short, long = "output-dir")]
     pub output_dir: Option<String>,
+
+    /// dir to search for imports that are not found next to the importing schema.
+    /// Can be repeated, and is searched before the dirs in the WHAS_PATH environment variable
+    #[arg(short = 'I', long = "include-path", global = true)]
+    pub include_paths: Vec<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// convert a schema in another format to WHAS
+    Import {
+        /// path to the schema to import. The format is determined by the extension (.dtd)
+        input: String,
+
+        /// path of the WHAS file to write. Prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// render a static HTML documentation site for a WHAS schema
+    Doc {
+        /// path to entrypoint WHAS schema
+        input: String,
+
+        /// directory to write the site to
+        #[arg(short, long, default_value = "doc")]
+        output: String,
+    },
+
+    /// compile all entry schemas of a whas.toml project manifest to their targets
+    Build {
+        /// path to the manifest. By default the closest whas.toml to the current dir is used
+        #[arg(short, long)]
+        manifest: Option<String>,
+    },
+
+    /// generate source code with types for documents of a WHAS schema
+    Codegen {
+        #[command(subcommand)]
+        target: CodegenTarget,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CodegenTarget {
+    /// Rust structs and enums with serde attributes for quick-xml
+    Rust {
+        /// path to entrypoint WHAS schema
+        input: String,
+
+        /// path of the Rust file to write. Prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// TypeScript interfaces and literal unions for element names, attributes and content
+    Typescript {
+        /// path to entrypoint WHAS schema
+        input: String,
+
+        /// path of the TypeScript file to write. Prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 impl Args {
@@ -48,4 +136,11 @@ impl Args {
             (FontoSchemaCompilerVersion::default())
         })
     }
+
+    /// path to entrypoint WHAS schema, which is required when not running a subcommand
+    pub fn input(&self) -> anyhow::Result<&str> {
+        self.input
+            .as_deref()
+            .ok_or(anyhow::anyhow!("no input schema given"))
+    }
 }
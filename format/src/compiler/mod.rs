@@ -9,7 +9,7 @@ use crate::model::{Schema, TypeRef};
 use crate::sourced::SourcedSchemaFile;
 use crate::tools::default;
 use crate::{ast, model, tools};
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use from_pest::log::info;
 use itertools::Itertools;
 use log::debug;
@@ -17,17 +17,67 @@ use result::CompileResult;
 use std::convert::identity;
 use std::ops::Deref;
 
+/// core type of elements without content. An empty block, so the elements can have attributes
+pub const CORE_TYPE_EMPTY: &str = "Empty";
+
+/// remaining stack below which compiling a type definition continues on a new stack segment
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// size of the stack segments that are allocated when the red zone is reached
+const STACK_GROWTH: usize = 4 * 1024 * 1024;
+
 pub fn compile(source: &SourcedSchemaFile) -> anyhow::Result<model::Schema> {
     // the target schema we are building
     let mut schema = model::Schema::default();
 
+    let validated = source.validate_import_selections();
+    schema.recover(validated);
+
+    validate_unique_definitions(source, &mut schema);
+
     // define all types using an ID so they can be recursively resolved
     compile_type_definitions(source, &mut schema)?;
 
     // finally, define all elements
     compile_elements(source, &mut schema)?;
 
-    Ok(schema)
+    // the groups are only complete when all elements compiled
+    if schema.errors().is_empty() {
+        validate_substitution_groups(&schema)?;
+    }
+
+    schema.register_schema_comments(
+        source
+            .documented()
+            .dangling
+            .iter()
+            .map(Into::into)
+            .collect(),
+    );
+
+    match schema.errors().as_slice() {
+        [] => Ok(schema),
+        [error] => Err(anyhow!("{}", error)),
+        errors => Err(anyhow!(
+            "{} errors in {}:\n{}",
+            errors.len(),
+            source.path().display(),
+            errors.iter().map(|error| format!("- {}", error)).join("\n")
+        )),
+    }
+}
+
+/// types and top-level elements are declared once, a later declaration would silently lose
+fn validate_unique_definitions(source: &SourcedSchemaFile, schema: &mut model::Schema) {
+    let types = source.types().into_iter().map(|typedef| typedef.ident_nonprim().to_string());
+    for name in types.duplicates() {
+        schema.recover::<()>(Err(anyhow!("type '{}' is defined more than once", name)));
+    }
+
+    let elements = source.elements_top_level().into_iter().map(|element| element.name());
+    for name in elements.duplicates() {
+        schema.recover::<()>(Err(anyhow!("#{} is declared more than once at the top level", name)));
+    }
 }
 
 pub fn compile_type_definitions(
@@ -38,8 +88,24 @@ pub fn compile_type_definitions(
 
     // define all types using an ID so they can be recursively resolved
     for typedef in source.types().iter().sorted() {
-        // schema.register_type_definition_name(&typedef)?;
-        compile_type_definition(source, schema, typedef)?;
+        let compiled = compile_type_definition(source, schema, typedef);
+        schema.recover(compiled);
+    }
+
+    // comments above the attributes of a type, and those between the attributes and the type.
+    // collected in one pass over the source, since attaching comments looks at the neighbours
+    for (item, mut comments) in source.documented().items {
+        if let ast::SchemaItem::TypeDefinition(typedef) = item {
+            let id = match schema.id_for_type_definition(typedef, source) {
+                Some(id) => *id,
+                // the type failed before it got an ID, which is reported already
+                None if !schema.errors().is_empty() => continue,
+                None => Err(anyhow!("type '{}' was not compiled", typedef.ident()))?,
+            };
+
+            comments.extend(typedef.attributes().documented().dangling);
+            schema.register_type_comments(&id, comments.iter().map(Into::into).collect());
+        }
     }
 
     Ok(())
@@ -52,8 +118,17 @@ pub fn compile_type_definition(
 ) -> anyhow::Result<model::TypeRef> {
     info!("compiling type definition {}...", typedef.ident());
 
+    if typedef.ident_nonprim().as_ref().contains('.') {
+        return Err(anyhow!(
+            "type definition '{}' cannot have a qualified name, only types of an import with an alias can",
+            typedef.ident_nonprim()
+        ));
+    }
+
+    schema.register_type_name_source(&source.type_name(typedef), source, typedef)?;
+
     // if type is already defined with this name, short-circuit and return known ref
-    if let Some(existing) = schema.preliminary_ref_for_typename(&typedef, source) {
+    if let Some(existing) = schema.preliminary_ref_for_typename(&typedef, source)? {
         return Ok(existing.get_ref());
     }
 
@@ -62,14 +137,14 @@ pub fn compile_type_definition(
         typedef.ident()
     );
 
-    let new_id = SchemaObjId::new();
+    let new_id = schema.new_id();
 
     // register name with an ID that will have no type info attached yet
-    schema.register_type_definition_name(&new_id, typedef)?;
+    schema.register_type_definition_name(&new_id, typedef, source)?;
 
     assert!(
         schema
-            .preliminary_ref_for_typename(&typedef, source)
+            .preliminary_ref_for_typename(&typedef, source)?
             .is_some(),
         "it should now be possible to retrieve a priliminary type reference because we just regstered the type"
     );
@@ -78,17 +153,45 @@ pub fn compile_type_definition(
     // which should have totally happened by now
     // tools::panic_nth(&typedef.ident().to_string(), 2);
 
-    let target_ty = match typedef {
-        ast::TypeDef::Inline(ty_inline) => compile_inline_type(source, ty_inline, schema)?,
-        ast::TypeDef::Block(blockdef) => {
-            compile_block_definition(source, &blockdef, schema)?.into()
-        }
+    // types refer to other types that are compiled first, and splats compile the groups they
+    // splat in place, which recurses deeply in large schemas of nested groups
+    let splat_path = schema.suspend_splats();
+    let compiled = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || {
+        anyhow::Ok(match typedef {
+            ast::TypeDef::Inline(ty_inline) => compile_inline_type(source, ty_inline, schema)?,
+            ast::TypeDef::Block(blockdef) => {
+                compile_block_definition(source, &blockdef, schema)?.into()
+            }
+        })
+    });
+    // restored before a failure propagates too, so the splats that refer to it stay intact
+    schema.resume_splats(splat_path);
+
+    // a type that fails is reported, and the types and elements that use it continue with
+    // a placeholder, so their own errors are found too
+    let target_ty = match schema.recover(compiled) {
+        Some(target_ty) => target_ty,
+        None => compile_placeholder(typedef, schema)?,
     };
 
     // resolve unnamed part of the type definition and
-    let type_ref = schema.register_preliminary_id_type(&new_id, target_ty)?;
+    schema.register_preliminary_id_type(&new_id, target_ty)?;
 
-    Ok(type_ref)
+    // refer to the type by the ID of its name, like later references do
+    Ok(schema
+        .preliminary_ref_for_typename(typedef, source)?
+        .ok_or_else(|| anyhow!("type '{}' has no ID for its name", typedef.ident()))?
+        .get_ref())
+}
+
+/// stands in for a type that failed to compile: an empty block, or a string for inline types
+fn compile_placeholder(typedef: &ast::TypeDef, schema: &mut Schema) -> anyhow::Result<TypeRef> {
+    Ok(match typedef {
+        ast::TypeDef::Inline(_) => schema
+            .register_primitive_type(model::PrimitiveType::String)?
+            .into(),
+        ast::TypeDef::Block(_) => schema.register_group(GroupBuilder::default().build()?)?.into(),
+    })
 }
 
 /// Compile inheritance clause and validate circular dependencies
@@ -102,10 +205,10 @@ pub fn compile_inheritance(
 
     // Resolve the base type name to a type definition
     let base_type_name = inheritance.base_type.ident_nonprim()
-        .ok_or(anyhow!("Base type must be a non-primitive type"))?;
+        .ok_or_else(|| anyhow!("Base type must be a non-primitive type"))?;
 
-    let base_typedef = source.find_type(base_type_name)
-        .ok_or(anyhow!("Base type '{}' not found", base_type_name))?;
+    let (base_source, base_typedef) = source
+        .require_type(base_type_name, || anyhow!("Base type '{}' not found", base_type_name))?;
 
     // Base type must be a block definition (complex type), not an inline simple type
     let base_block = match base_typedef {
@@ -119,24 +222,29 @@ pub fn compile_inheritance(
     };
 
     // Detect circular inheritance
-    validate_no_circular_inheritance(source, blockdef, base_block)?;
+    validate_no_circular_inheritance(source, blockdef, &base_source, base_block)?;
 
-    // Compile the base type
-    compile_block_definition(source, base_block, schema)
+    // Compile the base type, referred to by the ID of its name so exporters can name it
+    match compile_type_definition(&base_source, schema, base_typedef)? {
+        model::TypeRef::Group(group) => Ok(group),
+        model::TypeRef::Simple(_) => Err(anyhow!("Base type '{}' is not a block", base_type_name)),
+    }
 }
 
 /// Validate that there are no circular inheritance chains
 fn validate_no_circular_inheritance(
     source: &SourcedSchemaFile,
     current: &ast::TypeDefBlock,
+    base_source: &SourcedSchemaFile,
     base: &ast::TypeDefBlock,
 ) -> anyhow::Result<()> {
     let mut visited = std::collections::HashSet::new();
-    visited.insert(current.typename.as_ref().to_string());
+    visited.insert(source.qualified_name(current.typename.as_ref()));
 
-    let mut current_base = base;
+    let mut current_source = base_source.clone();
+    let mut current_base = base.clone();
     loop {
-        let base_name = current_base.typename.as_ref().to_string();
+        let base_name = current_source.qualified_name(current_base.typename.as_ref());
 
         if visited.contains(&base_name) {
             return Err(anyhow!(
@@ -150,9 +258,10 @@ fn validate_no_circular_inheritance(
         // Check if this base has its own base
         if let Some(inheritance) = &current_base.inheritance {
             if let Some(next_base_name) = inheritance.base_type.ident_nonprim() {
-                if let Some(next_base_typedef) = source.find_type(next_base_name) {
+                if let Some((next_source, next_base_typedef)) = current_source.resolve_type(next_base_name) {
                     if let ast::TypeDef::Block(next_base_block) = next_base_typedef {
-                        current_base = next_base_block;
+                        current_base = next_base_block.clone();
+                        current_source = next_source;
                         continue;
                     }
                 }
@@ -176,6 +285,12 @@ pub fn compile_block_definition(
         blockdef.typename.to_string()
     );
 
+    validate_unique_attributes(
+        &format!("type '{}'", blockdef.typename),
+        &blockdef.attributes,
+        Some(&blockdef.block),
+    )?;
+
     let attrs = compile_attributes(source, &blockdef.attributes, schema)?;
 
     if !attrs.is_empty() {
@@ -200,30 +315,55 @@ pub fn compile_elements(
     schema: &mut model::Schema,
 ) -> anyhow::Result<()> {
     // iterate element definitions in the AST
-    for element_ast in source.elements_top_level() {
-        // now build the element
-        let res = compile_element(source, element_ast, schema)?;
+    for (item, comments) in source.documented().items {
+        if let ast::SchemaItem::Element(element_ast) = item {
+            // now build the element, an element that fails is reported and left out
+            let compiled = compile_element(source, element_ast, comments, true, schema);
+            schema.recover(compiled);
+        }
     }
 
     Ok(())
 }
 
-/// given a top-level element definition, compile it into a model Element
+/// given an element definition, compile it into a model Element
 /// and return all supporting type definitions
 pub fn compile_element(
     // complete source AST to query from
     source: &SourcedSchemaFile,
     // the ast element definition to compile to our models
     element_ast: &ast::Element,
+    // leading and trailing comments of the element definition
+    comments: Vec<ast::Comment>,
+    // whether the element is defined in the root of the schema, rather than in a block
+    top_level: bool,
     // the schema to register types in
     schema: &mut Schema,
 ) -> anyhow::Result<Ref<model::Element>> {
     info!("compiling element '{}'...", element_ast.name());
 
+    // prefixed elements are declared by the schema of their namespace, like #dc:title
+    if let (Some(prefix), _) = model::split_qname(element_ast.name())
+        && model::namespace_uri(element_ast.name()).is_none()
+    {
+        return Err(anyhow!(
+            "#{} has an unknown namespace prefix '{}', only the {} prefixes are supported",
+            element_ast.name(),
+            prefix,
+            model::known_prefixes()
+        ));
+    }
+
     if !element_ast.attributes.0.is_empty() {
         info!("attributes: {:#?}", &element_ast.attributes.0);
     }
 
+    validate_unique_attributes(
+        &format!("#{}", element_ast.name()),
+        &element_ast.attributes,
+        element_ast.block(),
+    )?;
+
     // create a builder for the element and
     let mut element_builder = model::ElementBuilder::default();
 
@@ -234,20 +374,219 @@ pub fn compile_element(
         // .attributes(compile_attributes(source, element_ast, schema)?.unwrap())
         .attributes(compile_attributes(source, &element_ast.attributes, schema)?)
         .duplicity(element_ast.duplicity().map(Into::into).unwrap_or_default())
-        .typing(match &element_ast.item {
-            // element is defined as SimpleType or as type alias
-            ElementItem::WithType(ast::ElementWithType { typing, .. }) => {
-                compile_typing(source, typing, schema)?
-            }
-            // nested element definition
-            ElementItem::WithBlock(ast::ElementWithBlock { block, .. }) => {
-                compile_block(source, block, None, false, None, schema)?.into()
-            }
+        .comments(
+            comments
+                .iter()
+                .chain(&element_ast.attributes.documented().dangling)
+                .map(Into::into)
+                .collect(),
+        )
+        .constraints(compile_constraints(element_ast)?)
+        .rules(element_ast.rules().iter().map(Into::into).collect())
+        .substitutes(element_ast.substitutes().map(ToString::to_string))
+        .typing(match top_level {
+            true => compile_top_level_typing(source, element_ast, schema)?,
+            false => compile_element_typing(source, element_ast, schema)?,
         });
 
     schema.register_element(element_builder.build()?)
 }
 
+pub fn compile_element_typing(
+    source: &SourcedSchemaFile,
+    element_ast: &ast::Element,
+    schema: &mut Schema,
+) -> anyhow::Result<TypeRef> {
+    Ok(match &element_ast.item {
+        // element is defined as SimpleType or as type alias
+        ElementItem::WithType(ast::ElementWithType { typing, .. }) => {
+            compile_typing(source, typing, schema)?
+        }
+        // nested element definition
+        ElementItem::WithBlock(ast::ElementWithBlock { block, .. }) => {
+            compile_block(source, block, None, false, None, schema)?.into()
+        }
+    })
+}
+
+/// typing of a top-level element, shared with the references to the element. The ID of
+/// a block is handed out before compiling it, because the block can refer to the element itself
+pub fn compile_top_level_typing(
+    source: &SourcedSchemaFile,
+    element_ast: &ast::Element,
+    schema: &mut Schema,
+) -> anyhow::Result<TypeRef> {
+    if let Some(typing) = schema.get_element_typing(element_ast.name()) {
+        return Ok(typing.clone());
+    }
+
+    match &element_ast.item {
+        ElementItem::WithType(_) => {
+            let typing = compile_element_typing(source, element_ast, schema)?;
+            schema.register_element_typing(element_ast.name(), typing.clone());
+            Ok(typing)
+        }
+        ElementItem::WithBlock(ast::ElementWithBlock { block, .. }) => {
+            let typing = schema.register_preliminary_element_block(element_ast.name());
+            let compiled = compile_block(source, block, None, false, None, schema);
+
+            // references to the element need a type, also when its block failed
+            let group = match &compiled {
+                Ok(group) => group.clone(),
+                Err(_) => schema.register_group(GroupBuilder::default().build()?)?,
+            };
+            schema.register_preliminary_id_type(typing.schema_object_id(), group.into())?;
+
+            compiled.map(|_| typing)
+        }
+    }
+}
+
+/// a reference to a top-level element is a local element with the attributes and the type of
+/// the top-level element, and its own duplicity
+pub fn compile_element_ref(
+    source: &SourcedSchemaFile,
+    element_ref: &ast::ElementRef,
+    comments: Vec<ast::Comment>,
+    schema: &mut Schema,
+) -> anyhow::Result<Ref<model::Element>> {
+    info!("compiling reference to element '{}'...", element_ref.name());
+
+    let element_ast = source
+        .elements_top_level()
+        .into_iter()
+        .find(|el| el.name() == element_ref.name())
+        .ok_or_else(|| {
+            anyhow!(
+                "ref #{} refers to an element that is not defined in the root of the schema",
+                element_ref.name()
+            )
+        })?;
+
+    let element = model::ElementBuilder::default()
+        .name(element_ref.name().to_string())
+        .attributes(compile_attributes(source, &element_ast.attributes, schema)?)
+        .duplicity(element_ref.mod_dup.as_ref().map(Into::into).unwrap_or_default())
+        .comments(comments.iter().map(Into::into).collect())
+        .typing(compile_top_level_typing(source, element_ast, schema)?)
+        .global_ref(true)
+        .build()?;
+
+    schema.register_element(element)
+}
+
+/// members of a substitution group need a top-level head element, and have to be
+/// usable wherever the head is, so their type derives from the type of the head
+pub fn validate_substitution_groups(schema: &Schema) -> anyhow::Result<()> {
+    for member in schema.get_elements_root() {
+        let Some(head_name) = member.substitutes() else {
+            continue;
+        };
+
+        let head = schema.get_element_root_by_name(head_name).ok_or_else(|| {
+            anyhow!(
+                "#{} substitutes #{}, which is not a top-level element",
+                member.name(),
+                head_name
+            )
+        })?;
+
+        if schema
+            .get_substitutes(member.name())
+            .iter()
+            .any(|substitute| substitute.name() == member.name())
+        {
+            Err(anyhow!(
+                "the substitution group of #{} is circular",
+                member.name()
+            ))?;
+        }
+
+        if !member.typing().derives_from(head.typing(), schema) {
+            Err(anyhow!(
+                "#{} cannot substitute #{}, because its type '{}' does not derive from '{}'",
+                member.name(),
+                head.name(),
+                member.typing().display_name(schema),
+                head.typing().display_name(schema)
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// warnings for the element and attribute names that do not follow the naming convention.
+/// prefixed names belong to another namespace, so those are left alone
+pub fn lint_naming_convention(schema: &Schema) -> Vec<String> {
+    let elements = schema
+        .elements()
+        .values()
+        .map(|el| el.name())
+        .filter(|name| !model::follows_naming_convention(name))
+        .sorted()
+        .dedup()
+        .map(|name| format!("#{}", name));
+
+    let attributes = schema
+        .types_attribute()
+        .values()
+        .map(|attr| attr.name())
+        .filter(|name| model::split_qname(name).0.is_none())
+        .filter(|name| !model::follows_naming_convention(name))
+        .sorted()
+        .dedup()
+        .map(|name| format!("@{}", name));
+
+    elements
+        .chain(attributes)
+        .map(|ident| format!("{} does not follow the naming convention of lowercase names with dashes", ident))
+        .collect()
+}
+
+/// compile the identity constraints of an element.
+/// key references can only refer to keys that are declared on the same element
+pub fn compile_constraints(
+    element_ast: &ast::Element,
+) -> anyhow::Result<Vec<model::IdentityConstraint>> {
+    let constraints = element_ast
+        .constraints()
+        .iter()
+        .map(model::IdentityConstraint::from)
+        .collect_vec();
+
+    let keys = constraints
+        .iter()
+        .filter_map(|constraint| match constraint.kind() {
+            model::ConstraintKind::Key(name) => Some(name),
+            _ => None,
+        })
+        .collect_vec();
+
+    if let Some(duplicate) = keys.iter().duplicates().next() {
+        Err(anyhow!(
+            "key '{}' is declared more than once on #{}",
+            duplicate,
+            element_ast.name()
+        ))?;
+    }
+
+    for constraint in &constraints {
+        if let model::ConstraintKind::KeyRef(key) = constraint.kind()
+            && !keys.contains(&key)
+        {
+            Err(anyhow!(
+                "'{}' on #{} refers to key '{}', which is not declared on the element",
+                constraint,
+                element_ast.name(),
+                key
+            ))?;
+        }
+    }
+
+    Ok(constraints)
+}
+
 pub fn compile_typing_generic(
     source: &SourcedSchemaFile,
     element_ast: &ast::TypeWithGeneric,
@@ -261,13 +600,22 @@ pub fn compile_inline_type(
     element_ast: &ast::TypeDefInline,
     schema: &mut Schema,
 ) -> anyhow::Result<model::TypeRef> {
+    // UserId < BaseId: Int<1000..9999>
+    if let Some(inheritance) = &element_ast.inheritance {
+        return compile_simple_inheritance(source, element_ast, inheritance, schema);
+    }
+
     match &element_ast.typing {
         // Union type: Int | String | "literal"
         TypeDefInlineTyping::Union(union_ast) => {
             compile_type_union(source, union_ast, schema)
         }
-        TypeDefInlineTyping::Typename(typename) => {
-            compile_typename(source, typename, schema)
+        TypeDefInlineTyping::Typename(typename) => match typename.facets {
+            // like `Long: Title<10..50>`, which restricts the base type
+            Some(_) => compile_typename(source, typename, schema).with_context(|| {
+                format!("'{}' cannot restrict '{}'", element_ast.typename, typename.base_name())
+            }),
+            None => compile_typename(source, typename, schema),
         },
         // the element is typed like an attribute
         TypeDefInlineTyping::SimpleType(inlinetype) => {
@@ -295,17 +643,17 @@ pub fn compile_typing_regular(
 
         // alias to other type
         IdentType::NonPrimitive(alias) => {
-            let referred_typedef = source.find_type(&alias).ok_or(anyhow!(
-                "Type definition not found for NonPrimitive '{}'",
-                &alias
-            ))?;
-
-            // if type is already defined with this name, short-circuit and return known ref
-            if let Some(existing) = schema.preliminary_ref_for_typename(referred_typedef, source) {
-                return Ok(existing.get_ref());
+            // core types can be redefined by the schema
+            if alias.as_ref() == CORE_TYPE_EMPTY && source.resolve_type(alias).is_none() {
+                return Ok(schema.register_group(GroupBuilder::default().build()?)?.into());
             }
 
-            compile_type_definition(source, schema, referred_typedef)
+            let (referred_source, referred_typedef) = source.require_type(alias, || {
+                anyhow!("Type definition not found for NonPrimitive '{}'", &alias)
+            })?;
+
+            // returns the known ref when the type is already defined with this name
+            compile_type_definition(&referred_source, schema, referred_typedef)
         }
     }
 }
@@ -341,47 +689,206 @@ pub fn compile_typename(
     let base_type = match &typename.base {
         ast::TypeNameBase::Regular(regulartype) => compile_typing_regular(source, regulartype, schema)?,
         ast::TypeNameBase::Generic(generic_ty) => compile_typing_generic(source, generic_ty, schema)?,
+        ast::TypeNameBase::List(list) => compile_type_list(source, list, schema)?,
     };
 
     // Apply facets if present
     if let Some(facets) = &typename.facets {
-        // Get base primitive type to determine facet interpretation
         if let TypeRef::Simple(simple_ref) = &base_type {
-            let simple_type = simple_ref.resolve(schema);
-            let base_primitive = match simple_type {
-                SimpleType::Builtin { name } => name.clone(),
-                SimpleType::Derived { base, .. } => {
-                    // Get the ultimate base primitive
-                    let mut curr_base = base.resolve(schema);
-                    loop {
-                        match curr_base {
-                            SimpleType::Builtin { name } => break name.clone(),
-                            SimpleType::Derived { base, .. } => {
-                                curr_base = base.resolve(schema);
-                            }
-                            _ => return Err(anyhow!("Facets can only be applied to primitive or derived types"))
-                        }
-                    }
+            Ok(compile_restriction(simple_ref, facets, schema)?.into())
+        } else {
+            Err(anyhow!("Facets can only be applied to simple types, not complex types"))
+        }
+    } else {
+        Ok(base_type)
+    }
+}
+
+/// Derive a simple type from the base with the facets, which can only narrow the values
+/// that the base allows
+pub fn compile_restriction(
+    base: &Ref<SimpleType>,
+    facets: &ast::Facets,
+    schema: &mut Schema,
+) -> anyhow::Result<Ref<SimpleType>> {
+    let base_type = base.resolve(schema);
+
+    // facets of lists restrict the number of items, whatever the items are,
+    // other facets are interpreted by the primitive of the base
+    let restrictions = match base_type.primitive(schema) {
+        _ if base_type.is_list(schema) => compile_list_facets(facets)?,
+        Some(base_primitive) => compile_facets(facets, &base_primitive)?,
+        None => compile_union_facets(facets)?,
+    };
+
+    validate_narrowing(base, &restrictions, schema)?;
+
+    let faceted_type = SimpleType::Derived {
+        base: base.clone(),
+        restrictions,
+        abstract_type: false,
+    };
+
+    schema.register_simple_type(faceted_type)
+}
+
+/// restrictions can only narrow the values that all of the base types in the chain allow
+fn validate_narrowing(
+    base: &Ref<SimpleType>,
+    restrictions: &model::restriction::SimpleTypeRestriction,
+    schema: &Schema,
+) -> anyhow::Result<()> {
+    let mut current = base.resolve(schema);
+
+    loop {
+        match current {
+            SimpleType::Derived {
+                base,
+                restrictions: base_restrictions,
+                ..
+            } => {
+                restrictions.validate_narrows(base_restrictions)?;
+                current = base.resolve(schema);
+            }
+            // the values of unions of literals are known too
+            SimpleType::Union { member_types } => {
+                let literals = member_types
+                    .iter()
+                    .map(|member| member.resolve(schema).restrictions()?.enumeration.clone())
+                    .collect::<Option<Vec<_>>>();
+
+                if let (Some(values), Some(literals)) = (&restrictions.enumeration, literals) {
+                    model::restriction::validate_enumeration(values, &literals.concat())?;
                 }
-                _ => return Err(anyhow!("Facets can only be applied to simple types"))
+
+                return Ok(());
+            }
+            SimpleType::Builtin { .. } | SimpleType::List { .. } => return Ok(()),
+        }
+    }
+}
+
+/// Compile a simple type that restricts another named simple type, like
+/// `UserId < BaseId: Int<1000..9999>` or `Small < Size: "s" | "m"`
+pub fn compile_simple_inheritance(
+    source: &SourcedSchemaFile,
+    typedef: &ast::TypeDefInline,
+    inheritance: &ast::Inheritance,
+    schema: &mut Schema,
+) -> anyhow::Result<model::TypeRef> {
+    info!("compiling simple type inheritance from {:?}...", inheritance.base_type);
+
+    let name = &typedef.typename;
+    let base_name = inheritance.base_type.ident_nonprim()
+        .ok_or_else(|| anyhow!("Base type of '{}' must be a named simple type", name))?;
+
+    let base = match compile_typename(source, &inheritance.base_type, schema)? {
+        TypeRef::Simple(simple_ref) => simple_ref,
+        TypeRef::Group(_) => {
+            return Err(anyhow!(
+                "Simple type '{}' cannot inherit from complex type '{}'. Only blocks can extend blocks.",
+                name,
+                base_name
+            ))
+        }
+    };
+
+    let base_type = base.resolve(schema);
+
+    let restricted = match &typedef.typing {
+        TypeDefInlineTyping::Typename(typename) => {
+            // the typing names the base type, or the primitive of the base type
+            let restates_base = match &typename.base {
+                ast::TypeNameBase::Regular(ast::TypeWithoutGeneric(IdentType::Primitive(prim))) => {
+                    base_type.primitive(schema) == Some(prim.into())
+                }
+                ast::TypeNameBase::Regular(ast::TypeWithoutGeneric(IdentType::NonPrimitive(ident))) => {
+                    ident == base_name
+                }
+                _ => false,
             };
 
-            let restrictions = compile_facets(facets, &base_primitive)?;
+            if !restates_base {
+                return Err(anyhow!(
+                    "'{}' restricts '{}', which is not its base type '{}' or the primitive of it",
+                    name,
+                    typename.base_ident(),
+                    base_name
+                ));
+            }
 
-            // Create a derived type with the facets
-            let faceted_type = SimpleType::Derived {
-                base: simple_ref.clone(),
-                restrictions,
-                abstract_type: false,
+            match &typename.facets {
+                Some(facets) => compile_restriction(&base, facets, schema)
+                    .with_context(|| format!("'{}' cannot restrict '{}'", name, base_name))?,
+                None => schema.register_simple_type(SimpleType::Derived {
+                    base: base.clone(),
+                    restrictions: default(),
+                    abstract_type: false,
+                })?,
+            }
+        }
+        // a union of literals is an enumeration of the values of the base
+        TypeDefInlineTyping::Union(union) => {
+            let values = union
+                .members
+                .iter()
+                .map(|member| match member {
+                    ast::UnionMember::Literal(lit) => Some(lit.unquoted().to_string()),
+                    ast::UnionMember::Number(num) => Some(num.value.to_string()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow!(
+                    "'{}' can only restrict '{}' to literal values, not to a union of types",
+                    name,
+                    base_name
+                ))?;
+
+            compile_enumeration(name, &base, base_name, values, schema)?
+        }
+        // a single literal is an enumeration with one value
+        TypeDefInlineTyping::SimpleType(ast::SimpleTypingInline(items))
+            if matches!(items.as_slice(), [ast::AttrItem::AttrItemStr(_)]) =>
+        {
+            let [ast::AttrItem::AttrItemStr(lit)] = items.as_slice() else {
+                unreachable!()
             };
 
-            Ok(schema.register_simple_type(faceted_type)?.into())
-        } else {
-            Err(anyhow!("Facets can only be applied to simple types, not complex types"))
+            compile_enumeration(name, &base, base_name, vec![lit.unquoted().to_string()], schema)?
         }
-    } else {
-        Ok(base_type)
-    }
+        _ => {
+            return Err(anyhow!(
+                "'{}' can only restrict '{}' with facets or literal values",
+                name,
+                base_name
+            ))
+        }
+    };
+
+    Ok(restricted.into())
+}
+
+/// the restriction of the base to the literal values
+fn compile_enumeration(
+    name: &ast::IdentTypeNonPrimitive,
+    base: &Ref<SimpleType>,
+    base_name: &ast::IdentTypeNonPrimitive,
+    values: Vec<String>,
+    schema: &mut Schema,
+) -> anyhow::Result<Ref<SimpleType>> {
+    let restrictions = model::restriction::SimpleTypeRestriction {
+        enumeration: Some(values),
+        ..default()
+    };
+
+    validate_narrowing(base, &restrictions, schema)
+        .with_context(|| format!("'{}' cannot restrict '{}'", name, base_name))?;
+
+    schema.register_simple_type(SimpleType::Derived {
+        base: base.clone(),
+        restrictions,
+        abstract_type: false,
+    })
 }
 
 // todo: a variable name is the identifier for a type.
@@ -396,6 +903,36 @@ pub fn compile_typing_var(
     todo!("variable subtitution for type definitions")
 }
 
+/// Compile a list type ([Decimal]) of whitespace separated simple values
+pub fn compile_type_list(
+    source: &SourcedSchemaFile,
+    list_ast: &ast::TypeList,
+    schema: &mut Schema,
+) -> anyhow::Result<model::TypeRef> {
+    let ast::TypeList(item) = list_ast;
+
+    let item_type = match compile_typename(source, item, schema)? {
+        TypeRef::Simple(simple_ref) => simple_ref,
+        TypeRef::Group(_) => {
+            return Err(anyhow!(
+                "List items must be simple types. Complex types (blocks) are not allowed in lists."
+            ))
+        }
+    };
+
+    // XSD does not allow lists of lists, the items would be indistinguishable
+    if item_type.resolve(schema).is_list(schema) {
+        return Err(anyhow!("List items cannot be lists themselves, in [{}]", item.base_ident()));
+    }
+
+    Ok(schema
+        .register_simple_type(SimpleType::List {
+            item_type,
+            separator: None,
+        })?
+        .into())
+}
+
 /// Compile a union type (Type1 | Type2 | "literal" | 0)
 pub fn compile_type_union(
     source: &SourcedSchemaFile,
@@ -418,7 +955,7 @@ pub fn compile_type_union(
             }
             ast::UnionMember::Literal(lit) => {
                 // Register literal string as enumeration
-                schema.register_simple_type(SimpleType::static_string(lit, schema))?.into()
+                schema.register_simple_type(SimpleType::static_string(&lit.unquoted(), schema))?.into()
             }
             ast::UnionMember::Var(_) => {
                 return Err(anyhow!(
@@ -449,12 +986,13 @@ pub fn compile_type_union(
     Ok(schema.register_simple_type(SimpleType::Union { member_types })?.into())
 }
 
-pub fn resolve_block_def<'a>(
-    ast: &'a ast::SchemaFile,
-    typedef: &'a ast::TypeDef,
-) -> Option<&'a ast::TypeDefBlock> {
+/// the block that the type definition is or is an alias of, with the schema that defines it
+pub fn resolve_block_def(
+    source: &SourcedSchemaFile,
+    typedef: &ast::TypeDef,
+) -> Option<(SourcedSchemaFile, ast::TypeDefBlock)> {
     match typedef {
-        TypeDef::Block(block) => Some(block),
+        TypeDef::Block(block) => Some((source.clone(), block.clone())),
 
         TypeDef::Inline(inlinedef) => {
             if inlinedef.is_generic() {
@@ -468,13 +1006,14 @@ pub fn resolve_block_def<'a>(
                 }
                 ast::TypeDefInlineTyping::Typename(ty) => match &ty.base {
                     ast::TypeNameBase::Regular(reg) => {
-                        let name = reg.ident_nonprim().unwrap();
-                        let typedef = ast.find_type(name).unwrap();
-                        resolve_block_def(ast, typedef)
+                        let (source, typedef) = source.resolve_type(reg.ident_nonprim()?)?;
+                        resolve_block_def(&source, typedef)
                     }
                     ast::TypeNameBase::Generic(_) => {
                         todo!("generics still unimpl")
                     }
+                    // lists are simple types, not block definitions
+                    ast::TypeNameBase::List(_) => return None,
                 },
                 ast::TypeDefInlineTyping::Var(_) => {
                     todo!("generics still unimpl")
@@ -486,6 +1025,46 @@ pub fn resolve_block_def<'a>(
     }
 }
 
+/// the group of a splatted type. The items are flattened into the block, so the type is
+/// compiled again as an anonymous group, after the items of the types it inherits from
+pub fn compile_splat_type(
+    source: &SourcedSchemaFile,
+    name: &ast::IdentTypeNonPrimitive,
+    schema: &mut Schema,
+) -> anyhow::Result<Ref<model::Group>> {
+    let (source, typedef) = source
+        .require_type(name, || anyhow!("cannot splat '{}', because it is not defined", name))?;
+    let (block_source, blockdef) = resolve_block_def(&source, typedef)
+        .ok_or_else(|| anyhow!("cannot splat '{}', because it is not a block", name))?;
+
+    schema.enter_splat(&source.type_name(typedef))?;
+    let group = compile_splat_block(&block_source, &blockdef, schema);
+    schema.leave_splat();
+
+    group
+}
+
+fn compile_splat_block(
+    source: &SourcedSchemaFile,
+    blockdef: &ast::TypeDefBlock,
+    schema: &mut Schema,
+) -> anyhow::Result<Ref<model::Group>> {
+    let group = compile_block(source, &blockdef.block, None, false, None, schema)?;
+
+    match blockdef.inheritance.as_ref().and_then(|inh| inh.base_type.ident_nonprim()) {
+        None => Ok(group),
+        Some(base) => {
+            let base = compile_splat_type(source, base, schema)?;
+
+            schema.register_group(
+                GroupBuilder::default()
+                    .items(vec![base.into(), group.into()])
+                    .build()?,
+            )
+        }
+    }
+}
+
 pub fn compile_block(
     source: &SourcedSchemaFile,
     block_ast: &ast::Block,
@@ -504,50 +1083,113 @@ pub fn compile_block(
     // call builder setters
     builder
         .ty((&block_ast.mods))
+        .duplicity(block_ast.duplicity.as_ref().map(Into::into).unwrap_or_default())
         .mixed(block_ast.is_mixed_content())
         .abstract_type(is_abstract)
-        .base_type(base_type)
-        .attributes(attributes.unwrap_or_default())
-        .items(
-            block_ast
-                .items
-                .iter()
-                .filter_map(|item| {
-                    Some(match item {
-                        BlockItem::Element(element_item) => {
-                            compile_element(source, element_item, schema).map(Into::into)
-                        }
-                        BlockItem::SplatBlock(block) => {
-                            compile_block(source, block.as_ref(), None, false, None, schema).map(Into::into)
-                        }
-                        BlockItem::SplatType(ast::SplatType(ty)) => ty
-                            .ident_regular()
-                            .ok_or(anyhow!(
-                                "expected splatted type reference to not be generic!"
-                            ))
-                            .and_then(|res| {
-                                source.find_type(res).ok_or(anyhow!(
-                                    "type definition not found for IdentTypeNonPrimitive '{}'",
-                                    &res
-                                ))
-                            })
-                            .and_then(|res| {
-                                resolve_block_def(source, res).ok_or(anyhow!(
-                                    "expected resolved type definition to be a block definition"
-                                ))
-                            })
-                            .and_then(|res| {
-                                compile_block(source, &res.block, None, false, None, schema).map(Into::into)
-                            }),
-                        BlockItem::SplatGenericArg(_) => todo!("splat generic arg not impl yet"),
-                        BlockItem::Comment(txt) => {
-                            schema.push_comment(model::Comment::from(txt));
-                            return None;
-                        }
-                    })
-                })
-                .collect::<anyhow::Result<_>>()?,
-        );
+        .base_type(base_type);
+
+    let documented = block_ast.documented();
+
+    // comments of splats are not about a single element, so they document the block
+    let mut comments = vec![];
+    let mut items = vec![];
+    let mut assertions = vec![];
+
+    for (index, (item, item_comments)) in documented.items.into_iter().enumerate() {
+        let compiled: anyhow::Result<model::GroupItem> = match item {
+            // declared on the group itself, compiled below
+            BlockItem::Attribute(_) => continue,
+            BlockItem::Assertion(assertion) => {
+                // the comments of an assertion explain it
+                assertions.push(model::Assertion::new(
+                    (&assertion.expr).into(),
+                    item_comments.iter().map(Into::into).collect(),
+                ));
+                continue;
+            }
+            BlockItem::Element(element_item) => {
+                // attributes that open a block read like attributes of the block
+                if index == 0 && !element_item.attributes.is_empty() {
+                    let names = element_item
+                        .attributes
+                        .iter()
+                        .map(|attr| format!("@{}", attr.assign.ident))
+                        .join(", ");
+
+                    let message = match element_item.attributes.len() {
+                        1 => format!(
+                            "attribute {} at the start of the block is declared on #{}, the element right below. End it with a comma to declare it on the block",
+                            names,
+                            element_item.name()
+                        ),
+                        _ => format!(
+                            "attributes {} at the start of the block are declared on #{}, the element right below. End them with a comma to declare them on the block",
+                            names,
+                            element_item.name()
+                        ),
+                    };
+
+                    schema.warn(message);
+                }
+
+                if let Some(head) = element_item.substitutes() {
+                    schema.recover::<()>(Err(anyhow!(
+                        "#{} substitutes #{}, but only top-level elements can be part of a substitution group",
+                        element_item.name(),
+                        head
+                    )));
+                    continue;
+                }
+
+                // the block of the element can splat the types that are being splatted
+                if element_item.block().is_some() {
+                    schema.enter_splat_element(element_item.name());
+                }
+
+                let element = compile_element(source, element_item, item_comments, false, schema);
+
+                if element_item.block().is_some() {
+                    schema.leave_splat();
+                }
+
+                element.map(Into::into)
+            }
+            BlockItem::ElementRef(element_ref) => {
+                compile_element_ref(source, element_ref, item_comments, schema).map(Into::into)
+            }
+            BlockItem::SplatBlock(block) => {
+                comments.extend(item_comments);
+                compile_block(source, block.as_ref(), None, false, None, schema).map(Into::into)
+            }
+            BlockItem::SplatType(ast::SplatType(ty)) => {
+                comments.extend(item_comments);
+
+                ty.ident_regular()
+                    .ok_or_else(|| anyhow!("expected splatted type reference to not be generic!"))
+                    .and_then(|name| compile_splat_type(source, name, schema))
+                    .map(Into::into)
+            }
+            BlockItem::SplatGenericArg(_) => todo!("splat generic arg not impl yet"),
+            BlockItem::Comment(_) => unreachable!("comments are attached to the other items"),
+        };
+
+        // an item that fails is reported and left out, so the other items are checked too
+        items.extend(schema.recover(compiled));
+    }
+
+    comments.extend(documented.dangling);
+
+    // attributes above a type definition and those inside its block are declared on the type
+    builder.attributes(
+        attributes
+            .unwrap_or_default()
+            .merge(compile_block_attributes(source, block_ast, schema)?),
+    );
+
+    builder
+        .items(items)
+        .assertions(assertions)
+        .comments(comments.iter().map(Into::into).collect());
 
     Ok(schema.register_group(builder.build()?)?)
 }
@@ -578,18 +1220,19 @@ pub fn compile_element_attributes(
                 // and thus can be ignored
                 if let Some(name) = ty.ident_nonprim() {
                     // lookup the type definition in the schema and retrieve attributes
-                    let ast_attrs = &source
-                        .find_type(name)
-                        .ok_or(anyhow!(
-                            "Type definition not found for IdentTypeNonPrimitive '{}'",
-                            &name
-                        ))?
-                        .attributes();
+                    let (type_source, typedef) = source.require_type(name, || anyhow!(
+                        "Type definition not found for IdentTypeNonPrimitive '{}'",
+                        &name
+                    ))?;
+
+                    let mut type_attrs = compile_attributes(&type_source, &typedef.attributes(), schema)?;
+
+                    if let ast::TypeDef::Block(blockdef) = typedef {
+                        type_attrs = type_attrs.merge(compile_block_attributes(&type_source, &blockdef.block, schema)?);
+                    }
 
                     // parse attributes and merge so that the element attributes override the nested type attributes
-                    return Ok(compile_attributes(source, ast_attrs, schema)?
-                        .merge(attrs)
-                        .into());
+                    return Ok(type_attrs.merge(attrs).into());
                 }
             }
 
@@ -606,14 +1249,57 @@ pub fn compile_element_attributes(
             Typing::Union(_) | Typing::Regex(_) => {}
         },
 
-        // alternatively the element could have an attached inline block
-        // which doesnt allow inline attributes so we can ignore the case
-        ElementItem::WithBlock(_) => {}
+        // alternatively the element could have an attached inline block,
+        // with attributes of its own next to the elements
+        ElementItem::WithBlock(with_block) => {
+            return Ok(compile_block_attributes(source, &with_block.block, schema)?
+                .merge(attrs)
+                .into());
+        }
     }
 
     Ok(attrs.into())
 }
 
+/// compile the attributes declared inside a block, rather than above one of its elements
+pub fn compile_block_attributes(
+    source: &SourcedSchemaFile,
+    block: &ast::Block,
+    schema: &mut Schema,
+) -> anyhow::Result<model::Attributes> {
+    let attrs = block
+        .documented()
+        .items
+        .into_iter()
+        .filter_map(|(item, comments)| match item {
+            BlockItem::Attribute(attr) => {
+                let attribute = parse_attribute(source, attr, &comments, schema);
+                schema.recover(attribute)
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(model::Attributes::new(attrs, schema))
+}
+
+/// attributes can be declared above a definition and inside its block, but only once
+fn validate_unique_attributes(
+    owner: &str,
+    header: &ast::Attributes,
+    block: Option<&ast::Block>,
+) -> anyhow::Result<()> {
+    let names = header
+        .iter()
+        .chain(block.into_iter().flat_map(ast::Block::attributes))
+        .map(|attr| attr.assign.ident.as_ref());
+
+    match names.duplicates().next() {
+        Some(name) => Err(anyhow!("@{} is declared more than once on {}", name, owner)),
+        None => Ok(()),
+    }
+}
+
 /// compile AST attributes into model Attributes
 pub fn compile_attributes(
     source: &SourcedSchemaFile,
@@ -622,9 +1308,14 @@ pub fn compile_attributes(
 ) -> anyhow::Result<model::Attributes> {
     Ok(model::Attributes::new(
         attrs
-            .iter()
-            .map(|attr| parse_attribute(source, attr, schema))
-            .collect::<anyhow::Result<_>>()?,
+            .documented()
+            .items
+            .into_iter()
+            .filter_map(|(attr, comments)| {
+                let attribute = parse_attribute(source, attr, &comments, schema);
+                schema.recover(attribute)
+            })
+            .collect(),
         schema,
     ))
 }
@@ -641,10 +1332,11 @@ pub fn parse_attribute_type_from_primitive_or_alias(
             .into()),
         // type is alias and refers to definition elsewhere
         IdentType::NonPrimitive(alias) => {
-            let referenced_typedef = source.find_type(alias).ok_or(anyhow!(
+            let (source, referenced_typedef) = source.require_type(alias, || anyhow!(
                 "type definition not found in AST for Attribute type: '{}'",
                 alias
             ))?;
+            let source = &source;
 
             match referenced_typedef {
                 TypeDef::Inline(inlinedef) => {
@@ -706,7 +1398,7 @@ pub fn parse_type_from_inline(
                 .into()),
             // static string definition
             AttrItem::AttrItemStr(strval) => Ok(schema
-                .register_simple_type(SimpleType::static_string(strval, schema))?
+                .register_simple_type(SimpleType::static_string(&strval.unquoted(), schema))?
                 .into()),
             _ => unreachable!("typename should not be generic"),
         }
@@ -717,13 +1409,28 @@ pub fn parse_type_from_inline(
 pub fn parse_attribute(
     source: &SourcedSchemaFile,
     attr: &ast::AttrDef,
+    comments: &[ast::Comment],
     schema: &mut Schema,
 ) -> anyhow::Result<Ref<model::Attribute>> {
+    let name = attr.assign.ident.as_ref();
+
+    if let (Some(prefix), _) = model::split_qname(name)
+        && model::namespace_uri(name).is_none()
+    {
+        return Err(anyhow!(
+            "@{} has an unknown namespace prefix '{}', only the {} prefixes are supported",
+            name,
+            prefix,
+            model::known_prefixes()
+        ));
+    }
+
     let mut builder = model::AttributeBuilder::default();
 
     builder
-        .name(attr.assign.ident.as_ref().to_string())
+        .name(name.to_string())
         .required(attr.is_required())
+        .comments(comments.iter().map(Into::into).collect())
         .typing(match &attr.typing {
             None => schema.register_simple_type(default())?, // String by default
             Some(typing) => {
@@ -810,12 +1517,66 @@ pub fn is_independent_block_item(item: &ast::BlockItem) -> bool {
             _ => false,
         },
         BlockItem::SplatGenericArg(_) => false,
-        BlockItem::Comment(_) => true,
+        BlockItem::Assertion(_) | BlockItem::Comment(_) => true,
         // any of the specific branches that were unmatched
         _ => false,
     }
 }
 
+/// Compile the facets of a list, which can only restrict the number of items.
+/// the shorthand works like it does for the length of a string
+pub fn compile_list_facets(
+    facets: &ast::Facets,
+) -> anyhow::Result<model::restriction::SimpleTypeRestriction> {
+    use model::restriction::SimpleTypeRestriction;
+
+    let restriction = compile_facets(facets, &model::PrimitiveType::String)?;
+
+    let item_counts = SimpleTypeRestriction {
+        length: restriction.length,
+        min_length: restriction.min_length,
+        max_length: restriction.max_length,
+        ..Default::default()
+    };
+
+    if restriction != item_counts {
+        let allowed = item_counts.facet_names();
+        let unsupported = restriction
+            .facet_names()
+            .into_iter()
+            .filter(|facet| !allowed.contains(facet))
+            .join(", ");
+
+        return Err(anyhow!(
+            "{} cannot restrict a list. Lists only support the length, minLength and maxLength facets",
+            unsupported
+        ));
+    }
+
+    Ok(restriction)
+}
+
+/// Compile the facets of a union, which can only restrict it to some of its values
+pub fn compile_union_facets(
+    facets: &ast::Facets,
+) -> anyhow::Result<model::restriction::SimpleTypeRestriction> {
+    use model::restriction::SimpleTypeRestriction;
+
+    let restriction = compile_facets(facets, &model::PrimitiveType::String)?;
+
+    let values = SimpleTypeRestriction {
+        pattern: restriction.pattern.clone(),
+        enumeration: restriction.enumeration.clone(),
+        ..Default::default()
+    };
+
+    if restriction != values {
+        return Err(anyhow!("Unions only support the pattern and enumeration facets"));
+    }
+
+    Ok(restriction)
+}
+
 /// Compile facets from AST into SimpleTypeRestriction
 pub fn compile_facets(
     facets: &ast::Facets,
@@ -834,7 +1595,11 @@ pub fn compile_facets(
                     // Apply shorthand based on base type
                     match base_primitive {
                         // String types: shorthand = length constraints
-                        model::PrimitiveType::String => {
+                        model::PrimitiveType::String
+                        | model::PrimitiveType::Token
+                        | model::PrimitiveType::Name
+                        | model::PrimitiveType::NameToken
+                        | model::PrimitiveType::NoColName => {
                             if let Some(min) = &range.min {
                                 if let Some(max) = &range.max {
                                     if min == max {
@@ -911,6 +1676,12 @@ pub fn compile_facets(
                         // Pattern facet (from regex value)
                         "pattern" => restriction.pattern = Some(value),
 
+                        // Enumeration facet, repeated for every value
+                        "enumeration" => restriction
+                            .enumeration
+                            .get_or_insert_with(Vec::new)
+                            .push(value),
+
                         _ => {
                             return Err(anyhow!("Unknown facet name: '{}'", name));
                         }
@@ -920,5 +1691,7 @@ pub fn compile_facets(
         }
     }
 
+    restriction.validate_bounds()?;
+
     Ok(restriction)
 }
@@ -1,7 +1,162 @@
 use crate::model;
+use crate::model::{GetTypeHash, Group, Ref, SimpleType, TypeBor, TypeHash};
+use std::collections::{HashMap, HashSet};
 
 pub trait Exporter {
     type Output;
 
     fn export_schema(self, schema: &model::Schema) -> anyhow::Result<Self::Output>;
 }
+
+/// lookup of the user-defined names of types in a schema.
+///
+/// A reference is named either through its own ID, or because it refers to a definition
+/// that is structurally the same as a named definition
+#[derive(Default)]
+pub(crate) struct TypeNames {
+    /// sorted names of the named simple types, excluding builtins
+    simple: Vec<String>,
+
+    /// sorted names of the named groups
+    groups: Vec<String>,
+
+    /// name by type hash, where the alphabetically first name wins
+    names: HashMap<TypeHash, String>,
+
+    /// whether qualified names of imported types are joined into one identifier,
+    /// like 'dc.Title' => 'DcTitle'
+    identifiers: bool,
+}
+
+impl TypeNames {
+    pub fn new(schema: &model::Schema) -> Self {
+        let mut type_names = schema.all_type_names();
+        type_names.sort();
+        type_names.dedup();
+
+        let mut result = Self::default();
+
+        for name in type_names {
+            match schema.get_type_by_name(name) {
+                Some(TypeBor::Simple(st)) if !st.is_builtin() => {
+                    result.simple.push(name.clone());
+                    result.names.entry(st.id()).or_insert(name.clone());
+                }
+                Some(TypeBor::Group(group)) => {
+                    result.groups.push(name.clone());
+                    result.names.entry(group.id()).or_insert(name.clone());
+                }
+                _ => {}
+            }
+        }
+
+        result
+    }
+
+    /// names that are valid identifiers, for exports that define the types under their name
+    pub fn identifiers(schema: &model::Schema) -> Self {
+        Self {
+            identifiers: true,
+            ..Self::new(schema)
+        }
+    }
+
+    fn output(&self, name: &str) -> String {
+        match self.identifiers && name.contains('.') {
+            true => pascal_case(name),
+            false => name.to_string(),
+        }
+    }
+
+    /// named simple types that are not builtin, sorted by name
+    pub fn simple_types<'s>(&self, schema: &'s model::Schema) -> Vec<(String, &'s SimpleType)> {
+        self.simple
+            .iter()
+            .filter_map(|name| {
+                schema
+                    .get_simpletype_by_name(name)
+                    .map(|st| (self.output(name), st))
+            })
+            .collect()
+    }
+
+    /// named groups, sorted by name
+    pub fn groups<'s>(&self, schema: &'s model::Schema) -> Vec<(String, &'s Group)> {
+        self.groups
+            .iter()
+            .filter_map(|name| schema.get_group_by_name(name).map(|gr| (self.output(name), gr)))
+            .collect()
+    }
+
+    pub fn group(&self, group: &Ref<Group>, schema: &model::Schema) -> Option<String> {
+        schema
+            .get_type_name_for_group(group)
+            .filter(|name| self.groups.contains(name))
+            .or_else(|| self.names.get(&group.resolve(schema).id()).cloned())
+            .map(|name| self.output(&name))
+    }
+
+    /// name of a user-defined simple type. Builtins have no user-defined name
+    pub fn simple_type(&self, st: &Ref<SimpleType>, schema: &model::Schema) -> Option<String> {
+        match st.resolve(schema) {
+            SimpleType::Builtin { .. } => None,
+            resolved => schema
+                .get_type_name_for_simpletype(st)
+                .filter(|name| self.simple.contains(name))
+                .or_else(|| self.names.get(&resolved.id()).cloned())
+                .map(|name| self.output(&name)),
+        }
+    }
+}
+
+/// 'list-item' => 'ListItem'
+pub(crate) fn pascal_case(name: &str) -> String {
+    let result = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    match result.chars().next() {
+        None => "Value".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("V{}", result),
+        _ => result,
+    }
+}
+
+/// whether the top-level element is a root, or has to be global for other elements
+/// that refer to it or substitute it. All of them are roots when none are given
+pub(crate) fn is_declared_globally(
+    element: &model::Element,
+    roots: &[String],
+    schema: &model::Schema,
+) -> bool {
+    roots.is_empty()
+        || roots.contains(element.name())
+        || element.substitutes().is_some()
+        || element.is_substitution_head(schema)
+        || schema
+            .elements()
+            .values()
+            .any(|el| *el.global_ref() && el.name() == element.name())
+}
+
+/// make the name unique within the set by appending a number
+pub(crate) fn unique(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 2;
+
+    while used.contains(&candidate) {
+        candidate = format!("{}{}", name, counter);
+        counter += 1;
+    }
+
+    used.insert(candidate.clone());
+    candidate
+}
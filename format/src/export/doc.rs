@@ -0,0 +1,1159 @@
+use crate::export::{Exporter, TypeNames, facet_values, primitive_name, quote};
+use crate::model;
+use crate::model::{
+    Comment, Duplicity, Element, GetTypeHash, Group, GroupItem, GroupType, Ref, SimpleType,
+    TypeHash, TypeRef,
+};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Documentation exporter - renders a static HTML site for the authors of documents.
+///
+/// - a page per element name, with a section per declaration of that name
+/// - a page per named simple type and block type
+/// - comments are rendered as Markdown
+/// - content models are drawn as nested boxes, attributes as tables with their facets
+/// - every page lists where the element or type is used, and has a search box
+#[derive(Default)]
+pub struct DocExporter {
+    title: String,
+
+    names: TypeNames,
+
+    /// page path by element name
+    element_pages: HashMap<String, String>,
+
+    /// page path by type name
+    type_pages: HashMap<String, String>,
+
+    /// elements and named types that contain an element, by the hash of the element
+    parents: HashMap<TypeHash, BTreeSet<Link>>,
+
+    /// elements, attributes and types that refer to a named type, by type name
+    usages: HashMap<String, BTreeSet<Link>>,
+}
+
+/// the pages of a generated documentation site
+pub struct DocSite {
+    /// page content by path relative to the root of the site
+    pages: BTreeMap<String, String>,
+}
+
+impl DocSite {
+    pub fn pages(&self) -> &BTreeMap<String, String> {
+        &self.pages
+    }
+
+    pub fn save_to_dir(&self, dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&dir)?;
+
+        for (path, content) in &self.pages {
+            std::fs::write(dir.as_ref().join(path), content)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// link to a page of the site
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Link {
+    label: String,
+    href: String,
+}
+
+impl Link {
+    fn html(&self) -> String {
+        format!(
+            "<a href=\"{}\"><code>{}</code></a>",
+            escape(&self.href),
+            escape(&self.label)
+        )
+    }
+}
+
+impl Exporter for DocExporter {
+    type Output = DocSite;
+
+    fn export_schema(mut self, schema: &model::Schema) -> anyhow::Result<Self::Output> {
+        self.names = TypeNames::new(schema);
+
+        let simple_types = self.names.simple_types(schema);
+        let groups = self.names.groups(schema);
+
+        let elements: BTreeMap<&str, Vec<&Element>> = schema
+            .elements()
+            .values()
+            .into_group_map_by(|el| el.name().as_str())
+            .into_iter()
+            .collect();
+
+        let mut used_paths = HashSet::new();
+        for name in elements.keys() {
+            let path = unique_path(format!("element-{}", slug(name)), &mut used_paths);
+            self.element_pages
+                .insert(name.to_string(), format!("{}.html", path));
+        }
+        for name in simple_types
+            .iter()
+            .map(|(n, _)| n)
+            .chain(groups.iter().map(|(n, _)| n))
+        {
+            let path = unique_path(format!("type-{}", slug(name)), &mut used_paths);
+            self.type_pages
+                .insert(name.clone(), format!("{}.html", path));
+        }
+
+        self.index_references(&simple_types, &groups, schema);
+
+        let mut pages = BTreeMap::new();
+        let mut search = vec![];
+
+        for (name, declarations) in &elements {
+            let link = self.element_link(name);
+            let documentation = declarations
+                .iter()
+                .map(|el| summary(el.comments()))
+                .find(|summary| !summary.is_empty())
+                .unwrap_or_default();
+
+            search.push(serde_json::json!({
+                "name": name,
+                "kind": "element",
+                "href": link.href,
+                "summary": documentation,
+            }));
+            pages.insert(link.href, self.element_page(name, declarations, schema)?);
+        }
+
+        for (name, st) in &simple_types {
+            let link = self.type_link(name);
+
+            search.push(serde_json::json!({
+                "name": name,
+                "kind": "simple type",
+                "href": link.href,
+                "summary": summary(schema.get_comments_for_type_name(name)),
+            }));
+            pages.insert(link.href, self.simple_type_page(name, st, schema)?);
+        }
+
+        for (name, group) in &groups {
+            let link = self.type_link(name);
+
+            search.push(serde_json::json!({
+                "name": name,
+                "kind": "block type",
+                "href": link.href,
+                "summary": summary(&group_comments(name, group, schema)),
+            }));
+            pages.insert(link.href, self.group_page(name, group, schema)?);
+        }
+
+        pages.insert(
+            "index.html".to_string(),
+            self.index_page(&elements, &simple_types, &groups, schema)?,
+        );
+        pages.insert(
+            "search-index.js".to_string(),
+            format!(
+                "window.WHAS_SEARCH_INDEX = {};\n",
+                serde_json::to_string_pretty(&search)?
+            ),
+        );
+        pages.insert("search.js".to_string(), SEARCH_SCRIPT.to_string());
+        pages.insert("style.css".to_string(), STYLESHEET.to_string());
+
+        Ok(DocSite { pages })
+    }
+}
+
+impl DocExporter {
+    pub fn with_title(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    fn title(&self) -> &str {
+        if self.title.is_empty() {
+            "Schema"
+        } else {
+            &self.title
+        }
+    }
+
+    fn element_link(&self, name: &str) -> Link {
+        Link {
+            label: format!("<{}>", name),
+            href: self.element_pages[name].clone(),
+        }
+    }
+
+    fn type_link(&self, name: &str) -> Link {
+        Link {
+            label: name.to_string(),
+            href: self.type_pages[name].clone(),
+        }
+    }
+
+    /// collect the "used in" back-references of elements and named types
+    fn index_references(
+        &mut self,
+        simple_types: &[(String, &SimpleType)],
+        groups: &[(String, &Group)],
+        schema: &model::Schema,
+    ) {
+        for element in schema.elements().values() {
+            let link = self.element_link(element.name());
+
+            let type_name = match element.typing() {
+                TypeRef::Group(group) => {
+                    for child in contained_elements(group.resolve(schema), schema) {
+                        self.parents
+                            .entry(child.id())
+                            .or_default()
+                            .insert(link.clone());
+                    }
+
+                    self.names.group(group, schema)
+                }
+                TypeRef::Simple(st) => self.names.simple_type(st, schema),
+            };
+
+            if let Some(name) = type_name {
+                self.usages.entry(name).or_default().insert(link.clone());
+            }
+
+            for attr in element.attributes().get(schema) {
+                if let Some(name) = self.names.simple_type(&attr.typing, schema) {
+                    self.usages.entry(name).or_default().insert(Link {
+                        label: format!("{}/@{}", link.label, attr.name),
+                        href: link.href.clone(),
+                    });
+                }
+            }
+        }
+
+        for (name, group) in groups {
+            let link = self.type_link(name);
+
+            for child in contained_elements(group, schema) {
+                self.parents
+                    .entry(child.id())
+                    .or_default()
+                    .insert(link.clone());
+            }
+
+            if let Some(base) = group.base_type()
+                && let Some(base_name) = self.names.group(base, schema)
+            {
+                self.usages
+                    .entry(base_name)
+                    .or_default()
+                    .insert(link.clone());
+            }
+
+            for attr in group.attributes().get(schema) {
+                if let Some(attr_type) = self.names.simple_type(&attr.typing, schema) {
+                    self.usages.entry(attr_type).or_default().insert(Link {
+                        label: format!("{}/@{}", name, attr.name),
+                        href: link.href.clone(),
+                    });
+                }
+            }
+        }
+
+        for (name, st) in simple_types {
+            let link = self.type_link(name);
+
+            for dependency in st.dependent_on_refs() {
+                if let Some(dependency_name) = self.names.simple_type(dependency, schema)
+                    && dependency_name != *name
+                {
+                    self.usages
+                        .entry(dependency_name)
+                        .or_default()
+                        .insert(link.clone());
+                }
+            }
+        }
+    }
+
+    //
+    // PAGES
+    //
+
+    fn page(&self, title: &str, body: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} - {site}</title>
+<link rel="stylesheet" href="style.css">
+<script src="search-index.js" defer></script>
+<script src="search.js" defer></script>
+</head>
+<body>
+<header>
+<a class="site" href="index.html">{site}</a>
+<div class="search">
+<input type="search" id="search" placeholder="Search elements and types" autocomplete="off">
+<ul id="search-results"></ul>
+</div>
+</header>
+<main>
+{body}</main>
+</body>
+</html>
+"#,
+            title = escape(title),
+            site = escape(self.title()),
+            body = body
+        )
+    }
+
+    fn index_page(
+        &self,
+        elements: &BTreeMap<&str, Vec<&Element>>,
+        simple_types: &[(String, &SimpleType)],
+        groups: &[(String, &Group)],
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let mut body = String::new();
+        writeln!(body, "<h1>{}</h1>", escape(self.title()))?;
+
+        let roots = schema
+            .get_elements_root()
+            .into_iter()
+            .map(|el| el.name())
+            .sorted()
+            .dedup()
+            .collect_vec();
+
+        writeln!(body, "<h2>Document elements</h2>")?;
+        writeln!(body, "<ul class=\"links\">")?;
+        for name in roots {
+            writeln!(body, "<li>{}</li>", self.element_link(name).html())?;
+        }
+        writeln!(body, "</ul>")?;
+
+        writeln!(body, "<h2>Elements</h2>")?;
+        writeln!(body, "<table class=\"overview\">")?;
+        writeln!(
+            body,
+            "<thead><tr><th>Element</th><th>Type</th><th>Description</th></tr></thead>"
+        )?;
+        writeln!(body, "<tbody>")?;
+        for (name, declarations) in elements {
+            let types = declarations
+                .iter()
+                .map(|el| self.element_type_html(el, schema))
+                .sorted()
+                .dedup()
+                .join(", ");
+            let description = declarations
+                .iter()
+                .map(|el| summary(el.comments()))
+                .find(|summary| !summary.is_empty())
+                .unwrap_or_default();
+
+            writeln!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                self.element_link(name).html(),
+                types,
+                escape(&description)
+            )?;
+        }
+        writeln!(body, "</tbody>")?;
+        writeln!(body, "</table>")?;
+
+        writeln!(body, "<h2>Types</h2>")?;
+        writeln!(body, "<table class=\"overview\">")?;
+        writeln!(
+            body,
+            "<thead><tr><th>Type</th><th>Kind</th><th>Definition</th></tr></thead>"
+        )?;
+        writeln!(body, "<tbody>")?;
+        let types = simple_types
+            .iter()
+            .map(|(name, st)| {
+                (
+                    name,
+                    "simple type",
+                    self.simple_type_definition_html(st, schema),
+                )
+            })
+            .chain(
+                groups
+                    .iter()
+                    .map(|(name, group)| (name, "block type", group_summary(group))),
+            )
+            .sorted_by(|a, b| a.0.cmp(b.0));
+        for (name, kind, definition) in types {
+            writeln!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                self.type_link(name).html(),
+                kind,
+                definition
+            )?;
+        }
+        writeln!(body, "</tbody>")?;
+        writeln!(body, "</table>")?;
+
+        Ok(self.page(self.title(), &body))
+    }
+
+    fn element_page(
+        &self,
+        name: &str,
+        declarations: &[&Element],
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let roots = schema
+            .get_elements_root()
+            .into_iter()
+            .map(|el| el.id())
+            .collect::<HashSet<_>>();
+
+        let mut body = String::new();
+        writeln!(
+            body,
+            "<h1>Element <code>{}</code></h1>",
+            escape(&format!("<{}>", name))
+        )?;
+
+        // the occurrence of an element is part of the content model of its parent, so
+        // declarations that only differ in occurrence are documented together
+        let mut merged: Vec<(BTreeSet<Link>, bool, &Element)> = vec![];
+        for element in declarations {
+            let parents = self.parents.get(&element.id()).cloned().unwrap_or_default();
+            let is_root = roots.contains(&element.id());
+
+            match merged.iter_mut().find(|(_, _, other)| {
+                other.typing() == element.typing()
+                    && other.attributes() == element.attributes()
+                    && other.comments() == element.comments()
+            }) {
+                Some((other_parents, other_root, _)) => {
+                    other_parents.extend(parents);
+                    *other_root |= is_root;
+                }
+                None => merged.push((parents, is_root, element)),
+            }
+        }
+
+        // order the declarations by where they are used, so the page is the same on every run
+        let declarations = merged
+            .into_iter()
+            .sorted_by(|(a, _, el_a), (b, _, el_b)| {
+                a.cmp(b).then_with(|| {
+                    self.element_type_html(el_a, schema)
+                        .cmp(&self.element_type_html(el_b, schema))
+                })
+            })
+            .collect_vec();
+
+        if declarations.len() > 1 {
+            writeln!(
+                body,
+                "<p class=\"kind\">declared differently in {} places</p>",
+                declarations.len()
+            )?;
+        }
+
+        for (index, (parents, is_root, element)) in declarations.iter().enumerate() {
+            writeln!(
+                body,
+                "<section class=\"declaration\" id=\"declaration-{}\">",
+                index + 1
+            )?;
+
+            if declarations.len() > 1 {
+                let context = if parents.is_empty() {
+                    "as document element".to_string()
+                } else {
+                    format!("in {}", parents.iter().map(Link::html).join(", "))
+                };
+                writeln!(body, "<h2>Declared {}</h2>", context)?;
+            }
+
+            body.push_str(&markdown(element.comments()));
+
+            writeln!(body, "<dl class=\"properties\">")?;
+            writeln!(
+                body,
+                "<dt>Type</dt><dd>{}</dd>",
+                self.element_type_html(element, schema)
+            )?;
+            if *is_root {
+                writeln!(body, "<dt>Document element</dt><dd>yes</dd>")?;
+            }
+            writeln!(body, "</dl>")?;
+
+            writeln!(body, "<h3>Content model</h3>")?;
+            match element.typing() {
+                TypeRef::Group(group) => {
+                    self.content_model(group.resolve(schema), None, schema, &mut body)?
+                }
+                TypeRef::Simple(st) => {
+                    writeln!(body, "<div class=\"model text\">")?;
+                    writeln!(body, "<div class=\"model-label\">text</div>")?;
+                    writeln!(
+                        body,
+                        "<div class=\"particle\">{}</div>",
+                        self.simple_type_html(st, schema)
+                    )?;
+                    writeln!(body, "</div>")?;
+                }
+            }
+
+            let attributes = element.group_merged_attributes(schema);
+            self.attribute_table(&attributes, "h3", schema, &mut body)?;
+
+            writeln!(body, "<h3>Used in</h3>")?;
+            if parents.is_empty() {
+                writeln!(body, "<p>Only used as document element.</p>")?;
+            } else {
+                link_list(parents, &mut body)?;
+            }
+
+            writeln!(body, "</section>")?;
+        }
+
+        Ok(self.page(&format!("<{}>", name), &body))
+    }
+
+    fn simple_type_page(
+        &self,
+        name: &str,
+        st: &SimpleType,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let mut body = String::new();
+        writeln!(body, "<h1>Type <code>{}</code></h1>", escape(name))?;
+        writeln!(body, "<p class=\"kind\">simple type</p>")?;
+        body.push_str(&markdown(schema.get_comments_for_type_name(name)));
+
+        writeln!(body, "<dl class=\"properties\">")?;
+        writeln!(
+            body,
+            "<dt>Definition</dt><dd>{}</dd>",
+            self.simple_type_definition_html(st, schema)
+        )?;
+        match st {
+            SimpleType::Derived { base, .. } => writeln!(
+                body,
+                "<dt>Base type</dt><dd>{}</dd>",
+                self.simple_type_html(base, schema)
+            )?,
+            SimpleType::List {
+                item_type,
+                separator,
+            } => {
+                writeln!(
+                    body,
+                    "<dt>List of</dt><dd>{}</dd>",
+                    self.simple_type_html(item_type, schema)
+                )?;
+                if let Some(separator) = separator {
+                    writeln!(
+                        body,
+                        "<dt>Separator</dt><dd><code>{}</code></dd>",
+                        escape(separator)
+                    )?;
+                }
+            }
+            SimpleType::Union { member_types } => writeln!(
+                body,
+                "<dt>One of</dt><dd>{}</dd>",
+                member_types
+                    .iter()
+                    .map(|member| self.simple_type_html(member, schema))
+                    .join(", ")
+            )?,
+            SimpleType::Builtin { .. } => {}
+        }
+        writeln!(body, "</dl>")?;
+
+        if let Some(restrictions) = st.restrictions() {
+            let facets = facet_values(restrictions);
+            let (values, facets): (Vec<_>, Vec<_>) = facets
+                .into_iter()
+                .partition(|(facet, _)| *facet == "enumeration");
+
+            if !values.is_empty() {
+                writeln!(body, "<h2>Values</h2>")?;
+                writeln!(body, "<ul class=\"values\">")?;
+                for (_, value) in values {
+                    writeln!(body, "<li><code>{}</code></li>", escape(&value))?;
+                }
+                writeln!(body, "</ul>")?;
+            }
+
+            if !facets.is_empty() {
+                writeln!(body, "<h2>Facets</h2>")?;
+                writeln!(body, "<table class=\"facets\">")?;
+                writeln!(body, "<thead><tr><th>Facet</th><th>Value</th></tr></thead>")?;
+                writeln!(body, "<tbody>")?;
+                for (facet, value) in facets {
+                    writeln!(
+                        body,
+                        "<tr><td>{}</td><td><code>{}</code></td></tr>",
+                        facet,
+                        escape(&value)
+                    )?;
+                }
+                writeln!(body, "</tbody>")?;
+                writeln!(body, "</table>")?;
+            }
+        }
+
+        self.usage_list(name, &mut body)?;
+
+        Ok(self.page(name, &body))
+    }
+
+    fn group_page(
+        &self,
+        name: &str,
+        group: &Group,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let mut body = String::new();
+        writeln!(body, "<h1>Type <code>{}</code></h1>", escape(name))?;
+        writeln!(
+            body,
+            "<p class=\"kind\">{}block type</p>",
+            if group.is_abstract() { "abstract " } else { "" }
+        )?;
+        body.push_str(&markdown(&group_comments(name, group, schema)));
+
+        writeln!(body, "<h2>Content model</h2>")?;
+        self.content_model(group, None, schema, &mut body)?;
+
+        let attributes = inherited_attributes(group, schema);
+        self.attribute_table(&attributes, "h2", schema, &mut body)?;
+
+        self.usage_list(name, &mut body)?;
+
+        Ok(self.page(name, &body))
+    }
+
+    //
+    // FRAGMENTS
+    //
+
+    fn usage_list(&self, type_name: &str, body: &mut String) -> anyhow::Result<()> {
+        writeln!(body, "<h2>Used in</h2>")?;
+
+        match self.usages.get(type_name) {
+            Some(usages) => link_list(usages, body),
+            None => Ok(writeln!(body, "<p>Not used in this schema.</p>")?),
+        }
+    }
+
+    /// draw the content model of a group as nested boxes
+    fn content_model(
+        &self,
+        group: &Group,
+        label: Option<String>,
+        schema: &model::Schema,
+        out: &mut String,
+    ) -> anyhow::Result<()> {
+        let kind = match group.ty() {
+            GroupType::Sequence => "sequence",
+            GroupType::Choice => "choice",
+            GroupType::All => "all",
+        };
+
+        let mut description = kind.to_string();
+        if *group.mixed() {
+            description = format!("mixed {}", description);
+        }
+        if *group.duplicity() != Duplicity::Single {
+            description = format!("{} {}", description, occurs(group.duplicity()));
+        }
+        if let Some(label) = label {
+            description = format!("{}, {}", label, description);
+        }
+
+        writeln!(out, "<div class=\"model {}\">", kind)?;
+        writeln!(out, "<div class=\"model-label\">{}</div>", description)?;
+
+        if let Some(base) = group.base_type() {
+            let label = match self.names.group(base, schema) {
+                Some(base_name) => format!("inherited from {}", self.type_link(&base_name).html()),
+                None => "inherited".to_string(),
+            };
+            self.content_model(base.resolve(schema), Some(label), schema, out)?;
+        }
+
+        if *group.mixed() {
+            writeln!(out, "<div class=\"particle text\">text</div>")?;
+        }
+
+        for item in group.items() {
+            match item {
+                GroupItem::Element(element) => {
+                    let element = element.resolve(schema);
+                    writeln!(
+                        out,
+                        "<div class=\"particle\">{}<span class=\"occurs\">{}</span></div>",
+                        self.element_link(element.name()).html(),
+                        occurs(element.duplicity())
+                    )?;
+                }
+                GroupItem::Group(nested) => {
+                    let label = self
+                        .names
+                        .group(nested, schema)
+                        .map(|name| format!("from {}", self.type_link(&name).html()));
+                    self.content_model(nested.resolve(schema), label, schema, out)?;
+                }
+            }
+        }
+
+        if group.items().is_empty() && group.base_type().is_none() && !*group.mixed() {
+            writeln!(out, "<div class=\"particle empty\">empty</div>")?;
+        }
+
+        writeln!(out, "</div>")?;
+        Ok(())
+    }
+
+    fn attribute_table(
+        &self,
+        attributes: &model::Attributes,
+        heading: &str,
+        schema: &model::Schema,
+        out: &mut String,
+    ) -> anyhow::Result<()> {
+        writeln!(out, "<{0}>Attributes</{0}>", heading)?;
+
+        if attributes.is_empty() {
+            writeln!(out, "<p>No attributes.</p>")?;
+            return Ok(());
+        }
+
+        writeln!(out, "<table class=\"attributes\">")?;
+        writeln!(
+            out,
+            "<thead><tr><th>Name</th><th>Type</th><th>Use</th><th>Default</th><th>Facets</th><th>Description</th></tr></thead>"
+        )?;
+        writeln!(out, "<tbody>")?;
+
+        for attr in attributes
+            .get(schema)
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+        {
+            let facets = attr
+                .typing
+                .resolve(schema)
+                .restrictions()
+                .map(facet_values)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(facet, value)| format!("{}: <code>{}</code>", facet, escape(&value)))
+                .join("<br>");
+
+            writeln!(
+                out,
+                "<tr id=\"attr-{}\"><td><code>@{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&slug(&attr.name)),
+                escape(&attr.name),
+                self.simple_type_html(&attr.typing, schema),
+                if *attr.required() {
+                    "required"
+                } else {
+                    "optional"
+                },
+                attr.default_value
+                    .as_ref()
+                    .map(|value| format!("<code>{}</code>", escape(&quote(value))))
+                    .unwrap_or_default(),
+                facets,
+                markdown(&attr.comments)
+            )?;
+        }
+
+        writeln!(out, "</tbody>")?;
+        writeln!(out, "</table>")?;
+        Ok(())
+    }
+
+    fn element_type_html(&self, element: &Element, schema: &model::Schema) -> String {
+        match element.typing() {
+            TypeRef::Simple(st) => self.simple_type_html(st, schema),
+            TypeRef::Group(group) => match self.names.group(group, schema) {
+                Some(name) => self.type_link(&name).html(),
+                None => "<em>inline block</em>".to_string(),
+            },
+        }
+    }
+
+    /// a simple type reference: a link when it is named, its definition otherwise
+    fn simple_type_html(&self, st: &Ref<SimpleType>, schema: &model::Schema) -> String {
+        match st.resolve(schema) {
+            SimpleType::Builtin { name } => format!("<code>{}</code>", primitive_name(name)),
+            resolved => match self.names.simple_type(st, schema) {
+                Some(name) => self.type_link(&name).html(),
+                None => self.simple_type_definition_html(resolved, schema),
+            },
+        }
+    }
+
+    fn simple_type_definition_html(&self, st: &SimpleType, schema: &model::Schema) -> String {
+        match st {
+            SimpleType::Builtin { name } => format!("<code>{}</code>", primitive_name(name)),
+            SimpleType::Derived {
+                base, restrictions, ..
+            } => match &restrictions.enumeration {
+                Some(values) => values
+                    .iter()
+                    .map(|value| format!("<code>{}</code>", escape(&quote(value))))
+                    .join(" | "),
+                None => format!(
+                    "{}<code>{}</code>",
+                    self.simple_type_html(base, schema),
+                    escape(&format!(
+                        "<{}>",
+                        facet_values(restrictions)
+                            .into_iter()
+                            .map(|(facet, value)| format!("{}: {}", facet, value))
+                            .join(", ")
+                    ))
+                ),
+            },
+            SimpleType::Union { member_types } => member_types
+                .iter()
+                .map(|member| self.simple_type_html(member, schema))
+                .join(" | "),
+            SimpleType::List { item_type, .. } => {
+                format!("[{}]", self.simple_type_html(item_type, schema))
+            }
+        }
+    }
+}
+
+/// elements that can occur directly in the group, including those of its base types
+fn contained_elements<'s>(group: &'s Group, schema: &'s model::Schema) -> Vec<&'s Element> {
+    let mut elements = match group.base_type() {
+        Some(base) => contained_elements(base.resolve(schema), schema),
+        None => vec![],
+    };
+
+    for item in group.items() {
+        match item {
+            GroupItem::Element(element) => elements.push(element.resolve(schema)),
+            GroupItem::Group(nested) => {
+                elements.extend(contained_elements(nested.resolve(schema), schema))
+            }
+        }
+    }
+
+    elements
+}
+
+/// attributes of the group including those of its base types
+fn inherited_attributes(group: &Group, schema: &model::Schema) -> model::Attributes {
+    match group.base_type() {
+        Some(base) => {
+            inherited_attributes(base.resolve(schema), schema).merge(group.attributes().clone())
+        }
+        None => group.attributes().clone(),
+    }
+}
+
+/// comments of a block type: those of its definition followed by those inside its block
+fn group_comments(name: &str, group: &Group, schema: &model::Schema) -> Vec<Comment> {
+    schema
+        .get_comments_for_type_name(name)
+        .iter()
+        .chain(group.comments())
+        .cloned()
+        .collect()
+}
+
+/// short description of a block type for overviews
+fn group_summary(group: &Group) -> String {
+    let mut parts = vec![];
+
+    if group.is_abstract() {
+        parts.push("abstract".to_string());
+    }
+    if *group.mixed() {
+        parts.push("mixed".to_string());
+    }
+    parts.push(group.ty().to_string().to_lowercase());
+    parts.push(match group.items().len() {
+        1 => "of 1 item".to_string(),
+        n => format!("of {} items", n),
+    });
+
+    parts.join(" ")
+}
+
+fn link_list(links: &BTreeSet<Link>, out: &mut String) -> anyhow::Result<()> {
+    writeln!(out, "<ul class=\"links\">")?;
+    for link in links {
+        writeln!(out, "<li>{}</li>", link.html())?;
+    }
+    writeln!(out, "</ul>")?;
+    Ok(())
+}
+
+/// 'min..max' occurrence of an element
+fn occurs(duplicity: &Duplicity) -> String {
+    let min = duplicity.min_occurs();
+
+    match duplicity.max_occurs() {
+        Some(max) if max == min => min.to_string(),
+        Some(max) => format!("{}..{}", min, max),
+        None => format!("{}..∞", min),
+    }
+}
+
+/// render the comments of a schema object as Markdown
+fn markdown(comments: &[Comment]) -> String {
+    let text = comments.iter().map(Comment::content).join("\n");
+
+    if text.trim().is_empty() {
+        return String::new();
+    }
+
+    let mut options = pulldown_cmark::Options::empty();
+    options.insert(pulldown_cmark::Options::ENABLE_TABLES);
+    options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new_ext(&text, options));
+
+    format!("<div class=\"doc\">\n{}</div>\n", html)
+}
+
+/// first line of the comments, for overviews and search results
+fn summary(comments: &[Comment]) -> String {
+    comments
+        .iter()
+        .map(Comment::content)
+        .flat_map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect_vec()
+        })
+        .next()
+        .unwrap_or_default()
+}
+
+/// characters that are safe in file names on all platforms. others are escaped by their code
+/// point, like `_3a_` for `:`, and `_` itself is doubled so different names get different slugs
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c.to_string(),
+            '_' => "__".to_string(),
+            _ => format!("_{:x}_", c as u32),
+        })
+        .collect()
+}
+
+/// make the page path unique, also on file systems that ignore case
+fn unique_path(path: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = path.clone();
+    let mut counter = 2;
+
+    while !used.insert(candidate.to_lowercase()) {
+        candidate = format!("{}{}", path, counter);
+        counter += 1;
+    }
+
+    candidate
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const SEARCH_SCRIPT: &str = r#"(function () {
+    const input = document.getElementById("search");
+    const results = document.getElementById("search-results");
+    const index = window.WHAS_SEARCH_INDEX || [];
+
+    function render(query) {
+        results.innerHTML = "";
+        query = query.trim().toLowerCase();
+        if (!query) {
+            return;
+        }
+
+        const matches = index
+            .filter((entry) => entry.name.toLowerCase().includes(query)
+                || entry.summary.toLowerCase().includes(query))
+            .sort((a, b) => {
+                const aStarts = a.name.toLowerCase().startsWith(query);
+                const bStarts = b.name.toLowerCase().startsWith(query);
+                return aStarts === bStarts ? a.name.localeCompare(b.name) : aStarts ? -1 : 1;
+            })
+            .slice(0, 20);
+
+        for (const entry of matches) {
+            const item = document.createElement("li");
+            const link = document.createElement("a");
+            link.href = entry.href;
+            link.textContent = entry.kind === "element" ? "<" + entry.name + ">" : entry.name;
+            const kind = document.createElement("span");
+            kind.className = "search-kind";
+            kind.textContent = entry.kind;
+            item.append(link, kind);
+            results.append(item);
+        }
+    }
+
+    input.addEventListener("input", () => render(input.value));
+    input.addEventListener("keydown", (event) => {
+        const first = results.querySelector("a");
+        if (event.key === "Enter" && first) {
+            window.location.href = first.href;
+        }
+    });
+})();
+"#;
+
+const STYLESHEET: &str = r#"body {
+    margin: 0;
+    font-family: system-ui, sans-serif;
+    line-height: 1.5;
+    color: #1d2430;
+}
+
+header {
+    display: flex;
+    align-items: center;
+    justify-content: space-between;
+    padding: 0.75rem 2rem;
+    background: #1d3557;
+}
+
+header .site {
+    color: #fff;
+    font-weight: bold;
+    text-decoration: none;
+}
+
+.search {
+    position: relative;
+}
+
+#search {
+    width: 20rem;
+    padding: 0.3rem 0.5rem;
+}
+
+#search-results {
+    position: absolute;
+    right: 0;
+    z-index: 1;
+    min-width: 20rem;
+    margin: 0;
+    padding: 0;
+    list-style: none;
+    background: #fff;
+    box-shadow: 0 2px 6px rgba(0, 0, 0, 0.2);
+}
+
+#search-results li {
+    display: flex;
+    justify-content: space-between;
+    padding: 0.3rem 0.5rem;
+}
+
+.search-kind {
+    color: #6c757d;
+    font-size: 0.85em;
+}
+
+main {
+    max-width: 60rem;
+    padding: 1rem 2rem;
+}
+
+a {
+    color: #1d6fa5;
+}
+
+.kind {
+    color: #6c757d;
+}
+
+section.declaration + section.declaration {
+    border-top: 1px solid #d0d7de;
+}
+
+table {
+    border-collapse: collapse;
+    width: 100%;
+}
+
+th, td {
+    padding: 0.3rem 0.5rem;
+    border: 1px solid #d0d7de;
+    text-align: left;
+    vertical-align: top;
+}
+
+dl.properties {
+    display: grid;
+    grid-template-columns: max-content auto;
+    gap: 0.25rem 1rem;
+}
+
+dl.properties dd {
+    margin: 0;
+}
+
+.model {
+    margin: 0.4rem 0;
+    padding: 0.4rem 0.6rem;
+    border: 1px solid #8fa8c8;
+    border-radius: 4px;
+}
+
+.model.choice {
+    border-style: dashed;
+}
+
+.model-label {
+    color: #6c757d;
+    font-size: 0.85em;
+}
+
+.particle {
+    margin: 0.2rem 0;
+}
+
+.particle.text, .particle.empty {
+    color: #6c757d;
+    font-style: italic;
+}
+
+.occurs {
+    margin-left: 0.5rem;
+    color: #6c757d;
+    font-size: 0.85em;
+}
+"#;
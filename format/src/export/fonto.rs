@@ -1,4 +1,4 @@
-use crate::export::Exporter;
+use crate::export::{Exporter, is_declared_globally};
 use crate::formats::fonto;
 use crate::formats::fonto::FontoSchemaCompilerVersion;
 use crate::model;
@@ -16,6 +16,12 @@ pub struct FontoSchemaExporter {
 
     target_version: FontoSchemaCompilerVersion,
 
+    /// namespace of the elements that have no prefix
+    namespace: Option<String>,
+
+    /// names of the top-level elements that documents can start with, all when empty
+    roots: Vec<String>,
+
     result: fonto::Schema,
 }
 
@@ -32,6 +38,16 @@ impl Exporter for FontoSchemaExporter {
         // go over all elements to export the definitions
         info!("exporting Fonto elements...");
         for el in schema.elements().values() {
+            // references to global elements are not exported as local elements
+            if el.is_local(schema) && (*el.global_ref() || schema.get_substitution_head(el).is_some()) {
+                continue;
+            }
+
+            // like in the XSD, the other top-level elements are left out
+            if !el.is_local(schema) && !is_declared_globally(el, &self.roots, schema) {
+                continue;
+            }
+
             self.export_element(el, schema)?;
         }
 
@@ -57,12 +73,30 @@ impl Exporter for FontoSchemaExporter {
 impl FontoSchemaExporter {
     pub fn with_version(version: FontoSchemaCompilerVersion) -> Self {
         Self {
-            exported_type_ids: Default::default(),
             target_version: version,
-            result: Default::default(),
+            ..Self::default()
         }
     }
 
+    /// put the elements without a prefix in the namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// only export the top-level elements with the names as elements of their own
+    pub fn with_roots(mut self, roots: Vec<String>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// the namespace of the element name, which is that of its prefix if it has one
+    fn namespace_uri(&self, name: &str) -> Option<String> {
+        model::namespace_uri(name)
+            .map(str::to_string)
+            .or_else(|| self.namespace.clone())
+    }
+
     pub fn export_to_file(
         mut self,
         schema: &model::Schema,
@@ -90,13 +124,24 @@ impl FontoSchemaExporter {
                     GroupItem::Element(el) => {
                         let el = el.resolve(schema);
 
-                        // shoujld return existing position because it should have been exported already
-                        let pos = self.export_element(el, schema)?;
-
-                        fonto::ContentModel::LocalElement {
-                            element_ref: pos,
-                            max_occurs: el.max_occurs().map(Into::into),
-                            min_occurs: Some(el.min_occurs().into()),
+                        match schema.get_substitution_head(el) {
+                            Some(head) => self.create_substitution_choice(el, head, schema),
+                            None if *el.global_ref() => fonto::ContentModel::Element {
+                                name: model::local_name(el.name()).to_string(),
+                                namespace_uri: self.namespace_uri(el.name()),
+                                max_occurs: el.max_occurs().map(Into::into),
+                                min_occurs: Some(el.min_occurs().into()),
+                            },
+                            None => {
+                                // shoujld return existing position because it should have been exported already
+                                let pos = self.export_element(el, schema)?;
+
+                                fonto::ContentModel::LocalElement {
+                                    element_ref: pos,
+                                    max_occurs: el.max_occurs().map(Into::into),
+                                    min_occurs: Some(el.min_occurs().into()),
+                                }
+                            }
                         }
                     }
                     GroupItem::Group(gr) => {
@@ -109,18 +154,52 @@ impl FontoSchemaExporter {
         Ok(match st.ty() {
             GroupType::Sequence => fonto::ContentModel::Sequence {
                 items,
-                max_occurs: Some(1.into()),
-                min_occurs: Some(1.into()),
+                max_occurs: st.max_occurs().map(Into::into),
+                min_occurs: Some(st.min_occurs().into()),
             },
+            // a choice that is not repeated explicitly is left optional for the editor
+            GroupType::Choice if *st.duplicity() == model::Duplicity::Single => {
+                fonto::ContentModel::Choice {
+                    items,
+                    max_occurs: None,
+                    min_occurs: Some(0.into()),
+                }
+            }
             GroupType::Choice => fonto::ContentModel::Choice {
                 items,
-                max_occurs: None,
-                min_occurs: Some(0.into()),
+                max_occurs: st.max_occurs().map(Into::into),
+                min_occurs: Some(st.min_occurs().into()),
             },
             GroupType::All => fonto::ContentModel::All { items },
         })
     }
 
+    /// fonto has no substitution groups, so a local use of a head element becomes a choice
+    /// between the global head and its members, with the occurrence of the local element
+    fn create_substitution_choice(
+        &self,
+        el: &model::Element,
+        head: &model::Element,
+        schema: &Schema,
+    ) -> fonto::ContentModel {
+        let items = std::iter::once(head)
+            .chain(schema.get_substitutes(head.name()))
+            .filter(|member| !member.is_abstract(schema))
+            .map(|member| fonto::ContentModel::Element {
+                name: model::local_name(member.name()).to_string(),
+                namespace_uri: self.namespace_uri(member.name()),
+                max_occurs: Some(1.into()),
+                min_occurs: Some(1.into()),
+            })
+            .collect();
+
+        fonto::ContentModel::Choice {
+            items,
+            max_occurs: el.max_occurs().map(Into::into),
+            min_occurs: Some(el.min_occurs().into()),
+        }
+    }
+
     fn export_content_model(
         &mut self,
         st: &model::Group,
@@ -166,15 +245,19 @@ impl FontoSchemaExporter {
         let mut builder = fonto::ElementBuilder::default();
 
         builder
-            .name(st.name().clone())
+            // prefixed elements, like mml:mi, are in the namespace of their prefix
+            .name(model::local_name(st.name()).to_string())
+            .namespace_uri(self.namespace_uri(st.name()))
             .attribute_refs(attrs)
-            // todo: support namespaces
-            // .namespace_uri(todo!())
             .is_mixed(st.is_mixed_content(schema))
             .min_occurs(Some(st.min_occurs().into()))
             .max_occurs(st.max_occurs().map(Into::into));
 
         match st.typing() {
+            // elements without content only have attributes
+            model::TypeRef::Group(gr) if gr.resolve(schema).is_empty_content(schema) => {
+                builder.content_model_ref(self.result.get_content_model_empty_idx());
+            }
             // might be recursively added new
             model::TypeRef::Group(gr) => {
                 builder.content_model_ref(self.export_content_model(gr.resolve(schema), schema)?);
@@ -223,9 +306,9 @@ impl FontoSchemaExporter {
 
         let attr_idx = self.result.push_attribute(
             fonto::AttributeBuilder::default()
-                .name(st.name().clone())
-                // todo: namespace support
-                // .namespace_uri(st.namespace_uri().cloned())
+                // prefixed attributes, like xml:lang, are in the namespace of their prefix
+                .name(model::local_name(st.name()).to_string())
+                .namespace_uri(self.namespace_uri(st.name()))
                 .required(*st.required())
                 .default_value(st.default_value().clone())
                 .simple_type_ref(typeref)
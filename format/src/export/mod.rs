@@ -1,5 +1,10 @@
 mod common;
+mod doc;
 mod fonto;
+mod rust;
+mod schematron;
+mod typescript;
+mod whas;
 mod xsd;
 
-pub use {common::*, fonto::*, xsd::*};
+pub use {common::*, doc::*, fonto::*, rust::*, schematron::*, typescript::*, whas::*, xsd::*};
@@ -0,0 +1,1103 @@
+use crate::export::{pascal_case, unique, Exporter, TypeNames};
+use crate::model;
+use crate::model::restriction::{SimpleTypeRestriction, WhiteSpaceHandling};
+use crate::model::{
+    Duplicity, GetTypeHash, Group, GroupItem, GroupType, PrimitiveType, Ref, SimpleType,
+    TypeHash, TypeRef,
+};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// keywords that cannot be used as plain identifiers in Rust
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Rust source exporter - generates Rust types that (de)serialize documents of the schema.
+///
+/// The generated code uses the serde conventions of quick-xml: attributes are fields
+/// renamed to `@name`, text content is `$text` and element choices are enums in `$value`.
+/// - Groups become structs, with a field per attribute and element
+/// - Choices become enums with a variant per element
+/// - `Duplicity` becomes `Option` or `Vec`
+/// - named simple types become newtypes that validate their facets when constructed,
+///   or enums when they are an enumeration of strings
+///
+/// The generated code depends on `serde` and, when the schema contains patterns, `regex`.
+#[derive(Default)]
+pub struct RustExporter {
+    names: TypeNames,
+
+    /// generated items, in the order they were completed
+    items: Vec<String>,
+
+    /// Rust type names that have been taken
+    used_names: HashSet<String>,
+
+    /// Rust names of generated types for anonymous definitions, so they are generated once
+    anonymous: HashMap<TypeHash, String>,
+}
+
+impl Exporter for RustExporter {
+    type Output = String;
+
+    fn export_schema(mut self, schema: &model::Schema) -> anyhow::Result<Self::Output> {
+        self.names = TypeNames::identifiers(schema);
+
+        let simple_types = self.names.simple_types(schema);
+        let groups = self.names.groups(schema);
+
+        // named types keep their WHAS name, so reserve those first
+        for name in simple_types
+            .iter()
+            .map(|(name, _)| name)
+            .chain(groups.iter().map(|(name, _)| name))
+        {
+            self.used_names.insert(name.clone());
+        }
+
+        for (name, st) in &simple_types {
+            self.simple_type_item(name, st, schema)?;
+        }
+
+        for (name, group) in &groups {
+            self.group_item(name, group, &model::Attributes::default(), schema)?;
+        }
+
+        let roots = schema
+            .get_elements_root()
+            .into_iter()
+            .sorted_by(|a, b| a.name().cmp(b.name()))
+            .collect_vec();
+
+        for root in &roots {
+            self.element_type(root, schema)?;
+        }
+
+        let mut out = String::new();
+        writeln!(out, "// generated from a WHAS schema. Do not edit by hand")?;
+
+        if !self.items.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "use serde::{{Deserialize, Serialize}};")?;
+        }
+
+        for item in &self.items {
+            writeln!(out)?;
+            out.push_str(item);
+        }
+
+        writeln!(out)?;
+        writeln!(out, "/// names of the elements that can be used as document element")?;
+        writeln!(
+            out,
+            "pub const ROOT_ELEMENTS: &[&str] = &[{}];",
+            roots.iter().map(|el| format!("{:?}", el.name())).join(", ")
+        )?;
+
+        Ok(out)
+    }
+}
+
+impl RustExporter {
+    /// reserve a unique Rust type name based on the given name
+    fn reserve_name(&mut self, name: &str) -> String {
+        let base = pascal_case(name);
+        let mut candidate = base.clone();
+        let mut counter = 2;
+
+        while self.used_names.contains(&candidate) {
+            candidate = format!("{}{}", base, counter);
+            counter += 1;
+        }
+
+        self.used_names.insert(candidate.clone());
+        candidate
+    }
+
+    //
+    // SIMPLE TYPES
+    //
+
+    /// Rust type for a simple type reference. Anonymous user-defined types get a
+    /// generated type named after the hint
+    fn simple_type(
+        &mut self,
+        st: &Ref<SimpleType>,
+        hint: &str,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let resolved = st.resolve(schema);
+
+        if let SimpleType::Builtin { name } = resolved {
+            return Ok(primitive_type(name).to_string());
+        }
+
+        if let Some(name) = self.names.simple_type(st, schema) {
+            return Ok(name);
+        }
+
+        // lists do not need a type of their own
+        if let SimpleType::List { item_type, .. } = resolved {
+            return Ok(format!("Vec<{}>", self.simple_type(item_type, hint, schema)?));
+        }
+
+        if let Some(name) = self.anonymous.get(&resolved.id()) {
+            return Ok(name.clone());
+        }
+
+        let name = self.reserve_name(hint);
+        self.anonymous.insert(resolved.id(), name.clone());
+        self.simple_type_item(&name, resolved, schema)?;
+
+        Ok(name)
+    }
+
+    fn simple_type_item(
+        &mut self,
+        name: &str,
+        st: &SimpleType,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        match st {
+            SimpleType::Builtin { name: primitive } => {
+                let item = format!("pub type {} = {};\n", name, primitive_type(primitive));
+                self.items.push(item);
+            }
+            SimpleType::List { item_type, .. } => {
+                let item = format!(
+                    "pub type {} = Vec<{}>;\n",
+                    name,
+                    self.simple_type(item_type, &format!("{}Item", name), schema)?
+                );
+                self.items.push(item);
+            }
+            SimpleType::Union { member_types } => self.union_item(name, member_types, schema)?,
+            SimpleType::Derived {
+                base,
+                restrictions:
+                    SimpleTypeRestriction {
+                        enumeration: Some(values),
+                        ..
+                    },
+                ..
+            } if is_string_like(base.resolve(schema), schema) => {
+                self.enumeration_item(name, values)?
+            }
+            SimpleType::Derived {
+                base, restrictions, ..
+            } => self.newtype_item(name, base, restrictions, schema)?,
+        }
+
+        Ok(())
+    }
+
+    fn enumeration_item(&mut self, name: &str, values: &[String]) -> anyhow::Result<()> {
+        let mut item = String::new();
+        let mut variants = HashSet::new();
+
+        writeln!(
+            item,
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]"
+        )?;
+        writeln!(item, "pub enum {} {{", name)?;
+
+        let variants = values
+            .iter()
+            .map(|value| (value, unique(pascal_case(value), &mut variants)))
+            .collect_vec();
+
+        for (value, variant) in &variants {
+            writeln!(item, "    #[serde(rename = {:?})]", value)?;
+            writeln!(item, "    {},", variant)?;
+        }
+
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::str::FromStr for {} {{", name)?;
+        writeln!(item, "    type Err = String;")?;
+        writeln!(item)?;
+        writeln!(item, "    fn from_str(value: &str) -> Result<Self, Self::Err> {{")?;
+        writeln!(item, "        match value {{")?;
+        for (value, variant) in &variants {
+            writeln!(item, "            {:?} => Ok(Self::{}),", value, variant)?;
+        }
+        writeln!(
+            item,
+            "            _ => Err(format!(\"{{:?}} is not a valid {}\", value)),",
+            name
+        )?;
+        writeln!(item, "        }}")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::fmt::Display for {} {{", name)?;
+        writeln!(
+            item,
+            "    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+        )?;
+        writeln!(item, "        f.write_str(match self {{")?;
+        for (value, variant) in &variants {
+            writeln!(item, "            Self::{} => {:?},", variant, value)?;
+        }
+        writeln!(item, "        }})")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        self.items.push(item);
+
+        Ok(())
+    }
+
+    fn union_item(
+        &mut self,
+        name: &str,
+        member_types: &[Ref<SimpleType>],
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        // a union of string literals is an enumeration
+        let literals = member_types
+            .iter()
+            .map(|member| match member.resolve(schema) {
+                SimpleType::Derived {
+                    base,
+                    restrictions:
+                        SimpleTypeRestriction {
+                            enumeration: Some(values),
+                            ..
+                        },
+                    ..
+                } if is_string_like(base.resolve(schema), schema) => Some(values.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(literals) = literals {
+            return self.enumeration_item(name, &literals.concat());
+        }
+
+        let mut variants = HashSet::new();
+        let mut members = vec![];
+
+        for member in member_types {
+            let variant = unique(self.member_name(member, schema), &mut variants);
+
+            // anonymous member types are named after the union and the member
+            let ty = self.simple_type(member, &format!("{}{}", name, variant), schema)?;
+
+            members.push((variant, ty));
+        }
+
+        // XML values are text, so the members are tried in order by parsing that text
+        let mut item = String::new();
+        writeln!(item, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+        writeln!(item, "#[serde(try_from = \"String\", into = \"String\")]")?;
+        writeln!(item, "pub enum {} {{", name)?;
+        for (variant, ty) in &members {
+            writeln!(item, "    {}({}),", variant, ty)?;
+        }
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::str::FromStr for {} {{", name)?;
+        writeln!(item, "    type Err = String;")?;
+        writeln!(item)?;
+        writeln!(item, "    fn from_str(value: &str) -> Result<Self, Self::Err> {{")?;
+        for (variant, ty) in &members {
+            // any text is a string, so the members after it are never tried
+            if ty == "String" {
+                writeln!(item, "        Ok(Self::{}(value.to_string()))", variant)?;
+                break;
+            }
+
+            writeln!(
+                item,
+                "        if let Ok(member) = {} {{",
+                parse_expr(ty, "value")
+            )?;
+            writeln!(item, "            return Ok(Self::{}(member));", variant)?;
+            writeln!(item, "        }}")?;
+        }
+        if !members.iter().any(|(_, ty)| ty == "String") {
+            writeln!(
+                item,
+                "        Err(format!(\"{{:?}} is not a valid {}\", value))",
+                name
+            )?;
+        }
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::fmt::Display for {} {{", name)?;
+        writeln!(
+            item,
+            "    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+        )?;
+        writeln!(item, "        match self {{")?;
+        for (variant, ty) in &members {
+            writeln!(
+                item,
+                "            Self::{}(member) => f.write_str(&{}),",
+                variant,
+                display_expr(ty, "member")
+            )?;
+        }
+        writeln!(item, "        }}")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        write_string_conversions(&mut item, name)?;
+        self.items.push(item);
+
+        Ok(())
+    }
+
+    /// name of a union member: builtins are named after their WHAS primitive, single literals
+    /// after their value and other types after their name or the type they derive from
+    fn member_name(&self, member: &Ref<SimpleType>, schema: &model::Schema) -> String {
+        if let Some(name) = self.names.simple_type(member, schema) {
+            return pascal_case(&name);
+        }
+
+        match member.resolve(schema) {
+            SimpleType::Builtin { name } => name.to_string(),
+            SimpleType::Derived {
+                restrictions:
+                    SimpleTypeRestriction {
+                        enumeration: Some(values),
+                        ..
+                    },
+                ..
+            } if values.len() == 1 => pascal_case(&values[0]),
+            SimpleType::Derived { base, .. } => self.member_name(base, schema),
+            SimpleType::List { item_type, .. } => {
+                format!("{}List", self.member_name(item_type, schema))
+            }
+            SimpleType::Union { .. } => "Union".to_string(),
+        }
+    }
+
+    /// newtype with a constructor that validates the facets of the restriction
+    fn newtype_item(
+        &mut self,
+        name: &str,
+        base: &Ref<SimpleType>,
+        restrictions: &SimpleTypeRestriction,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        let repr = representation(base.resolve(schema), schema);
+        let base_name = self.names.simple_type(base, schema);
+        let is_string = repr == "String";
+        let is_float = matches!(repr, "f32" | "f64");
+
+        let mut checks = String::new();
+
+        // validate against the named base type first
+        if let Some(base_name) = &base_name {
+            writeln!(checks, "        {}::new(value.clone())?;", base_name)?;
+        }
+
+        if is_string {
+            match restrictions.white_space {
+                Some(WhiteSpaceHandling::Collapse) => writeln!(
+                    checks,
+                    "        let value = value.split_whitespace().collect::<Vec<_>>().join(\" \");"
+                )?,
+                Some(WhiteSpaceHandling::Replace) => writeln!(
+                    checks,
+                    "        let value = value.replace(['\\t', '\\n', '\\r'], \" \");"
+                )?,
+                _ => {}
+            }
+        }
+
+        let length = if is_string {
+            "value.chars().count()"
+        } else {
+            "value.len()"
+        };
+
+        if let Some(n) = restrictions.length {
+            check(&mut checks, &format!("{} == {}", length, n), &format!("length must be {}", n))?;
+        }
+        if let Some(n) = restrictions.min_length {
+            check(&mut checks, &format!("{} >= {}", length, n), &format!("length must be at least {}", n))?;
+        }
+        if let Some(n) = restrictions.max_length {
+            check(&mut checks, &format!("{} <= {}", length, n), &format!("length must be at most {}", n))?;
+        }
+
+        if let Some(pattern) = &restrictions.pattern {
+            writeln!(
+                checks,
+                "        static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();"
+            )?;
+            writeln!(
+                checks,
+                "        let pattern = PATTERN.get_or_init(|| regex::Regex::new({}).unwrap());",
+                raw_string(&format!("^(?:{})$", pattern))
+            )?;
+            check(
+                &mut checks,
+                match is_string {
+                    true => "pattern.is_match(&value)",
+                    false => "pattern.is_match(&value.to_string())",
+                },
+                &format!("value must match /{}/", pattern),
+            )?;
+        }
+
+        if let Some(values) = &restrictions.enumeration {
+            let literals = values.iter().map(|v| literal(v, repr)).join(", ");
+            check(
+                &mut checks,
+                &format!("[{}].contains(&value)", literals),
+                &format!("value must be one of {}", values.join(", ")),
+            )?;
+        }
+
+        let bounds = [
+            (&restrictions.min_inclusive, ">=", "at least"),
+            (&restrictions.max_inclusive, "<=", "at most"),
+            (&restrictions.min_exclusive, ">", "more than"),
+            (&restrictions.max_exclusive, "<", "less than"),
+        ];
+
+        for (bound, op, desc) in bounds {
+            if let Some(bound) = bound {
+                check(
+                    &mut checks,
+                    &format!("value {} {}", op, number_literal(bound, is_float)),
+                    &format!("value must be {} {}", desc, bound),
+                )?;
+            }
+        }
+
+        if let Some(n) = restrictions.total_digits {
+            check(
+                &mut checks,
+                &format!(
+                    "value.to_string().chars().filter(char::is_ascii_digit).count() <= {}",
+                    n
+                ),
+                &format!("value must have at most {} digits", n),
+            )?;
+        }
+        if let Some(n) = restrictions.fraction_digits {
+            check(
+                &mut checks,
+                &format!(
+                    "value.to_string().split('.').nth(1).map_or(0, str::len) <= {}",
+                    n
+                ),
+                &format!("value must have at most {} fraction digits", n),
+            )?;
+        }
+
+        let mut item = String::new();
+        writeln!(item, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+        writeln!(item, "#[serde(try_from = \"{0}\", into = \"{0}\")]", repr)?;
+        writeln!(item, "pub struct {}({});", name, repr)?;
+        writeln!(item)?;
+        writeln!(item, "impl {} {{", name)?;
+        writeln!(item, "    pub fn new(value: {}) -> Result<Self, String> {{", repr)?;
+        item.push_str(&checks);
+        writeln!(item, "        Ok(Self(value))")?;
+        writeln!(item, "    }}")?;
+        writeln!(item)?;
+        writeln!(item, "    pub fn value(&self) -> &{} {{", repr)?;
+        writeln!(item, "        &self.0")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl TryFrom<{}> for {} {{", repr, name)?;
+        writeln!(item, "    type Error = String;")?;
+        writeln!(item)?;
+        writeln!(item, "    fn try_from(value: {}) -> Result<Self, Self::Error> {{", repr)?;
+        writeln!(item, "        Self::new(value)")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl From<{}> for {} {{", name, repr)?;
+        writeln!(item, "    fn from(value: {}) -> Self {{", name)?;
+        writeln!(item, "        value.0")?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::str::FromStr for {} {{", name)?;
+        writeln!(item, "    type Err = String;")?;
+        writeln!(item)?;
+        writeln!(item, "    fn from_str(value: &str) -> Result<Self, Self::Err> {{")?;
+        match is_string {
+            true => writeln!(item, "        Self::new(value.to_string())")?,
+            false => writeln!(item, "        Self::new({}?)", parse_expr(repr, "value"))?,
+        }
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        writeln!(item)?;
+        writeln!(item, "impl std::fmt::Display for {} {{", name)?;
+        writeln!(
+            item,
+            "    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{"
+        )?;
+        writeln!(item, "        f.write_str(&{})", display_expr(repr, "self.0"))?;
+        writeln!(item, "    }}")?;
+        writeln!(item, "}}")?;
+        self.items.push(item);
+
+        Ok(())
+    }
+
+    //
+    // GROUPS
+    //
+
+    /// generate a struct for a group, with additional attributes from the element using it
+    fn group_item(
+        &mut self,
+        name: &str,
+        group: &Group,
+        extra_attributes: &model::Attributes,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        let mut fields = Fields::default();
+        let attributes = inherited_attributes(group, schema).merge(extra_attributes.clone());
+
+        for attr in attributes
+            .get(schema)
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+        {
+            let hint = format!("{}{}", name, pascal_case(&attr.name));
+            let ty = self.simple_type(&attr.typing, &hint, schema)?;
+            let (ty, serde) = if *attr.required() {
+                (ty, String::new())
+            } else {
+                (
+                    format!("Option<{}>", ty),
+                    ", default, skip_serializing_if = \"Option::is_none\"".to_string(),
+                )
+            };
+
+            fields.push(&attr.name, format!("@{}", attr.name), serde, ty);
+        }
+
+        let current = group.id();
+
+        if *group.mixed() {
+            // text and elements can be interleaved, so all content goes into a single list
+            let content = self.content_enum(
+                &format!("{}Content", name),
+                &inherited_items(group, schema),
+                true,
+                current,
+                schema,
+            )?;
+            fields.push(
+                "content",
+                "$value".into(),
+                ", default, skip_serializing_if = \"Vec::is_empty\"".into(),
+                format!("Vec<{}>", content),
+            );
+        } else if *group.ty() == GroupType::Choice {
+            let items = inherited_items(group, schema);
+            let content = self.content_enum(&format!("{}Content", name), &items, false, current, schema)?;
+            fields.push_choice(content, group, &items, schema);
+        } else {
+            for item in inherited_items(group, schema) {
+                self.sequence_item(name, &item, &mut fields, current, schema)?;
+            }
+        }
+
+        let mut out = String::new();
+        writeln!(out, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+        writeln!(out, "pub struct {} {{", name)?;
+        out.push_str(&fields.render());
+        writeln!(out, "}}")?;
+        self.items.push(out);
+
+        Ok(())
+    }
+
+    /// add the fields for an item of a sequence. Nested sequences are flattened into
+    /// the parent, nested choices become a field holding a content enum
+    fn sequence_item(
+        &mut self,
+        parent: &str,
+        item: &GroupItem,
+        fields: &mut Fields,
+        current: TypeHash,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        match item {
+            GroupItem::Element(element) => {
+                let element = element.resolve(schema);
+                let ty = self.element_field_type(element, current, schema)?;
+                let (ty, serde) = wrap_duplicity(ty, element.duplicity());
+                fields.push(element.name(), element.name().to_string(), serde, ty);
+            }
+            GroupItem::Group(nested) => {
+                let group = nested.resolve(schema);
+
+                if *group.ty() == GroupType::Choice {
+                    let items = inherited_items(group, schema);
+                    let name = match self.names.group(nested, schema) {
+                        Some(named) => format!("{}Content", named),
+                        None => format!("{}Choice", parent),
+                    };
+                    let content = self.content_enum(&name, &items, false, current, schema)?;
+                    fields.push_choice(content, group, &items, schema);
+                } else {
+                    for nested_item in inherited_items(group, schema) {
+                        self.sequence_item(parent, &nested_item, fields, current, schema)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// generate an enum with a variant for every element in the items.
+    /// Returns the name of the enum, which is only generated once per set of items
+    fn content_enum(
+        &mut self,
+        name: &str,
+        items: &[GroupItem],
+        mixed: bool,
+        current: TypeHash,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let key = (name, mixed, items).id();
+        if let Some(existing) = self.anonymous.get(&key) {
+            return Ok(existing.clone());
+        }
+
+        let name = self.reserve_name(name);
+        self.anonymous.insert(key, name.clone());
+
+        let mut variants = HashSet::new();
+        let mut out = String::new();
+        writeln!(out, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+        writeln!(out, "pub enum {} {{", name)?;
+
+        for element in flatten_elements(items, schema) {
+            let ty = self.element_field_type(element, current, schema)?;
+            writeln!(out, "    #[serde(rename = {:?})]", element.name())?;
+            writeln!(out, "    {}({}),", unique(pascal_case(element.name()), &mut variants), ty)?;
+        }
+
+        if mixed {
+            writeln!(out, "    #[serde(rename = \"$text\")]")?;
+            writeln!(out, "    {}(String),", unique("Text".to_string(), &mut variants))?;
+        }
+
+        writeln!(out, "}}")?;
+        self.items.push(out);
+
+        Ok(name)
+    }
+
+    /// Rust type of a single occurrence of an element, boxed when it contains the current type
+    fn element_field_type(
+        &mut self,
+        element: &model::Element,
+        current: TypeHash,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let ty = self.element_type(element, schema)?;
+
+        let recursive = match element.typing() {
+            TypeRef::Group(group) => reaches(group.resolve(schema), current, schema),
+            TypeRef::Simple(_) => false,
+        };
+
+        let repeated = matches!(
+            element.duplicity(),
+            Duplicity::Any | Duplicity::Min1 | Duplicity::Custom(_)
+        );
+
+        Ok(if recursive && !repeated {
+            format!("Box<{}>", ty)
+        } else {
+            ty
+        })
+    }
+
+    /// Rust type for the content of an element, generating a struct if the element type is
+    /// anonymous or the element defines attributes of its own
+    fn element_type(
+        &mut self,
+        element: &model::Element,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let own_attributes = !element.attributes().is_empty();
+
+        match element.typing() {
+            TypeRef::Simple(st) if !own_attributes => {
+                self.simple_type(st, &pascal_case(element.name()), schema)
+            }
+            TypeRef::Simple(st) => {
+                let key = element.id();
+                if let Some(existing) = self.anonymous.get(&key) {
+                    return Ok(existing.clone());
+                }
+
+                let name = self.reserve_name(element.name());
+                self.anonymous.insert(key, name.clone());
+
+                let ty = self.simple_type(st, &format!("{}Value", name), schema)?;
+                let mut fields = Fields::default();
+
+                for attr in element
+                    .attributes()
+                    .get(schema)
+                    .into_iter()
+                    .sorted_by(|a, b| a.name.cmp(&b.name))
+                {
+                    let hint = format!("{}{}", name, pascal_case(&attr.name));
+                    let attr_ty = self.simple_type(&attr.typing, &hint, schema)?;
+                    let (attr_ty, serde) = if *attr.required() {
+                        (attr_ty, String::new())
+                    } else {
+                        (
+                            format!("Option<{}>", attr_ty),
+                            ", default, skip_serializing_if = \"Option::is_none\"".to_string(),
+                        )
+                    };
+                    fields.push(&attr.name, format!("@{}", attr.name), serde, attr_ty);
+                }
+
+                fields.push("value", "$text".into(), String::new(), ty);
+
+                let mut out = String::new();
+                writeln!(out, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+                writeln!(out, "pub struct {} {{", name)?;
+                out.push_str(&fields.render());
+                writeln!(out, "}}")?;
+                self.items.push(out);
+
+                Ok(name)
+            }
+            TypeRef::Group(group) => {
+                if !own_attributes && let Some(name) = self.names.group(group, schema) {
+                    return Ok(name);
+                }
+
+                let resolved = group.resolve(schema);
+                let key = if own_attributes {
+                    element.id()
+                } else {
+                    resolved.id()
+                };
+
+                if let Some(existing) = self.anonymous.get(&key) {
+                    return Ok(existing.clone());
+                }
+
+                let name = self.reserve_name(element.name());
+                self.anonymous.insert(key, name.clone());
+                self.group_item(&name, resolved, element.attributes(), schema)?;
+
+                Ok(name)
+            }
+        }
+    }
+}
+
+/// fields of a generated struct
+#[derive(Default)]
+struct Fields {
+    /// field name, serde rename, additional serde attributes, Rust type
+    fields: Vec<(String, String, String, String)>,
+    names: HashSet<String>,
+}
+
+impl Fields {
+    fn push(&mut self, name: &str, rename: String, serde: String, ty: String) {
+        let name = unique(field_name(name), &mut self.names);
+        self.fields.push((name, rename, serde, ty));
+    }
+
+    /// add a field holding the choice between items, with the occurrence derived from the items
+    fn push_choice(
+        &mut self,
+        content: String,
+        choice: &Group,
+        items: &[GroupItem],
+        schema: &model::Schema,
+    ) {
+        let elements = flatten_elements(items, schema);
+
+        // the choice itself can be made more than once, like `?{ .. }*`
+        let repeated = choice.max_occurs() != Some(1)
+            || elements.iter().any(|el| {
+                matches!(
+                    el.duplicity(),
+                    Duplicity::Any | Duplicity::Min1 | Duplicity::Custom(_)
+                )
+            });
+        let optional = choice.min_occurs() == 0 || elements.iter().any(|el| el.min_occurs() == 0);
+
+        let (ty, serde) = if repeated {
+            (
+                format!("Vec<{}>", content),
+                ", default, skip_serializing_if = \"Vec::is_empty\"",
+            )
+        } else if optional {
+            (
+                format!("Option<{}>", content),
+                ", default, skip_serializing_if = \"Option::is_none\"",
+            )
+        } else {
+            (content, "")
+        };
+
+        self.push("content", "$value".into(), serde.into(), ty);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        for (name, rename, serde, ty) in &self.fields {
+            let _ = writeln!(out, "    #[serde(rename = {:?}{})]", rename, serde);
+            let _ = writeln!(out, "    pub {}: {},", name, ty);
+        }
+
+        out
+    }
+}
+
+/// wrap the type of a single occurrence according to the duplicity. Returns the type and the
+/// additional serde attributes
+fn wrap_duplicity(ty: String, duplicity: &Duplicity) -> (String, String) {
+    match duplicity {
+        Duplicity::Single => (ty, String::new()),
+        Duplicity::Optional => (
+            format!("Option<{}>", ty),
+            ", default, skip_serializing_if = \"Option::is_none\"".into(),
+        ),
+        Duplicity::Any | Duplicity::Min1 | Duplicity::Custom(_) => (
+            format!("Vec<{}>", ty),
+            ", default, skip_serializing_if = \"Vec::is_empty\"".into(),
+        ),
+    }
+}
+
+/// items of the group preceded by those of its base types
+fn inherited_items(group: &Group, schema: &model::Schema) -> Vec<GroupItem> {
+    let mut items = match group.base_type() {
+        Some(base) => inherited_items(base.resolve(schema), schema),
+        None => vec![],
+    };
+    items.extend(group.items().iter().cloned());
+    items
+}
+
+/// attributes of the group including those of its base types
+fn inherited_attributes(group: &Group, schema: &model::Schema) -> model::Attributes {
+    match group.base_type() {
+        Some(base) => inherited_attributes(base.resolve(schema), schema)
+            .merge(group.attributes().clone()),
+        None => group.attributes().clone(),
+    }
+}
+
+/// all elements in the items, including those of nested groups
+fn flatten_elements<'a>(items: &[GroupItem], schema: &'a model::Schema) -> Vec<&'a model::Element> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            GroupItem::Element(element) => vec![element.resolve(schema)],
+            GroupItem::Group(group) => {
+                flatten_elements(&inherited_items(group.resolve(schema), schema), schema)
+            }
+        })
+        .collect()
+}
+
+/// whether the group contains the target group, directly or through its elements
+fn reaches(group: &Group, target: TypeHash, schema: &model::Schema) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![group];
+
+    while let Some(group) = stack.pop() {
+        let hash = group.id();
+
+        if hash == target {
+            return true;
+        }
+
+        if !visited.insert(hash) {
+            continue;
+        }
+
+        if let Some(base) = group.base_type() {
+            stack.push(base.resolve(schema));
+        }
+
+        for item in group.items() {
+            match item {
+                GroupItem::Element(element) => {
+                    if let TypeRef::Group(child) = element.resolve(schema).typing() {
+                        stack.push(child.resolve(schema));
+                    }
+                }
+                GroupItem::Group(nested) => stack.push(nested.resolve(schema)),
+            }
+        }
+    }
+
+    false
+}
+
+/// the Rust type used for a primitive
+fn primitive_type(primitive: &PrimitiveType) -> &'static str {
+    match primitive {
+        PrimitiveType::Bool => "bool",
+        PrimitiveType::Int | PrimitiveType::IntNeg | PrimitiveType::IntPos => "i64",
+        PrimitiveType::IntNonNeg | PrimitiveType::UnsignedLong => "u64",
+        PrimitiveType::Float => "f32",
+        PrimitiveType::Double | PrimitiveType::Decimal => "f64",
+        PrimitiveType::Short => "i16",
+        PrimitiveType::IDRefs | PrimitiveType::NameTokens => "Vec<String>",
+        PrimitiveType::String
+        | PrimitiveType::URI
+        | PrimitiveType::DateTimestamp
+        | PrimitiveType::DateTime
+        | PrimitiveType::Date
+        | PrimitiveType::Time
+        | PrimitiveType::Duration
+        | PrimitiveType::IDRef
+        | PrimitiveType::ID
+        | PrimitiveType::Lang
+        | PrimitiveType::NoColName
+        | PrimitiveType::Token
+        | PrimitiveType::NameToken
+        | PrimitiveType::Name
+        | PrimitiveType::Base64Binary
+        | PrimitiveType::AnySimpleType => "String",
+    }
+}
+
+/// the Rust type that stores the value of a derived simple type
+fn representation(st: &SimpleType, schema: &model::Schema) -> &'static str {
+    match st {
+        SimpleType::Builtin { name } => primitive_type(name),
+        SimpleType::Derived { base, .. } => representation(base.resolve(schema), schema),
+        SimpleType::Union { .. } | SimpleType::List { .. } => "String",
+    }
+}
+
+fn is_string_like(st: &SimpleType, schema: &model::Schema) -> bool {
+    representation(st, schema) == "String"
+}
+
+/// expression that parses the string variable into the given type, resulting in a `Result`
+fn parse_expr(ty: &str, var: &str) -> String {
+    match ty.strip_prefix("Vec<").and_then(|inner| inner.strip_suffix('>')) {
+        Some(inner) => format!(
+            "{}.split_whitespace().map(|item| {}).collect::<Result<Vec<_>, String>>()",
+            var,
+            parse_expr(inner, "item")
+        ),
+        None if ty == "String" => format!("Ok({}.to_string())", var),
+        None => format!("{}.parse::<{}>().map_err(|e| e.to_string())", var, ty),
+    }
+}
+
+/// expression that formats the variable of the given type as XML text
+fn display_expr(ty: &str, var: &str) -> String {
+    if ty.starts_with("Vec<") {
+        format!(
+            "{}.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(\" \")",
+            var
+        )
+    } else {
+        format!("{}.to_string()", var)
+    }
+}
+
+/// conversions used by serde to (de)serialize a type through its text
+fn write_string_conversions(out: &mut String, name: &str) -> std::fmt::Result {
+    writeln!(out)?;
+    writeln!(out, "impl TryFrom<String> for {} {{", name)?;
+    writeln!(out, "    type Error = String;")?;
+    writeln!(out)?;
+    writeln!(out, "    fn try_from(value: String) -> Result<Self, Self::Error> {{")?;
+    writeln!(out, "        value.parse()")?;
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "impl From<{}> for String {{", name)?;
+    writeln!(out, "    fn from(value: {}) -> Self {{", name)?;
+    writeln!(out, "        value.to_string()")?;
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")
+}
+
+/// write a validation that returns an error when the condition does not hold
+fn check(out: &mut String, condition: &str, message: &str) -> std::fmt::Result {
+    writeln!(out, "        if !({}) {{", condition)?;
+    writeln!(out, "            return Err(format!(\"{{:?}}: {}\", value));", message.replace('{', "{{").replace('}', "}}").replace('\\', "\\\\").replace('"', "\\\""))?;
+    writeln!(out, "        }}")
+}
+
+/// literal of a value in the given representation
+fn literal(value: &str, repr: &str) -> String {
+    match repr {
+        "String" => format!("{:?}.to_string()", value),
+        "f32" | "f64" => number_literal(value, true),
+        _ => value.to_string(),
+    }
+}
+
+fn number_literal(value: &str, is_float: bool) -> String {
+    if is_float && !value.contains(['.', 'e', 'E']) {
+        format!("{}.0", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// raw string literal with enough hashes to contain the value
+fn raw_string(value: &str) -> String {
+    let mut hashes = String::new();
+
+    while value.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+
+    format!("r{0}\"{1}\"{0}", hashes, value)
+}
+
+/// 'num-doors' => 'num_doors'
+fn field_name(name: &str) -> String {
+    let mut result = String::new();
+
+    for (idx, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if idx > 0 && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            result.push(c);
+        } else if !result.ends_with('_') {
+            result.push('_');
+        }
+    }
+
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+
+    match result.as_str() {
+        // these keywords cannot be raw identifiers
+        "self" | "crate" | "super" => format!("{}_", result),
+        keyword if RUST_KEYWORDS.contains(&keyword) => format!("r#{}", result),
+        _ => result,
+    }
+}
@@ -0,0 +1,258 @@
+use crate::export::Exporter;
+use crate::export::xsd::{ElementExt, write_document};
+use crate::model;
+use anyhow::Result;
+use crate::model::TypeHash;
+use std::collections::{BTreeMap, HashSet};
+use xmltree::{Element, XMLNode};
+
+pub(crate) const SCHEMATRON_NAMESPACE: &str = "http://purl.oclc.org/dsdl/schematron";
+
+/// Schematron exporter - exports the rules declared on elements and the assertions of a schema
+/// as ISO Schematron, for editors like Fonto and for validators that do not support XSD 1.1.
+///
+/// - rules have the element, or the descendants at their path below it, as their context
+/// - assertions have the elements with the group as their content as their context, and the
+///   comments of an assertion are its message
+///
+/// The context of an element is its path from the document element, so that elements with
+/// the same name and another type are not checked. Below a recursive element the path
+/// continues at any depth, like `/book/section//section/title`.
+///
+/// Every context gets a pattern of its own, so that a node matching multiple contexts is
+/// checked by all of them. The tests are XPath 2.0.
+///
+/// With a namespace, the elements of the contexts and assertions get the `tns:` prefix,
+/// which the tests of rules have to use too.
+#[derive(Default)]
+pub struct SchematronExporter {
+    /// namespace of the elements of the schema
+    namespace: Option<String>,
+}
+
+impl SchematronExporter {
+    pub fn with_namespace(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: Some(namespace.into()),
+        }
+    }
+
+    /// the prefix of the elements in the XPaths
+    fn prefix(&self) -> Option<&str> {
+        self.namespace.as_ref().map(|_| model::TARGET_PREFIX)
+    }
+}
+
+impl Exporter for SchematronExporter {
+    type Output = String;
+
+    fn export_schema(self, schema: &model::Schema) -> Result<Self::Output> {
+        let prefix = self.prefix();
+        let mut contexts: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+        let mut add = |context: String, test: String, message: String| {
+            let asserts = contexts.entry(context).or_default();
+
+            if !asserts.contains(&(test.clone(), message.clone())) {
+                asserts.push((test, message));
+            }
+        };
+
+        for (element, context) in element_contexts(schema, prefix) {
+            for rule in element.rules() {
+                add(
+                    rule.context_xpath(&context, prefix),
+                    rule.test().clone(),
+                    rule.message().clone(),
+                );
+            }
+
+            if let Some(group) = element.typing().grouptype(schema) {
+                for assertion in group.merged_assertions(schema) {
+                    add(context.clone(), assertion.xpath_in(prefix), assertion.message());
+                }
+            }
+        }
+
+        let mut root = Element::new("schema")
+            .with_prefix("sch")
+            .with_attr("xmlns:sch", SCHEMATRON_NAMESPACE)
+            .with_attr("queryBinding", "xslt2");
+
+        if let Some(namespace) = &self.namespace {
+            root = root.with_child(namespace_binding(model::TARGET_PREFIX, namespace));
+        }
+
+        // tests can use prefixed attributes, the xml prefix is bound without a declaration
+        for (prefix, uri, _) in model::KNOWN_NAMESPACES {
+            if prefix != "xml" && schema.uses_namespace(uri) {
+                root = root.with_child(namespace_binding(prefix, uri));
+            }
+        }
+
+        for (context, asserts) in contexts {
+            root = root.with_child(pattern(&context, asserts));
+        }
+
+        write_document(&root)
+    }
+}
+
+/// sch:ns that binds the prefix in the XPaths to the namespace
+pub(crate) fn namespace_binding(prefix: &str, uri: &str) -> Element {
+    Element::new("sch:ns")
+        .with_attr("prefix", prefix)
+        .with_attr("uri", uri)
+}
+
+/// patterns for the rules declared on an element, to embed them in another schema. The
+/// rules get a context for every place the element occurs in
+pub(crate) fn element_patterns(
+    element: &model::Element,
+    schema: &model::Schema,
+    prefix: Option<&str>,
+) -> Vec<Element> {
+    let mut contexts: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+    if element.rules().is_empty() {
+        return vec![];
+    }
+
+    for (_, context) in element_contexts(schema, prefix)
+        .into_iter()
+        .filter(|(occurrence, _)| *occurrence == element)
+    {
+        for rule in element.rules() {
+            contexts
+                .entry(rule.context_xpath(&context, prefix))
+                .or_default()
+                .push((rule.test().clone(), rule.message().clone()));
+        }
+    }
+
+    contexts
+        .into_iter()
+        .map(|(context, asserts)| pattern(&context, asserts))
+        .collect()
+}
+
+/// sch:pattern with a single rule for the context, with an assert per test and message
+fn pattern(context: &str, asserts: Vec<(String, String)>) -> Element {
+    let mut rule = Element::new("sch:rule").with_attr("context", context);
+
+    for (test, message) in asserts {
+        let mut assert = Element::new("sch:assert").with_attr("test", test);
+        assert.children.push(XMLNode::Text(message));
+        rule = rule.with_child(assert);
+    }
+
+    Element::new("sch:pattern").with_child(rule)
+}
+
+/// the elements of the schema with the XPath pattern of every place they occur in, from the
+/// top-level elements down
+pub(crate) fn element_contexts<'a>(
+    schema: &'a model::Schema,
+    prefix: Option<&str>,
+) -> Vec<(&'a model::Element, String)> {
+    let mut walk = ContextWalk {
+        schema,
+        prefix,
+        ancestors: vec![],
+        recursions: HashSet::new(),
+        contexts: vec![],
+    };
+
+    let mut roots = schema.get_elements_root();
+    roots.sort_by_key(|element| element.name());
+
+    for element in roots {
+        walk.visit(element, format!("/{}", model::qualify(element.name(), prefix)));
+    }
+
+    walk.contexts
+}
+
+struct ContextWalk<'a, 'p> {
+    schema: &'a model::Schema,
+    prefix: Option<&'p str>,
+
+    /// the types of the elements above the current one, with their context
+    ancestors: Vec<(TypeHash, String)>,
+
+    /// contexts that continue at any depth below a recursive element, which are walked once
+    recursions: HashSet<String>,
+
+    contexts: Vec<(&'a model::Element, String)>,
+}
+
+impl<'a> ContextWalk<'a, '_> {
+    fn visit(&mut self, element: &'a model::Element, context: String) {
+        let hash = element.typing().typehash(self.schema);
+
+        // the content of a recursive element repeats the content of its ancestor, at any depth
+        if let Some((_, ancestor)) = self.ancestors.iter().find(|(known, _)| *known == hash) {
+            let context = format!(
+                "{}//{}",
+                ancestor,
+                model::qualify(element.name(), self.prefix)
+            );
+
+            if self.recursions.insert(context.clone()) {
+                self.visit_content(element, hash, context);
+            }
+            return;
+        }
+
+        self.visit_content(element, hash, context);
+    }
+
+    fn visit_content(&mut self, element: &'a model::Element, hash: TypeHash, context: String) {
+        self.contexts.push((element, context.clone()));
+
+        let Some(group) = element.typing().grouptype(self.schema) else {
+            return;
+        };
+
+        self.ancestors.push((hash, context.clone()));
+
+        for child in child_elements(group, self.schema) {
+            let child_context = format!(
+                "{}/{}",
+                context,
+                model::qualify(child.name(), self.prefix)
+            );
+            self.visit(child, child_context);
+        }
+
+        self.ancestors.pop();
+    }
+}
+
+/// the elements that can be children of the group, from nested groups, the base type and
+/// substitution groups too
+fn child_elements<'a>(group: &'a model::Group, schema: &'a model::Schema) -> Vec<&'a model::Element> {
+    let mut children = vec![];
+
+    for item in group.items() {
+        match item {
+            model::GroupItem::Element(element) => {
+                let element = element.resolve(schema);
+                children.push(element);
+
+                if let Some(head) = schema.get_substitution_head(element) {
+                    children.extend(schema.get_substitutes(head.name()));
+                }
+            }
+            model::GroupItem::Group(nested) => {
+                children.extend(child_elements(nested.resolve(schema), schema))
+            }
+        }
+    }
+
+    if let Some(base) = group.base_type() {
+        children.extend(child_elements(base.resolve(schema), schema));
+    }
+
+    children
+}
@@ -0,0 +1,509 @@
+use crate::export::{Exporter, TypeNames, pascal_case, unique};
+use crate::model;
+use crate::model::restriction::SimpleTypeRestriction;
+use crate::model::{
+    Duplicity, GetTypeHash, Group, GroupItem, GroupType, PrimitiveType, Ref, SimpleType, TypeHash,
+    TypeRef,
+};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// TypeScript exporter - generates type declarations for code that works with documents
+/// of the schema, like Fonto operations and selectors.
+///
+/// Values are typed as they appear in the DOM, so numbers are `${number}` strings
+/// and enumerations are unions of string literals.
+/// - named simple types become type aliases
+/// - groups become interfaces with a property per attribute (`"@name"`) and element
+/// - `Elements` and `ElementAttributes` map element names to their content and attributes,
+///   with `ElementName`, `AttributeName` and `AttributeValue` helpers on top
+#[derive(Default)]
+pub struct TypescriptExporter {
+    names: TypeNames,
+
+    /// generated declarations, in the order they were completed
+    items: Vec<String>,
+
+    /// interface names that have been taken
+    used_names: HashSet<String>,
+
+    /// names of interfaces generated for anonymous groups and elements with own attributes
+    anonymous: HashMap<TypeHash, String>,
+}
+
+impl Exporter for TypescriptExporter {
+    type Output = String;
+
+    fn export_schema(mut self, schema: &model::Schema) -> anyhow::Result<Self::Output> {
+        self.names = TypeNames::identifiers(schema);
+
+        let simple_types = self.names.simple_types(schema);
+        let groups = self.names.groups(schema);
+
+        let taken = simple_types.iter().map(|(name, _)| name);
+        self.used_names
+            .extend(taken.chain(groups.iter().map(|(name, _)| name)).cloned());
+
+        for (name, st) in &simple_types {
+            let mut item = String::new();
+            let facets = restriction_comment(st);
+
+            if !facets.is_empty() {
+                writeln!(item, "/** {} */", facets)?;
+            }
+
+            writeln!(
+                item,
+                "export type {} = {};",
+                name,
+                self.simple_type_definition(st, schema)
+            )?;
+            self.items.push(item);
+        }
+
+        for (name, group) in &groups {
+            self.interface(name, group, &model::Attributes::default(), schema)?;
+        }
+
+        // element and attribute maps for all elements, including local ones
+        let mut element_types: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        let mut element_attributes: BTreeMap<&str, BTreeMap<String, (BTreeSet<String>, bool)>> =
+            BTreeMap::new();
+
+        for element in schema.elements().values() {
+            let ty = self.element_type(element, schema)?;
+            element_types.entry(element.name()).or_default().insert(ty);
+
+            let attributes = element_attributes.entry(element.name()).or_default();
+            for attr in element.group_merged_attributes(schema).get(schema) {
+                let ty = self.simple_type(&attr.typing, schema);
+                let entry = attributes
+                    .entry(attr.name.clone())
+                    .or_insert((BTreeSet::new(), true));
+                entry.0.insert(ty);
+                entry.1 &= *attr.required();
+            }
+        }
+
+        let roots = schema
+            .get_elements_root()
+            .into_iter()
+            .map(|el| el.name())
+            .sorted()
+            .dedup()
+            .collect_vec();
+
+        let mut out = String::new();
+        writeln!(out, "// generated from a WHAS schema. Do not edit by hand")?;
+
+        for item in &self.items {
+            writeln!(out)?;
+            out.push_str(item);
+        }
+
+        writeln!(out)?;
+        writeln!(out, "/** content of every element by its name */")?;
+        writeln!(out, "export interface Elements {{")?;
+        for (name, types) in &element_types {
+            writeln!(out, "    {:?}: {};", name, types.iter().join(" | "))?;
+        }
+        writeln!(out, "}}")?;
+
+        writeln!(out)?;
+        writeln!(out, "/** attributes of every element by its name */")?;
+        writeln!(out, "export interface ElementAttributes {{")?;
+        for (name, attributes) in &element_attributes {
+            if attributes.is_empty() {
+                writeln!(out, "    {:?}: {{}};", name)?;
+                continue;
+            }
+
+            writeln!(out, "    {:?}: {{", name)?;
+            for (attr, (types, required)) in attributes {
+                let optional = if *required { "" } else { "?" };
+                writeln!(
+                    out,
+                    "        {:?}{}: {};",
+                    attr,
+                    optional,
+                    types.iter().join(" | ")
+                )?;
+            }
+            writeln!(out, "    }};")?;
+        }
+        writeln!(out, "}}")?;
+
+        writeln!(out)?;
+        writeln!(out, "export type ElementName = keyof Elements;")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "/** names of the elements that can be used as document element */"
+        )?;
+        writeln!(
+            out,
+            "export type RootElementName = {};",
+            if roots.is_empty() {
+                "never".to_string()
+            } else {
+                roots.iter().map(|name| format!("{:?}", name)).join(" | ")
+            }
+        )?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "export type AttributeName<E extends ElementName = ElementName> = E extends ElementName"
+        )?;
+        writeln!(out, "    ? keyof ElementAttributes[E] & string")?;
+        writeln!(out, "    : never;")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "export type AttributeValue<E extends ElementName, A extends AttributeName<E>> = NonNullable<"
+        )?;
+        writeln!(
+            out,
+            "    ElementAttributes[E][A & keyof ElementAttributes[E]]"
+        )?;
+        writeln!(out, ">;")?;
+
+        Ok(out)
+    }
+}
+
+impl TypescriptExporter {
+    fn reserve_name(&mut self, name: &str) -> String {
+        unique(pascal_case(name), &mut self.used_names)
+    }
+
+    /// TypeScript type of a simple type reference: its name when it has one,
+    /// its definition otherwise
+    fn simple_type(&self, st: &Ref<SimpleType>, schema: &model::Schema) -> String {
+        match st.resolve(schema) {
+            SimpleType::Builtin { name } => primitive_type(name).to_string(),
+            resolved => self
+                .names
+                .simple_type(st, schema)
+                .unwrap_or_else(|| self.simple_type_definition(resolved, schema)),
+        }
+    }
+
+    fn simple_type_definition(&self, st: &SimpleType, schema: &model::Schema) -> String {
+        match st {
+            SimpleType::Builtin { name } => primitive_type(name).to_string(),
+            SimpleType::Derived {
+                restrictions:
+                    SimpleTypeRestriction {
+                        enumeration: Some(values),
+                        ..
+                    },
+                ..
+            } => values
+                .iter()
+                .map(|value| format!("{:?}", value))
+                .join(" | "),
+            SimpleType::Derived { base, .. } => self.simple_type(base, schema),
+            SimpleType::Union { member_types } => member_types
+                .iter()
+                .map(|member| self.simple_type(member, schema))
+                .join(" | "),
+            // the DOM value of a list is the whitespace separated text
+            SimpleType::List { .. } => "string".to_string(),
+        }
+    }
+
+    /// generate an interface for a group, with additional attributes of the element using it
+    fn interface(
+        &mut self,
+        name: &str,
+        group: &Group,
+        extra_attributes: &model::Attributes,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        let mut properties = Properties::default();
+
+        // inherit from named base types, and inline the content of anonymous ones
+        let (extends, attributes, items) = match group.base_type() {
+            Some(base) if let Some(base_name) = self.names.group(base, schema) => (
+                format!(" extends {}", base_name),
+                group.attributes().clone(),
+                group.items().clone(),
+            ),
+            _ => (
+                String::new(),
+                inherited_attributes(group, schema),
+                inherited_items(group, schema),
+            ),
+        };
+
+        for attr in attributes
+            .merge(extra_attributes.clone())
+            .get(schema)
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+        {
+            let ty = self.simple_type(&attr.typing, schema);
+            properties.push(format!("@{}", attr.name), ty, !attr.required(), false);
+        }
+
+        let choice = *group.ty() == GroupType::Choice;
+        for item in &items {
+            self.properties(item, choice, repeats(group), &mut properties, schema)?;
+        }
+
+        if *group.mixed() {
+            properties.push("#text".into(), "string".into(), true, true);
+        }
+
+        let mut out = String::new();
+        writeln!(out, "export interface {}{} {{", name, extends)?;
+        out.push_str(&properties.render());
+        writeln!(out, "}}")?;
+        self.items.push(out);
+
+        Ok(())
+    }
+
+    /// add the properties for a group item. Elements inside a choice are optional, and
+    /// those inside a repeated group are repeated as well
+    fn properties(
+        &mut self,
+        item: &GroupItem,
+        in_choice: bool,
+        in_repeat: bool,
+        properties: &mut Properties,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        match item {
+            GroupItem::Element(element) => {
+                let element = element.resolve(schema);
+                let ty = self.element_type(element, schema)?;
+                let duplicity = element.duplicity();
+                let repeated =
+                    in_repeat || !matches!(duplicity, Duplicity::Single | Duplicity::Optional);
+                let optional = in_choice || duplicity.min_occurs() == 0;
+
+                properties.push(element.name().to_string(), ty, optional, repeated);
+            }
+            GroupItem::Group(nested) => {
+                let group = nested.resolve(schema);
+                let choice = in_choice || *group.ty() == GroupType::Choice;
+                let repeat = in_repeat || repeats(group);
+
+                for nested_item in inherited_items(group, schema) {
+                    self.properties(&nested_item, choice, repeat, properties, schema)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// TypeScript type for the content of an element, generating an interface if the
+    /// element type is anonymous or the element defines attributes of its own
+    fn element_type(
+        &mut self,
+        element: &model::Element,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let own_attributes = !element.attributes().is_empty();
+
+        match element.typing() {
+            TypeRef::Simple(st) if !own_attributes => Ok(self.simple_type(st, schema)),
+            TypeRef::Group(group)
+                if !own_attributes && let Some(name) = self.names.group(group, schema) =>
+            {
+                Ok(name)
+            }
+            typing => {
+                let key = match typing {
+                    TypeRef::Group(group) if !own_attributes => group.resolve(schema).id(),
+                    _ => element.id(),
+                };
+
+                if let Some(existing) = self.anonymous.get(&key) {
+                    return Ok(existing.clone());
+                }
+
+                let name = self.reserve_name(element.name());
+                self.anonymous.insert(key, name.clone());
+
+                match typing {
+                    TypeRef::Group(group) => {
+                        self.interface(&name, group.resolve(schema), element.attributes(), schema)?
+                    }
+                    TypeRef::Simple(st) => {
+                        let mut properties = Properties::default();
+
+                        for attr in element
+                            .attributes()
+                            .get(schema)
+                            .into_iter()
+                            .sorted_by(|a, b| a.name.cmp(&b.name))
+                        {
+                            let ty = self.simple_type(&attr.typing, schema);
+                            properties.push(format!("@{}", attr.name), ty, !attr.required(), false);
+                        }
+
+                        properties.push("#text".into(), self.simple_type(st, schema), false, false);
+
+                        let mut out = String::new();
+                        writeln!(out, "export interface {} {{", name)?;
+                        out.push_str(&properties.render());
+                        writeln!(out, "}}")?;
+                        self.items.push(out);
+                    }
+                }
+
+                Ok(name)
+            }
+        }
+    }
+}
+
+/// properties of a generated interface
+#[derive(Default)]
+struct Properties {
+    /// key, value types, optional, repeated
+    properties: Vec<(String, BTreeSet<String>, bool, bool)>,
+}
+
+impl Properties {
+    /// add a property. Keys that occur more than once are merged into a repeated property
+    fn push(&mut self, key: String, ty: String, optional: bool, repeated: bool) {
+        match self
+            .properties
+            .iter_mut()
+            .find(|(existing, ..)| *existing == key)
+        {
+            Some((_, types, existing_optional, existing_repeated)) => {
+                types.insert(ty);
+                *existing_optional &= optional;
+                *existing_repeated = true;
+            }
+            None => self
+                .properties
+                .push((key, BTreeSet::from([ty]), optional, repeated)),
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        for (key, types, optional, repeated) in &self.properties {
+            let ty = types.iter().join(" | ");
+            let ty = match (repeated, types.len() > 1 || ty.contains(' ')) {
+                (true, true) => format!("({})[]", ty),
+                (true, false) => format!("{}[]", ty),
+                (false, _) => ty,
+            };
+            let optional = if *optional { "?" } else { "" };
+            let _ = writeln!(out, "    {:?}{}: {};", key, optional, ty);
+        }
+
+        out
+    }
+}
+
+/// items of the group preceded by those of its base types
+fn inherited_items(group: &Group, schema: &model::Schema) -> Vec<GroupItem> {
+    let mut items = match group.base_type() {
+        Some(base) => inherited_items(base.resolve(schema), schema),
+        None => vec![],
+    };
+    items.extend(group.items().iter().cloned());
+    items
+}
+
+/// whether the group can occur more than once, like `?{ .. }*`
+fn repeats(group: &Group) -> bool {
+    group.max_occurs() != Some(1)
+}
+
+/// attributes of the group including those of its base types
+fn inherited_attributes(group: &Group, schema: &model::Schema) -> model::Attributes {
+    match group.base_type() {
+        Some(base) => {
+            inherited_attributes(base.resolve(schema), schema).merge(group.attributes().clone())
+        }
+        None => group.attributes().clone(),
+    }
+}
+
+/// the TypeScript type of a primitive value in the DOM
+fn primitive_type(primitive: &PrimitiveType) -> &'static str {
+    match primitive {
+        PrimitiveType::Int
+        | PrimitiveType::IntNeg
+        | PrimitiveType::IntPos
+        | PrimitiveType::IntNonNeg
+        | PrimitiveType::UnsignedLong
+        | PrimitiveType::Short
+        | PrimitiveType::Float
+        | PrimitiveType::Double
+        | PrimitiveType::Decimal => "`${number}`",
+        PrimitiveType::Bool => "\"true\" | \"false\" | \"1\" | \"0\"",
+        PrimitiveType::String
+        | PrimitiveType::URI
+        | PrimitiveType::DateTimestamp
+        | PrimitiveType::DateTime
+        | PrimitiveType::Date
+        | PrimitiveType::Time
+        | PrimitiveType::Duration
+        | PrimitiveType::IDRefs
+        | PrimitiveType::IDRef
+        | PrimitiveType::ID
+        | PrimitiveType::Lang
+        | PrimitiveType::NoColName
+        | PrimitiveType::Token
+        | PrimitiveType::NameTokens
+        | PrimitiveType::NameToken
+        | PrimitiveType::Name
+        | PrimitiveType::Base64Binary
+        | PrimitiveType::AnySimpleType => "string",
+    }
+}
+
+/// describe the facets that TypeScript cannot express, for the doc comment of a type
+fn restriction_comment(st: &SimpleType) -> String {
+    let Some(restrictions) = st.restrictions() else {
+        return String::new();
+    };
+
+    let mut facets = vec![];
+
+    if let Some(v) = restrictions.length {
+        facets.push(format!("length: {}", v));
+    }
+    if let Some(v) = restrictions.min_length {
+        facets.push(format!("minLength: {}", v));
+    }
+    if let Some(v) = restrictions.max_length {
+        facets.push(format!("maxLength: {}", v));
+    }
+    if let Some(v) = &restrictions.pattern {
+        facets.push(format!("pattern: /{}/", v));
+    }
+    if let Some(v) = &restrictions.min_inclusive {
+        facets.push(format!("minInclusive: {}", v));
+    }
+    if let Some(v) = &restrictions.max_inclusive {
+        facets.push(format!("maxInclusive: {}", v));
+    }
+    if let Some(v) = &restrictions.min_exclusive {
+        facets.push(format!("minExclusive: {}", v));
+    }
+    if let Some(v) = &restrictions.max_exclusive {
+        facets.push(format!("maxExclusive: {}", v));
+    }
+    if let Some(v) = restrictions.total_digits {
+        facets.push(format!("totalDigits: {}", v));
+    }
+    if let Some(v) = restrictions.fraction_digits {
+        facets.push(format!("fractionDigits: {}", v));
+    }
+
+    // a comment cannot contain its own terminator
+    facets.join(", ").replace("*/", "*\\/")
+}
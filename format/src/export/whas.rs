@@ -0,0 +1,393 @@
+use crate::export::{Exporter, TypeNames};
+use crate::model;
+use crate::model::restriction::{SimpleTypeRestriction, WhiteSpaceHandling};
+use crate::model::{Duplicity, Group, GroupItem, GroupType, PrimitiveType, Ref, SimpleType, TypeRef};
+use itertools::Itertools;
+use std::fmt::Write;
+
+const INDENT: &str = "    ";
+
+/// WHAS source exporter - prints a model back as a WHAS schema file.
+///
+/// Mainly used to turn schemas imported from other formats (like DTD) into WHAS.
+/// Named types are printed first, followed by the root elements, all sorted by name.
+#[derive(Default)]
+pub struct WhasExporter {
+    names: TypeNames,
+}
+
+impl Exporter for WhasExporter {
+    type Output = String;
+
+    fn export_schema(mut self, schema: &model::Schema) -> anyhow::Result<Self::Output> {
+        self.names = TypeNames::identifiers(schema);
+
+        let mut out = String::new();
+
+        for (name, st) in self.names.simple_types(schema) {
+            writeln!(out, "{}: {}", name, self.simple_type_definition(st, schema))?;
+        }
+
+        for (name, group) in self.names.groups(schema) {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+
+            self.write_attributes(&mut out, group.attributes(), "", schema)?;
+            write!(out, "{}", name)?;
+
+            if let Some(base) = group.base_type() {
+                write!(out, " < {}", self.names.group(base, schema).unwrap_or_default())?;
+            }
+
+            let block = self.block(group, false, "", schema)?;
+            if block.starts_with('{') {
+                writeln!(out, " {}", block)?;
+            } else {
+                writeln!(out, ": {}", block)?;
+            }
+        }
+
+        let roots = schema
+            .get_elements_root()
+            .into_iter()
+            .sorted_by(|a, b| a.name().cmp(b.name()))
+            .collect_vec();
+
+        if !roots.is_empty() && !out.is_empty() {
+            out.push('\n');
+        }
+
+        for element in roots {
+            self.write_element(&mut out, element, "", schema)?;
+        }
+
+        Ok(out)
+    }
+}
+
+impl WhasExporter {
+    /// reference to a simple type: its name when it has one, its definition otherwise
+    fn simple_type(&self, st: &Ref<SimpleType>, schema: &model::Schema) -> String {
+        let name = match st.resolve(schema) {
+            SimpleType::Builtin { name } => Some(primitive_name(name).to_string()),
+            _ => self.names.simple_type(st, schema),
+        };
+
+        name.unwrap_or_else(|| self.simple_type_definition(st.resolve(schema), schema))
+    }
+
+    fn simple_type_definition(&self, st: &SimpleType, schema: &model::Schema) -> String {
+        match st {
+            SimpleType::Builtin { name } => primitive_name(name).to_string(),
+            SimpleType::Derived {
+                base, restrictions, ..
+            } => {
+                let base_is_string = matches!(
+                    base.resolve(schema),
+                    SimpleType::Builtin {
+                        name: PrimitiveType::String
+                    }
+                );
+
+                match restrictions {
+                    SimpleTypeRestriction {
+                        enumeration: Some(values),
+                        ..
+                    } if base_is_string => values.iter().map(|v| quote(v)).join(" | "),
+                    SimpleTypeRestriction {
+                        pattern: Some(pattern),
+                        ..
+                    } if base_is_string && *restrictions == pattern_only(pattern) => {
+                        format!("/{}/", pattern)
+                    }
+                    _ => format!(
+                        "{}<{}>",
+                        self.simple_type(base, schema),
+                        facets(restrictions).join(", ")
+                    ),
+                }
+            }
+            SimpleType::Union { member_types } => member_types
+                .iter()
+                .map(|member| self.simple_type(member, schema))
+                .join(" | "),
+            SimpleType::List { item_type, .. } => {
+                format!("[{}]", self.simple_type(item_type, schema))
+            }
+        }
+    }
+
+    /// print the block of a group including its modifiers, like `x?{ .. }*`. only inline blocks
+    /// declare their attributes, after the elements so they do not end up on the last one.
+    /// named types have them above the definition instead
+    fn block(
+        &self,
+        group: &Group,
+        inline: bool,
+        indent: &str,
+        schema: &model::Schema,
+    ) -> anyhow::Result<String> {
+        let mut out = String::new();
+
+        if group.is_abstract() {
+            out.push('a');
+        }
+        if *group.mixed() {
+            out.push('x');
+        }
+        match group.ty() {
+            GroupType::Sequence => {}
+            GroupType::Choice => out.push('?'),
+            GroupType::All => out.push('!'),
+        }
+
+        let attributes = match inline {
+            true => group.attributes().clone(),
+            false => model::Attributes::default(),
+        };
+
+        if group.items().is_empty() && group.assertions().is_empty() && attributes.is_empty() {
+            out.push_str("{}");
+            out.push_str(&duplicity(group.duplicity()));
+            return Ok(out);
+        }
+
+        out.push_str("{\n");
+        let inner = format!("{}{}", indent, INDENT);
+
+        for item in group.items() {
+            match item {
+                GroupItem::Element(element) => {
+                    self.write_element(&mut out, element.resolve(schema), &inner, schema)?
+                }
+                GroupItem::Group(nested) => match self.names.group(nested, schema) {
+                    Some(name) => writeln!(out, "{}...{}", inner, name)?,
+                    None => writeln!(
+                        out,
+                        "{}...{}",
+                        inner,
+                        self.block(nested.resolve(schema), true, &inner, schema)?
+                    )?,
+                },
+            }
+        }
+
+        self.write_attributes(&mut out, &attributes, &inner, schema)?;
+
+        for assertion in group.assertions() {
+            writeln!(out, "{}{}", inner, assertion)?;
+        }
+
+        write!(out, "{}}}{}", indent, duplicity(group.duplicity()))?;
+        Ok(out)
+    }
+
+    fn write_element(
+        &self,
+        out: &mut String,
+        element: &model::Element,
+        indent: &str,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        // the attributes and the type are those of the top-level element
+        if *element.global_ref() {
+            writeln!(out, "{}ref #{}{}", indent, element.name(), duplicity(element.duplicity()))?;
+            return Ok(());
+        }
+
+        self.write_attributes(out, element.attributes(), indent, schema)?;
+        write!(out, "{}#{}{}", indent, element.name(), duplicity(element.duplicity()))?;
+
+        if let Some(head) = element.substitutes() {
+            write!(out, " < #{}", head)?;
+        }
+
+        match element.typing() {
+            TypeRef::Simple(st) => write!(out, ": {}", self.simple_type(st, schema))?,
+            TypeRef::Group(group) => match self.names.group(group, schema) {
+                Some(name) => write!(out, ": {}", name)?,
+                None => write!(out, " {}", self.block(group.resolve(schema), true, indent, schema)?)?,
+            },
+        }
+
+        for constraint in element.constraints() {
+            write!(out, "\n{}{}{}", indent, INDENT, constraint)?;
+        }
+
+        for rule in element.rules() {
+            write!(out, "\n{}{}{}", indent, INDENT, rule)?;
+        }
+
+        out.push('\n');
+        Ok(())
+    }
+
+    fn write_attributes(
+        &self,
+        out: &mut String,
+        attributes: &model::Attributes,
+        indent: &str,
+        schema: &model::Schema,
+    ) -> anyhow::Result<()> {
+        for attr in attributes
+            .get(schema)
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+        {
+            write!(out, "{}@{}", indent, attr.name)?;
+
+            if !attr.required() {
+                out.push('?');
+            }
+
+            let typing = self.simple_type(&attr.typing, schema);
+            if typing != primitive_name(&PrimitiveType::String) {
+                write!(out, ": {}", typing)?;
+            }
+
+            // WHAS has no syntax for default values yet, so keep them as a hint for the reader
+            if let Some(default) = &attr.default_value {
+                write!(out, " // default: {}", quote(default))?;
+            }
+
+            out.push('\n');
+        }
+
+        Ok(())
+    }
+}
+
+/// the way a primitive is spelled in WHAS. Primitives without WHAS syntax fall back to String
+pub(crate) fn primitive_name(primitive: &PrimitiveType) -> &'static str {
+    match primitive {
+        PrimitiveType::String
+        | PrimitiveType::Base64Binary
+        | PrimitiveType::UnsignedLong
+        | PrimitiveType::AnySimpleType => "String",
+        PrimitiveType::URI => "URI",
+        PrimitiveType::DateTimestamp => "DateTimestamp",
+        PrimitiveType::DateTime => "DateTime",
+        PrimitiveType::Date => "Date",
+        PrimitiveType::Time => "Time",
+        PrimitiveType::Duration => "Duration",
+        PrimitiveType::Bool => "Bool",
+        PrimitiveType::Int => "Int",
+        PrimitiveType::Float => "Float",
+        PrimitiveType::Double => "Double",
+        PrimitiveType::Short => "Short",
+        PrimitiveType::Decimal => "Decimal",
+        PrimitiveType::IDRefs => "IDRefs",
+        PrimitiveType::IDRef => "IDRef",
+        PrimitiveType::ID => "ID",
+        PrimitiveType::Lang => "Lang",
+        PrimitiveType::NoColName => "NoColName",
+        PrimitiveType::IntNeg => "-Int",
+        PrimitiveType::IntNonNeg => "Int<0..>",
+        PrimitiveType::IntPos => "+Int",
+        PrimitiveType::Token => "Token",
+        PrimitiveType::NameTokens => "NameTokens",
+        PrimitiveType::NameToken => "NameToken",
+        PrimitiveType::Name => "Name",
+    }
+}
+
+fn duplicity(duplicity: &Duplicity) -> String {
+    match duplicity {
+        Duplicity::Single => String::new(),
+        Duplicity::Optional => "?".to_string(),
+        Duplicity::Any => "*".to_string(),
+        Duplicity::Min1 => "+".to_string(),
+        Duplicity::Custom(range) if range.start == range.end => format!("[{}]", range.start),
+        Duplicity::Custom(range) => format!("[{}..{}]", range.start, range.end),
+    }
+}
+
+fn pattern_only(pattern: &str) -> SimpleTypeRestriction {
+    SimpleTypeRestriction {
+        pattern: Some(pattern.to_string()),
+        ..Default::default()
+    }
+}
+
+/// named facets for all restrictions that are set
+fn facets(restrictions: &SimpleTypeRestriction) -> Vec<String> {
+    facet_values(restrictions)
+        .into_iter()
+        .map(|(facet, value)| format!("{}: {}", facet, value))
+        .collect()
+}
+
+/// facet name and value in WHAS syntax for all restrictions that are set
+pub(crate) fn facet_values(restrictions: &SimpleTypeRestriction) -> Vec<(&'static str, String)> {
+    let SimpleTypeRestriction {
+        length,
+        min_length,
+        max_length,
+        pattern,
+        enumeration,
+        white_space,
+        min_inclusive,
+        max_inclusive,
+        min_exclusive,
+        max_exclusive,
+        total_digits,
+        fraction_digits,
+    } = restrictions;
+
+    let mut facets = vec![];
+
+    if let Some(v) = length {
+        facets.push(("length", v.to_string()));
+    }
+    if let Some(v) = min_length {
+        facets.push(("minLength", v.to_string()));
+    }
+    if let Some(v) = max_length {
+        facets.push(("maxLength", v.to_string()));
+    }
+    if let Some(v) = pattern {
+        facets.push(("pattern", format!("/{}/", v)));
+    }
+    if let Some(values) = enumeration {
+        facets.extend(values.iter().map(|v| ("enumeration", quote(v))));
+    }
+    if let Some(v) = white_space {
+        let value = match v {
+            WhiteSpaceHandling::Preserve => "preserve",
+            WhiteSpaceHandling::Replace => "replace",
+            WhiteSpaceHandling::Collapse => "collapse",
+        };
+        facets.push(("whiteSpace", format!("\"{}\"", value)));
+    }
+    if let Some(v) = min_inclusive {
+        facets.push(("minInclusive", v.clone()));
+    }
+    if let Some(v) = max_inclusive {
+        facets.push(("maxInclusive", v.clone()));
+    }
+    if let Some(v) = min_exclusive {
+        facets.push(("minExclusive", v.clone()));
+    }
+    if let Some(v) = max_exclusive {
+        facets.push(("maxExclusive", v.clone()));
+    }
+    if let Some(v) = total_digits {
+        facets.push(("totalDigits", v.to_string()));
+    }
+    if let Some(v) = fraction_digits {
+        facets.push(("fractionDigits", v.to_string()));
+    }
+
+    facets
+}
+
+/// quote a literal with a delimiter that does not occur in it
+pub(crate) fn quote(value: &str) -> String {
+    let delimiter = ['"', '\'', '`', '%']
+        .into_iter()
+        .find(|d| !value.contains(*d))
+        .unwrap_or('"');
+
+    format!("{}{}{}", delimiter, value, delimiter)
+}
@@ -1,13 +1,15 @@
 use crate::model;
 use anyhow::Result;
-use xmltree::{Element, XMLNode};
+use xmltree::{Element, EmitterConfig, XMLNode};
 use std::io::Cursor;
 
 /// Helper trait to add fluent-style methods to xmltree::Element
-trait ElementExt {
+pub(crate) trait ElementExt {
     fn with_attr(self, key: impl Into<String>, value: impl Into<String>) -> Self;
     fn with_child(self, child: Element) -> Self;
     fn with_prefix(self, prefix: impl Into<String>) -> Self;
+    fn with_annotation<'a>(self, comments: impl IntoIterator<Item = &'a model::Comment>) -> Self;
+    fn with_appinfo(self, children: Vec<Element>) -> Self;
 }
 
 impl ElementExt for Element {
@@ -25,20 +27,82 @@ impl ElementExt for Element {
         self.prefix = Some(prefix.into());
         self
     }
+
+    /// document the element with xs:annotation, which has to be its first child.
+    /// Documentation is added to an existing annotation
+    fn with_annotation<'a>(mut self, comments: impl IntoIterator<Item = &'a model::Comment>) -> Self {
+        let mut annotation = Element::new("xs:annotation");
+
+        for comment in comments {
+            let mut documentation = Element::new("xs:documentation");
+            documentation.children.push(XMLNode::Text(comment.content()));
+            annotation = annotation.with_child(documentation);
+        }
+
+        if annotation.children.is_empty() {
+            return self;
+        }
+
+        match self.children.first_mut() {
+            Some(XMLNode::Element(existing)) if existing.name == "xs:annotation" => {
+                existing.children.extend(annotation.children);
+            }
+            _ => self.children.insert(0, XMLNode::Element(annotation)),
+        }
+        self
+    }
+
+    /// add xs:appinfo to the xs:annotation of the element, which is created when there is none
+    fn with_appinfo(mut self, children: Vec<Element>) -> Self {
+        if children.is_empty() {
+            return self;
+        }
+
+        let mut appinfo = Element::new("xs:appinfo");
+        appinfo.children.extend(children.into_iter().map(XMLNode::Element));
+
+        match self.children.first_mut() {
+            Some(XMLNode::Element(annotation)) if annotation.name == "xs:annotation" => {
+                annotation.children.push(XMLNode::Element(appinfo));
+            }
+            _ => {
+                let annotation = Element::new("xs:annotation").with_child(appinfo);
+                self.children.insert(0, XMLNode::Element(annotation));
+            }
+        }
+        self
+    }
 }
 
-use crate::export::Exporter;
+use crate::export::{Exporter, is_declared_globally, unique};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use crate::export::schematron::{SCHEMATRON_NAMESPACE, element_patterns, namespace_binding};
 
 /// XSD XML Exporter - exports WHAS model to XSD (XML Schema Definition)
 pub struct XsdExporter {
     /// Target namespace (if supported)
     target_namespace: Option<String>,
+
+    /// whether the Schematron rules of elements are embedded in their xs:appinfo
+    embed_schematron: bool,
+
+    /// names of the top-level elements that documents can start with, all when empty
+    roots: Vec<String>,
+
+    /// names of the identity constraints exported so far, which are global in XSD, while the
+    /// same name can be declared on several elements, or an element can be exported in
+    /// several places
+    constraint_names: RefCell<HashSet<String>>,
 }
 
 impl Default for XsdExporter {
     fn default() -> Self {
         Self {
             target_namespace: None,
+            embed_schematron: false,
+            roots: vec![],
+            constraint_names: RefCell::default(),
         }
     }
 }
@@ -54,7 +118,46 @@ impl Exporter for XsdExporter {
             .with_attr("elementFormDefault", "qualified");
 
         if let Some(ns) = &self.target_namespace {
-            schema_elem = schema_elem.with_attr("targetNamespace", ns);
+            // types are referred to without a prefix, so they are in the default namespace.
+            // XPaths do not use the default namespace, so they refer to elements with a prefix
+            schema_elem = schema_elem
+                .with_attr("targetNamespace", ns)
+                .with_attr("xmlns", ns)
+                .with_attr(format!("xmlns:{}", model::TARGET_PREFIX), ns);
+        }
+
+        // assertions are part of XSD 1.1, which 1.0 processors are told to skip
+        if schema.types_group().values().any(|group| !group.assertions().is_empty()) {
+            schema_elem = schema_elem
+                .with_attr("xmlns:vc", "http://www.w3.org/2007/XMLSchema-versioning")
+                .with_attr("vc:minVersion", "1.1");
+        }
+
+        if self.embed_schematron && schema.elements().values().any(|el| !el.rules().is_empty()) {
+            schema_elem = schema_elem.with_attr("xmlns:sch", SCHEMATRON_NAMESPACE);
+
+            if let Some(ns) = &self.target_namespace {
+                schema_elem =
+                    schema_elem.with_appinfo(vec![namespace_binding(model::TARGET_PREFIX, ns)]);
+            }
+        }
+
+        // prefixed names are declared by the schema of their namespace, which is imported
+        for (prefix, uri, location) in model::KNOWN_NAMESPACES {
+            if !schema.uses_namespace(uri) {
+                continue;
+            }
+
+            // the xml prefix is bound without a declaration
+            if prefix != "xml" {
+                schema_elem = schema_elem.with_attr(format!("xmlns:{}", prefix), uri);
+            }
+
+            schema_elem = schema_elem.with_child(
+                Element::new("xs:import")
+                    .with_attr("namespace", uri)
+                    .with_attr("schemaLocation", location),
+            );
         }
 
         // Export simple types (primitives are built into XSD, only custom types need export)
@@ -79,29 +182,72 @@ impl Exporter for XsdExporter {
 
         // Export top-level elements (sorted by name for deterministic output)
         // Elements are named by their name() method, not via the type name mapping
-        let mut root_elements = schema.get_elements_root();
+        let mut root_elements = schema
+            .get_elements_root()
+            .into_iter()
+            .filter(|el| is_declared_globally(el, &self.roots, schema))
+            .filter(|el| model::namespace_uri(el.name()).is_none())
+            .collect::<Vec<_>>();
         root_elements.sort_by_key(|el| el.name());
 
         for element in &root_elements {
             schema_elem = schema_elem.with_child(self.export_element(element.name(), element, schema)?);
         }
 
-        // Write XML to string with declaration
-        let mut buffer = Cursor::new(Vec::new());
-        schema_elem.write(&mut buffer)?;
+        write_document(&schema_elem)
+    }
+}
+
+/// indented XML document with the root element
+pub(crate) fn write_document(root: &Element) -> Result<String> {
+    // Write XML to string, the declaration is prepended below
+    let config = EmitterConfig::new()
+        .perform_indent(true)
+        .indent_string("  ")
+        .pad_self_closing(false)
+        .write_document_declaration(false);
 
-        let xml_bytes = buffer.into_inner();
-        let xml_content = String::from_utf8(xml_bytes)?;
+    let mut buffer = Cursor::new(Vec::new());
+    root.write_with_config(&mut buffer, config)?;
 
-        // Prepend XML declaration
-        Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}", xml_content))
-    }
+    let xml_bytes = buffer.into_inner();
+    let xml_content = String::from_utf8(xml_bytes)?;
+
+    // Prepend XML declaration
+    Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}", xml_content))
 }
 
 impl XsdExporter {
     pub fn with_namespace(namespace: impl Into<String>) -> Self {
         Self {
             target_namespace: Some(namespace.into()),
+            ..Self::default()
+        }
+    }
+
+    /// embed the Schematron rules of elements in the xs:appinfo of their declarations
+    pub fn with_embedded_schematron(mut self) -> Self {
+        self.embed_schematron = true;
+        self
+    }
+
+    /// only declare the top-level elements with the names globally, since documents can
+    /// start with any global element
+    pub fn with_roots(mut self, roots: Vec<String>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// the prefix of the elements in XPaths
+    fn prefix(&self) -> Option<&str> {
+        self.target_namespace.as_ref().map(|_| model::TARGET_PREFIX)
+    }
+
+    /// the Schematron patterns to embed in the declaration of the element, if any
+    fn export_rules(&self, element: &model::Element, schema: &model::Schema) -> Vec<Element> {
+        match self.embed_schematron {
+            true => element_patterns(element, schema, self.prefix()),
+            false => vec![],
         }
     }
 
@@ -112,13 +258,12 @@ impl XsdExporter {
         schema: &model::Schema,
     ) -> Result<Element> {
         let mut simple_type_elem = Element::new("xs:simpleType")
-            .with_attr("name", name);
+            .with_attr("name", name)
+            .with_annotation(schema.get_comments_for_type_name(name));
 
         match simple_type {
             model::SimpleType::Derived { base, restrictions, .. } => {
-                let base_name = base.resolve(schema).to_type_name(schema);
-                let mut restriction_elem = Element::new("xs:restriction")
-                    .with_attr("base", format!("xs:{}", self.map_primitive_to_xsd(&base_name)));
+                let mut restriction_elem = self.export_restriction_base(base, schema)?;
 
                 // Export all facets using helper
                 for facet_elem in self.export_restrictions(restrictions)? {
@@ -143,12 +288,7 @@ impl XsdExporter {
                 );
             }
             model::SimpleType::List { item_type, separator: _ } => {
-                let item_name = item_type.resolve(schema).to_type_name(schema);
-                simple_type_elem = simple_type_elem.with_child(
-                    Element::new("xs:list")
-                        .with_attr("itemType", format!("xs:{}", self.map_primitive_to_xsd(&item_name)))
-                        
-                );
+                simple_type_elem = simple_type_elem.with_child(self.export_list(item_type, schema)?);
             }
             model::SimpleType::Builtin { .. } => {
                 // Should not reach here - builtins are filtered out
@@ -165,13 +305,20 @@ impl XsdExporter {
         schema: &model::Schema,
     ) -> Result<Element> {
         let mut complex_type_elem = Element::new("xs:complexType")
-            .with_attr("name", name);
+            .with_attr("name", name)
+            .with_annotation(schema.get_comments_for_type_name(name));
+
+        if *group.mixed() {
+            complex_type_elem = complex_type_elem.with_attr("mixed", "true");
+        }
 
         // Add abstract attribute if type is abstract
         if group.is_abstract() {
             complex_type_elem = complex_type_elem.with_attr("abstract", "true");
         }
 
+        let attr_elems = self.export_attributes(group.attributes(), schema)?;
+
         // Handle inheritance with xs:extension
         if let Some(base_ref) = group.base_type() {
             // Find the base type name
@@ -182,6 +329,15 @@ impl XsdExporter {
                 // Export only local fields (not inherited)
                 extension_elem = extension_elem.with_child(self.export_group_content_local(group, schema)?);
 
+                for attr_elem in attr_elems {
+                    extension_elem = extension_elem.with_child(attr_elem);
+                }
+
+                // assertions of the base type are inherited too
+                for assert_elem in self.export_assertions(group.nested_assertions(schema)) {
+                    extension_elem = extension_elem.with_child(assert_elem);
+                }
+
                 let complex_content_elem = Element::new("xs:complexContent")
                     .with_child(extension_elem);
 
@@ -189,15 +345,45 @@ impl XsdExporter {
             } else {
                 // Fallback if base name not found - export all content
                 complex_type_elem = complex_type_elem.with_child(self.export_group_content(group, schema)?);
+
+                for attr_elem in attr_elems {
+                    complex_type_elem = complex_type_elem.with_child(attr_elem);
+                }
+
+                for assert_elem in self.export_assertions(group.merged_assertions(schema)) {
+                    complex_type_elem = complex_type_elem.with_child(assert_elem);
+                }
             }
         } else {
             // No inheritance - export group content normally
-            complex_type_elem = complex_type_elem.with_child(self.export_group_content(group, schema)?);
+            complex_type_elem = self.with_content(complex_type_elem, group, schema)?;
+
+            for attr_elem in attr_elems {
+                complex_type_elem = complex_type_elem.with_child(attr_elem);
+            }
+
+            for assert_elem in self.export_assertions(group.nested_assertions(schema)) {
+                complex_type_elem = complex_type_elem.with_child(assert_elem);
+            }
         }
 
         Ok(complex_type_elem)
     }
 
+    /// the model group of the content. Elements without content have none, so the comments
+    /// that document the group go to the complex type instead
+    fn with_content(
+        &self,
+        complex_type_elem: Element,
+        group: &model::Group,
+        schema: &model::Schema,
+    ) -> Result<Element> {
+        Ok(match group.is_empty_content(schema) {
+            true => complex_type_elem.with_annotation(group.nested_comments(schema)),
+            false => complex_type_elem.with_child(self.export_group_content(group, schema)?),
+        })
+    }
+
     fn export_group_content(
         &self,
         group: &model::Group,
@@ -210,7 +396,8 @@ impl XsdExporter {
             model::GroupType::All => "xs:all",
         };
 
-        let mut group_elem = Element::new(group_tag);
+        let mut group_elem = Element::new(group_tag).with_annotation(group.comments());
+        group_elem = self.with_group_occurs(group_elem, group);
 
         // Export items
         for item in group.items() {
@@ -242,7 +429,8 @@ impl XsdExporter {
             model::GroupType::All => "xs:all",
         };
 
-        let mut group_elem = Element::new(group_tag);
+        let mut group_elem = Element::new(group_tag).with_annotation(group.comments());
+        group_elem = self.with_group_occurs(group_elem, group);
 
         // Export only local items (all items in this group are local by definition)
         // Inheritance is handled by XSD's extension mechanism
@@ -262,6 +450,19 @@ impl XsdExporter {
         Ok(group_elem)
     }
 
+    /// the occurrence of a group that repeats as a whole, like a choice that is made again
+    fn with_group_occurs(&self, elem: Element, group: &model::Group) -> Element {
+        if *group.duplicity() == model::Duplicity::Single {
+            return elem;
+        }
+
+        let elem = elem.with_attr("minOccurs", group.min_occurs().to_string());
+        match group.max_occurs() {
+            Some(max) => elem.with_attr("maxOccurs", max.to_string()),
+            None => elem.with_attr("maxOccurs", "unbounded"),
+        }
+    }
+
     fn export_element(
         &self,
         name: &str,
@@ -269,7 +470,20 @@ impl XsdExporter {
         schema: &model::Schema,
     ) -> Result<Element> {
         let mut elem = Element::new("xs:element")
-            .with_attr("name", name);
+            .with_attr("name", name)
+            .with_annotation(element.comments())
+            .with_appinfo(self.export_rules(element, schema));
+
+        if let Some(head) = element.substitutes() {
+            elem = elem.with_attr("substitutionGroup", head);
+        }
+
+        let is_substitution_head = element.is_substitution_head(schema);
+
+        // only the members of the group of an abstract head can appear in documents
+        if is_substitution_head && element.is_abstract(schema) {
+            elem = elem.with_attr("abstract", "true");
+        }
 
         // Add occurrence constraints
         elem = elem.with_attr("minOccurs", element.min_occurs().to_string());
@@ -283,28 +497,46 @@ impl XsdExporter {
         let attrs = element.group_merged_attributes(schema);
         let has_attrs = !attrs.as_vec().is_empty();
 
-        // Check if it has complex type
-        if let Some(group_type) = element.typing().grouptype(schema) {
+        // the type of a member has to derive from the type of its head,
+        // which XSD can only tell when both refer to named types
+        let substitution_type_name = match element.typing() {
+            model::TypeRef::Group(group_ref)
+                if element.attributes().is_empty()
+                    && (is_substitution_head || element.substitutes().is_some()) =>
+            {
+                schema.get_type_name_for_group(group_ref)
+            }
+            _ => None,
+        };
+
+        if let Some(type_name) = substitution_type_name {
+            elem = elem.with_attr("type", type_name);
+        } else if let Some(group_type) = element.typing().grouptype(schema) {
             // Check for mixed content
             let mut complex_type_elem = Element::new("xs:complexType");
             if element.is_mixed_content(schema) {
                 complex_type_elem = complex_type_elem.with_attr("mixed", "true");
             }
 
-            complex_type_elem = complex_type_elem.with_child(self.export_group_content(group_type, schema)?);
+            complex_type_elem = self.with_content(complex_type_elem, group_type, schema)?;
 
             // Add attributes
             for attr_elem in self.export_attributes(&attrs, schema)? {
                 complex_type_elem = complex_type_elem.with_child(attr_elem);
             }
 
+            // assertions come after the attributes
+            for assert_elem in self.export_assertions(group_type.merged_assertions(schema)) {
+                complex_type_elem = complex_type_elem.with_child(assert_elem);
+            }
+
             elem = elem.with_child(complex_type_elem);
         } else if let model::TypeRef::Simple(simple_ref) = element.typing() {
             let simple_type = simple_ref.resolve(schema);
 
-            // Check if this is an anonymous union (inline union)
-            let is_anonymous_union = matches!(simple_type, model::SimpleType::Union { .. }) &&
-                                     schema.get_type_name_for_simpletype(simple_ref).is_none();
+            // Check if this is an anonymous union or list (inline union)
+            let is_anonymous_union = (matches!(simple_type, model::SimpleType::Union { .. }) || simple_type.is_list(schema)) &&
+                                     self.is_anonymous_simple_type(simple_ref, schema);
 
             // Simple type with attributes (simpleContent)
             if has_attrs {
@@ -349,22 +581,88 @@ impl XsdExporter {
                     elem = elem.with_attr("type", type_name);
                 }
             }
-        } else if has_attrs {
-            // Attributes only (empty content)
-            let mut complex_type_elem = Element::new("xs:complexType");
-
-            // Add attributes
-            for attr_elem in self.export_attributes(&attrs, schema)? {
-                complex_type_elem = complex_type_elem.with_child(attr_elem);
-            }
+        }
 
-            elem = elem.with_child(complex_type_elem);
-        } else {
+        // identity constraints come after the type of the element
+        for constraint_elem in self.export_constraints(element) {
+            elem = elem.with_child(constraint_elem);
         }
 
         Ok(elem)
     }
 
+    /// xs:unique, xs:key and xs:keyref. The first selected element is found at any depth
+    /// The names are numbered when they are taken already, and key references refer to the
+    /// name of their key on the same element
+    fn export_constraints(&self, element: &model::Element) -> Vec<Element> {
+        let mut used = self.constraint_names.borrow_mut();
+
+        let keys = element
+            .constraints()
+            .iter()
+            .filter_map(|constraint| match constraint.kind() {
+                model::ConstraintKind::Key(name) => Some((name, unique(name.clone(), &mut used))),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        element
+            .constraints()
+            .iter()
+            .map(|constraint| {
+                let (tag, name) = match constraint.kind() {
+                    model::ConstraintKind::Key(key) => ("xs:key", keys[key].clone()),
+                    model::ConstraintKind::Unique => (
+                        "xs:unique",
+                        unique(constraint.name(element.name()), &mut used),
+                    ),
+                    model::ConstraintKind::KeyRef(_) => (
+                        "xs:keyref",
+                        unique(constraint.name(element.name()), &mut used),
+                    ),
+                };
+                let mut constraint_elem = Element::new(tag).with_attr("name", name);
+
+                if let model::ConstraintKind::KeyRef(key) = constraint.kind() {
+                    let refer = keys.get(key).unwrap_or(key);
+                    constraint_elem = constraint_elem.with_attr("refer", refer);
+                }
+
+                let field = match constraint.field() {
+                    model::ConstraintField::Attribute(name) => format!("@{}", name),
+                    model::ConstraintField::Element(name) => model::qualify(name, self.prefix()),
+                };
+                let selector = constraint
+                    .selector()
+                    .iter()
+                    .map(|step| model::qualify(step, self.prefix()))
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                constraint_elem
+                    .with_child(
+                        Element::new("xs:selector").with_attr("xpath", format!(".//{}", selector)),
+                    )
+                    .with_child(Element::new("xs:field").with_attr("xpath", field))
+            })
+            .collect()
+    }
+
+    /// XSD 1.1 xs:assert, documented by the comments of the assertion
+    fn export_assertions<'a>(
+        &self,
+        assertions: impl IntoIterator<Item = &'a model::Assertion>,
+    ) -> Vec<Element> {
+        assertions
+            .into_iter()
+            .map(|assertion| {
+                Element::new("xs:assert")
+                    .with_attr("test", assertion.xpath_in(self.prefix()))
+                    .with_annotation(assertion.comments())
+            })
+            .collect()
+    }
+
     fn export_attributes(
         &self,
         attrs: &model::Attributes,
@@ -378,29 +676,44 @@ impl XsdExporter {
 
         for attr_ref in attr_vec {
             let attr = attr_ref.resolve(schema);
+
+            // the type of a prefixed attribute is declared in the schema of its namespace
+            if model::namespace_uri(attr.name()).is_some() {
+                let mut attr_elem = Element::new("xs:attribute")
+                    .with_attr("ref", attr.name())
+                    .with_annotation(attr.comments());
+
+                if *attr.required() {
+                    attr_elem = attr_elem.with_attr("use", "required");
+                }
+
+                result.push(attr_elem);
+                continue;
+            }
+
             let mut attr_elem = Element::new("xs:attribute")
-                .with_attr("name", attr.name());
+                .with_attr("name", attr.name())
+                .with_annotation(attr.comments());
 
             // Type - attr.typing is directly a Ref<SimpleType>
             let attr_type = attr.typing.resolve(schema);
 
-            // Check if this is an anonymous union type (inline union)
-            if matches!(attr_type, model::SimpleType::Union { .. }) &&
-               schema.get_type_name_for_simpletype(&attr.typing).is_none() {
+            // Check if this is an anonymous union or list type (inline union)
+            if (matches!(attr_type, model::SimpleType::Union { .. }) || attr_type.is_list(schema)) &&
+               self.is_anonymous_simple_type(&attr.typing, schema) {
                 // Anonymous inline union - export inline
                 attr_elem = attr_elem.with_child(self.export_simple_type_inline(attr_type, schema)?);
             } else {
                 // Named type or non-union - use type reference
                 let type_name = self.get_simple_type_xsd_name(&attr.typing, schema);
                 attr_elem = attr_elem.with_attr("type", type_name);
-
-                // Required/optional (use="required" vs use="optional")
-                if *attr.required() {
-                    attr_elem = attr_elem.with_attr("use", "required");
-                } // Optional is the default, no need to specify
-
             }
 
+            // Required/optional (use="required" vs use="optional")
+            if *attr.required() {
+                attr_elem = attr_elem.with_attr("use", "required");
+            } // Optional is the default, no need to specify
+
             result.push(attr_elem);
         }
 
@@ -412,8 +725,28 @@ impl XsdExporter {
         element: &model::Element,
         schema: &model::Schema,
     ) -> Result<Element> {
+        // refer to top-level elements, like the head of a substitution group
+        // so its members are accepted as well. prefixed elements are declared by the
+        // schema of their namespace, like their attributes
+        if *element.global_ref()
+            || schema.get_substitution_head(element).is_some()
+            || model::namespace_uri(element.name()).is_some()
+        {
+            let elem = Element::new("xs:element")
+                .with_attr("ref", element.name())
+                .with_annotation(element.comments())
+                .with_attr("minOccurs", element.min_occurs().to_string());
+
+            return Ok(match element.max_occurs() {
+                Some(max) => elem.with_attr("maxOccurs", max.to_string()),
+                None => elem.with_attr("maxOccurs", "unbounded"),
+            });
+        }
+
         let mut elem = Element::new("xs:element")
-            .with_attr("name", element.name());
+            .with_attr("name", element.name())
+            .with_annotation(element.comments())
+            .with_appinfo(self.export_rules(element, schema));
 
         // Occurrence constraints
         elem = elem.with_attr("minOccurs", element.min_occurs().to_string());
@@ -429,7 +762,7 @@ impl XsdExporter {
 
             // Check if this is an anonymous type (inline facets or inline unions)
             if schema.get_type_name_for_simpletype(simple_ref).is_none() &&
-               (simple_type.is_derived() || matches!(simple_type, model::SimpleType::Union { .. })) {
+               (simple_type.is_derived() || matches!(simple_type, model::SimpleType::Union { .. } | model::SimpleType::List { .. })) {
                 // Export as anonymous inline simpleType
                 elem = elem.with_child(self.export_simple_type_inline(simple_type, schema)?);
             } else {
@@ -437,16 +770,75 @@ impl XsdExporter {
                 let type_name = self.get_simple_type_xsd_name(simple_ref, schema);
                 elem = elem.with_attr("type", type_name);
             }
+        } else if let Some(type_name) = self.named_group_type(element, schema) {
+            elem = self.with_named_group_type(elem, &type_name, element, schema)?;
         } else if let Some(group_type) = element.typing().grouptype(schema) {
             let mut complex_type_elem = Element::new("xs:complexType");
-            complex_type_elem = complex_type_elem.with_child(self.export_group_content(group_type, schema)?);
+            complex_type_elem = self.with_content(complex_type_elem, group_type, schema)?;
+
+            // attributes of the element and of its block type
+            for attr_elem in self.export_attributes(&element.group_merged_attributes(schema), schema)? {
+                complex_type_elem = complex_type_elem.with_child(attr_elem);
+            }
+
+            // assertions come after the attributes
+            for assert_elem in self.export_assertions(group_type.merged_assertions(schema)) {
+                complex_type_elem = complex_type_elem.with_child(assert_elem);
+            }
+
             elem = elem.with_child(complex_type_elem);
-        } else {
+        }
+
+        // identity constraints come after the type of the element
+        for constraint_elem in self.export_constraints(element) {
+            elem = elem.with_child(constraint_elem);
         }
 
         Ok(elem)
     }
 
+    /// the name of the complex type of a local element, to refer to instead of repeating
+    /// its content. That is what ends the nesting of recursive types
+    fn named_group_type(&self, element: &model::Element, schema: &model::Schema) -> Option<String> {
+        let model::TypeRef::Group(group_ref) = element.typing() else {
+            return None;
+        };
+        let group = group_ref.resolve(schema);
+
+        // the element can only extend the type with attributes that the type does not declare
+        match element.attributes().keys().any(|name| group.attributes().contains_key(name)) {
+            true => None,
+            false => schema.get_type_name_for_group(group_ref),
+        }
+    }
+
+    /// refer to the named type, extended with the attributes of the element itself
+    fn with_named_group_type(
+        &self,
+        elem: Element,
+        type_name: &str,
+        element: &model::Element,
+        schema: &model::Schema,
+    ) -> Result<Element> {
+        if element.attributes().is_empty() {
+            return Ok(elem.with_attr("type", type_name));
+        }
+
+        let mut extension = Element::new("xs:extension").with_attr("base", type_name);
+        for attr_elem in self.export_attributes(element.attributes(), schema)? {
+            extension = extension.with_child(attr_elem);
+        }
+
+        let mut complex_type_elem = Element::new("xs:complexType");
+        if element.is_mixed_content(schema) {
+            complex_type_elem = complex_type_elem.with_attr("mixed", "true");
+        }
+
+        Ok(elem.with_child(
+            complex_type_elem.with_child(Element::new("xs:complexContent").with_child(extension)),
+        ))
+    }
+
     /// Export an inline anonymous simpleType (for inline facets or inline unions)
     fn export_simple_type_inline(
         &self,
@@ -457,9 +849,7 @@ impl XsdExporter {
 
         match simple_type {
             model::SimpleType::Derived { base, restrictions, .. } => {
-                let base_name = base.resolve(schema).to_type_name(schema);
-                let mut restriction_elem = Element::new("xs:restriction")
-                    .with_attr("base", format!("xs:{}", self.map_primitive_to_xsd(&base_name)));
+                let mut restriction_elem = self.export_restriction_base(base, schema)?;
 
                 for facet_elem in self.export_restrictions(restrictions)? {
                     restriction_elem = restriction_elem.with_child(facet_elem);
@@ -487,6 +877,9 @@ impl XsdExporter {
                         
                 );
             }
+            model::SimpleType::List { item_type, separator: _ } => {
+                simple_type_elem = simple_type_elem.with_child(self.export_list(item_type, schema)?);
+            }
             _ => {
                 // Shouldn't happen for inline types, but handle gracefully
             }
@@ -495,6 +888,48 @@ impl XsdExporter {
         Ok(simple_type_elem)
     }
 
+    /// xs:restriction of the base type, which is declared inside of it when it has no name,
+    /// so that restrictions form a chain up to the primitive
+    fn export_restriction_base(
+        &self,
+        base: &model::Ref<model::SimpleType>,
+        schema: &model::Schema,
+    ) -> Result<Element> {
+        if self.is_anonymous_simple_type(base, schema) {
+            return Ok(Element::new("xs:restriction")
+                .with_child(self.export_simple_type_inline(base.resolve(schema), schema)?));
+        }
+
+        Ok(Element::new("xs:restriction")
+            .with_attr("base", self.get_simple_type_xsd_name(base, schema)))
+    }
+
+    /// xs:list of the item type, which is declared inside of it when it has no name
+    fn export_list(
+        &self,
+        item_type: &model::Ref<model::SimpleType>,
+        schema: &model::Schema,
+    ) -> Result<Element> {
+        let item = item_type.resolve(schema);
+
+        if self.is_anonymous_simple_type(item_type, schema) {
+            Ok(Element::new("xs:list").with_child(self.export_simple_type_inline(item, schema)?))
+        } else {
+            Ok(Element::new("xs:list")
+                .with_attr("itemType", self.get_simple_type_xsd_name(item_type, schema)))
+        }
+    }
+
+    /// whether the simple type has no name of its own and has to be declared where it is used
+    fn is_anonymous_simple_type(
+        &self,
+        simple_ref: &model::Ref<model::SimpleType>,
+        schema: &model::Schema,
+    ) -> bool {
+        schema.get_type_name_for_simpletype(simple_ref).is_none()
+            && !simple_ref.resolve(schema).is_builtin()
+    }
+
     /// Export restriction facets (helper for reuse)
     fn export_restrictions(
         &self,
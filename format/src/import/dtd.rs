@@ -0,0 +1,935 @@
+use crate::import::Importer;
+use crate::model;
+use crate::model::restriction::SimpleTypeRestriction;
+use crate::model::{Duplicity, GroupBuilder, GroupItem, GroupType, PrimitiveType, Ref, TypeRef};
+use crate::tools::default;
+use anyhow::{anyhow, bail, Context};
+use log::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// maximum nesting of parameter entity references before we assume the references form a cycle
+const MAX_ENTITY_DEPTH: usize = 64;
+
+/// names that cannot be used for generated type names because they are WHAS primitives
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "String", "URI", "DateTimestamp", "DateTime", "Date", "Time", "Duration", "Boolean", "Bool",
+    "Integer", "Int", "Float", "Double", "Short", "Decimal", "IDRefs", "IDRef", "ID", "Lang",
+    "NoColName", "Token", "NameTokens", "NameToken", "Name",
+];
+
+/// Importer for XML Document Type Definitions.
+///
+/// Reads ELEMENT, ATTLIST and ENTITY declarations, expands parameter entities
+/// (both internal and external ones) and INCLUDE/IGNORE conditional sections.
+///
+/// Every element with element content or attributes gets a named Type derived from
+/// its element name, so that recursive content models can be expressed.
+/// Text-only elements without attributes are typed as String.
+pub struct DtdImporter {
+    /// the DTD text to import
+    source: String,
+
+    /// directory relative to which external parameter entities are resolved
+    base_dir: PathBuf,
+}
+
+impl DtdImporter {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            base_dir: PathBuf::new(),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .context(format!("reading DTD from {}", path.display()))?;
+
+        Ok(Self {
+            source,
+            base_dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        })
+    }
+}
+
+impl Importer for DtdImporter {
+    fn import_schema(&mut self) -> anyhow::Result<model::Schema> {
+        let mut declarations = DtdDeclarations::default();
+        declarations.read(&self.source, &self.base_dir, 0)?;
+
+        info!(
+            "read {} element declarations from DTD",
+            declarations.elements.len()
+        );
+
+        DtdCompiler::new(declarations).compile()
+    }
+}
+
+//
+// DECLARATIONS
+//
+
+/// content specification of an <!ELEMENT> declaration
+#[derive(Debug, Clone, PartialEq)]
+enum ContentSpec {
+    Empty,
+    Any,
+    /// (#PCDATA | a | b)*. an empty list means text-only content
+    Mixed(Vec<String>),
+    Children(Particle),
+}
+
+/// content particle in a children content model
+#[derive(Debug, Clone, PartialEq)]
+enum Particle {
+    Name(String, Occurrence),
+    Sequence(Vec<Particle>, Occurrence),
+    Choice(Vec<Particle>, Occurrence),
+}
+
+/// occurrence indicator following a content particle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Occurrence {
+    Once,
+    Optional,
+    Any,
+    Min1,
+}
+
+impl From<Occurrence> for Duplicity {
+    fn from(occ: Occurrence) -> Self {
+        match occ {
+            Occurrence::Once => Duplicity::Single,
+            Occurrence::Optional => Duplicity::Optional,
+            Occurrence::Any => Duplicity::Any,
+            Occurrence::Min1 => Duplicity::Min1,
+        }
+    }
+}
+
+/// single attribute definition in an <!ATTLIST> declaration
+#[derive(Debug, Clone, PartialEq)]
+struct AttDef {
+    name: String,
+    ty: AttType,
+    default: AttDefault,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttType {
+    CData,
+    Id,
+    IdRef,
+    IdRefs,
+    Entity,
+    Entities,
+    NameToken,
+    NameTokens,
+    /// enumerated values, including NOTATION enumerations
+    Enumeration(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttDefault {
+    Required,
+    Implied,
+    Fixed(String),
+    Value(String),
+}
+
+/// value of a parameter entity
+#[derive(Debug, Clone)]
+enum ParameterEntity {
+    /// <!ENTITY % name "value">
+    Internal(String),
+    /// <!ENTITY % name SYSTEM "file.ent">
+    External(PathBuf),
+}
+
+/// all declarations read from a DTD, with parameter entities already expanded
+#[derive(Debug, Default)]
+struct DtdDeclarations {
+    /// element declarations in the order they were declared
+    elements: Vec<(String, ContentSpec)>,
+
+    /// attribute definitions per element name
+    attlists: HashMap<String, Vec<AttDef>>,
+
+    /// parameter entities known so far
+    parameter_entities: HashMap<String, ParameterEntity>,
+}
+
+impl DtdDeclarations {
+    /// read declarations from DTD text. `dir` is the directory relative to which
+    /// external entities in this text are resolved
+    fn read(&mut self, text: &str, dir: &Path, depth: usize) -> anyhow::Result<()> {
+        if depth > MAX_ENTITY_DEPTH {
+            bail!("parameter entities nested too deeply. Do they reference each other?");
+        }
+
+        let mut rest = text;
+
+        loop {
+            rest = rest.trim_start();
+
+            if rest.is_empty() {
+                return Ok(());
+            }
+
+            if let Some(inner) = rest.strip_prefix("<!--") {
+                let end = inner.find("-->").ok_or(anyhow!("unterminated comment in DTD"))?;
+                rest = &inner[end + 3..];
+            } else if let Some(inner) = rest.strip_prefix("<?") {
+                let end = inner
+                    .find("?>")
+                    .ok_or(anyhow!("unterminated processing instruction in DTD"))?;
+                rest = &inner[end + 2..];
+            } else if let Some(inner) = rest.strip_prefix("<![") {
+                let (keyword, body, after) = split_conditional_section(inner)?;
+
+                match self.expand(keyword, depth)?.trim() {
+                    "INCLUDE" => self.read(body, dir, depth)?,
+                    "IGNORE" => debug!("skipping IGNORE section"),
+                    other => bail!("unknown conditional section keyword '{}'", other),
+                }
+
+                rest = after;
+            } else if rest.starts_with("<!") {
+                let end = declaration_end(rest)?;
+                self.declare(&rest[2..end - 1], dir, depth)?;
+                rest = &rest[end..];
+            } else if let Some(inner) = rest.strip_prefix('%') {
+                let end = inner
+                    .find(';')
+                    .ok_or(anyhow!("unterminated parameter entity reference"))?;
+                let (value, entity_dir) = self.resolve_entity(&inner[..end], dir)?;
+                self.read(&value, &entity_dir, depth + 1)?;
+                rest = &inner[end + 1..];
+            } else {
+                bail!("unexpected content in DTD near '{}'", snippet(rest));
+            }
+        }
+    }
+
+    /// process a single markup declaration, without the surrounding '<!' and '>'
+    fn declare(&mut self, decl: &str, dir: &Path, depth: usize) -> anyhow::Result<()> {
+        let keyword = decl
+            .split(|c: char| c.is_whitespace() || c == '%')
+            .next()
+            .unwrap_or_default();
+        let body = &decl[keyword.len()..];
+
+        match keyword {
+            "ENTITY" => self.declare_entity(body, dir, depth),
+            "ELEMENT" => {
+                let mut tokens = Tokens::new(&self.expand(body, depth)?)?;
+                let name = tokens.expect_name()?;
+                let spec = tokens.content_spec()?;
+                tokens.expect_end()?;
+
+                if self.elements.iter().any(|(existing, _)| existing == &name) {
+                    bail!("element '{}' is declared more than once", name);
+                }
+
+                self.elements.push((name, spec));
+                Ok(())
+            }
+            "ATTLIST" => {
+                let mut tokens = Tokens::new(&self.expand(body, depth)?)?;
+                let element = tokens.expect_name()?;
+                let attlist = self.attlists.entry(element).or_default();
+
+                while !tokens.is_end() {
+                    let attdef = tokens.attribute_definition()?;
+
+                    // the first declaration of an attribute is binding, later ones are ignored
+                    if !attlist.iter().any(|existing| existing.name == attdef.name) {
+                        attlist.push(attdef);
+                    }
+                }
+
+                Ok(())
+            }
+            "NOTATION" => Ok(()),
+            other => bail!("unknown DTD declaration '<!{}'", other),
+        }
+    }
+
+    fn declare_entity(&mut self, body: &str, dir: &Path, depth: usize) -> anyhow::Result<()> {
+        let body = body.trim_start();
+
+        // general entities are irrelevant for the structure of the schema
+        let Some(body) = body.strip_prefix('%') else {
+            return Ok(());
+        };
+
+        let mut tokens = Tokens::new(body)?;
+        let name = tokens.expect_name()?;
+
+        let entity = match tokens.next() {
+            Some(Token::Literal(value)) => ParameterEntity::Internal(self.expand(&value, depth)?),
+            Some(Token::Name(kw)) if kw == "SYSTEM" => {
+                ParameterEntity::External(dir.join(tokens.expect_literal()?))
+            }
+            Some(Token::Name(kw)) if kw == "PUBLIC" => {
+                tokens.expect_literal()?;
+                ParameterEntity::External(dir.join(tokens.expect_literal()?))
+            }
+            other => bail!("invalid declaration of parameter entity '{}': {:?}", name, other),
+        };
+
+        // the first declaration of an entity is binding, which is how DTDs allow overrides
+        self.parameter_entities.entry(name).or_insert(entity);
+
+        Ok(())
+    }
+
+    /// get the replacement text of a parameter entity and the directory to resolve its contents in
+    fn resolve_entity(&self, name: &str, dir: &Path) -> anyhow::Result<(String, PathBuf)> {
+        match self.parameter_entities.get(name) {
+            Some(ParameterEntity::Internal(value)) => Ok((value.clone(), dir.to_path_buf())),
+            Some(ParameterEntity::External(path)) => Ok((
+                std::fs::read_to_string(path)
+                    .context(format!("reading parameter entity '{}' from {}", name, path.display()))?,
+                path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            )),
+            None => Err(anyhow!("undeclared parameter entity '%{};'", name)),
+        }
+    }
+
+    /// replace all parameter entity references in a piece of declaration text
+    fn expand(&self, text: &str, depth: usize) -> anyhow::Result<String> {
+        if depth > MAX_ENTITY_DEPTH {
+            bail!("parameter entities nested too deeply. Do they reference each other?");
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(pos) = rest.find('%') {
+            result.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+            let name_len = after
+                .find(|c: char| !is_name_char(c))
+                .unwrap_or(after.len());
+
+            // '%' that is not followed by a name and ';' is not a reference
+            if name_len == 0 || !after[name_len..].starts_with(';') {
+                result.push('%');
+                rest = after;
+                continue;
+            }
+
+            let (value, _) = self.resolve_entity(&after[..name_len], Path::new(""))?;
+
+            // the spec pads the replacement text with spaces when it is used in declarations
+            result.push(' ');
+            result.push_str(&self.expand(&value, depth + 1)?);
+            result.push(' ');
+
+            rest = &after[name_len + 1..];
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+/// split the text following '<![' into the section keyword, its body and the remaining text
+fn split_conditional_section(text: &str) -> anyhow::Result<(&str, &str, &str)> {
+    let open = text
+        .find('[')
+        .ok_or(anyhow!("conditional section without '['"))?;
+    let bytes = text.as_bytes();
+    let mut nesting = 1;
+    let mut pos = open + 1;
+
+    while pos < bytes.len() {
+        if bytes[pos..].starts_with(b"<![") {
+            nesting += 1;
+            pos += 3;
+        } else if bytes[pos..].starts_with(b"]]>") {
+            nesting -= 1;
+            if nesting == 0 {
+                return Ok((&text[..open], &text[open + 1..pos], &text[pos + 3..]));
+            }
+            pos += 3;
+        } else {
+            pos += 1;
+        }
+    }
+
+    bail!("unterminated conditional section")
+}
+
+/// position just after the '>' that closes the declaration at the start of the text
+fn declaration_end(text: &str) -> anyhow::Result<usize> {
+    let mut quote = None;
+
+    for (pos, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Ok(pos + 1),
+            _ => {}
+        }
+    }
+
+    bail!("unterminated declaration near '{}'", snippet(text))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | ':' | '.' | '-') || !c.is_ascii()
+}
+
+fn snippet(text: &str) -> String {
+    text.chars().take(40).collect()
+}
+
+//
+// TOKENIZER
+//
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// names, name tokens and keywords like #PCDATA
+    Name(String),
+    /// quoted string
+    Literal(String),
+    Open,
+    Close,
+    Pipe,
+    Comma,
+    Optional,
+    Any,
+    Min1,
+}
+
+struct Tokens {
+    items: Vec<Token>,
+    pos: usize,
+}
+
+impl Tokens {
+    fn new(text: &str) -> anyhow::Result<Self> {
+        let mut items = vec![];
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            items.push(match c {
+                c if c.is_whitespace() => continue,
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '|' => Token::Pipe,
+                ',' => Token::Comma,
+                '?' => Token::Optional,
+                '*' => Token::Any,
+                '+' => Token::Min1,
+                '"' | '\'' => {
+                    let rest = &text[start + 1..];
+                    let end = rest
+                        .find(c)
+                        .ok_or(anyhow!("unterminated literal near '{}'", snippet(rest)))?;
+                    for _ in 0..rest[..=end].chars().count() {
+                        chars.next();
+                    }
+                    Token::Literal(rest[..end].to_string())
+                }
+                c if c == '#' || is_name_char(c) => {
+                    let mut name = c.to_string();
+                    while let Some((_, next)) = chars.peek() {
+                        if !is_name_char(*next) {
+                            break;
+                        }
+                        name.push(*next);
+                        chars.next();
+                    }
+                    Token::Name(name)
+                }
+                other => bail!("unexpected character '{}' in declaration", other),
+            });
+        }
+
+        Ok(Self { items, pos: 0 })
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.items.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.items.get(self.pos)
+    }
+
+    fn is_end(&self) -> bool {
+        self.pos >= self.items.len()
+    }
+
+    fn expect_end(&self) -> anyhow::Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(anyhow!("unexpected {:?} at end of declaration", token)),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> anyhow::Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            other => Err(anyhow!("expected {:?} but found {:?}", expected, other)),
+        }
+    }
+
+    fn expect_name(&mut self) -> anyhow::Result<String> {
+        match self.next() {
+            Some(Token::Name(name)) => Ok(name),
+            other => Err(anyhow!("expected a name but found {:?}", other)),
+        }
+    }
+
+    fn expect_literal(&mut self) -> anyhow::Result<String> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(value),
+            other => Err(anyhow!("expected a quoted string but found {:?}", other)),
+        }
+    }
+
+    fn occurrence(&mut self) -> Occurrence {
+        let occ = match self.peek() {
+            Some(Token::Optional) => Occurrence::Optional,
+            Some(Token::Any) => Occurrence::Any,
+            Some(Token::Min1) => Occurrence::Min1,
+            _ => return Occurrence::Once,
+        };
+        self.pos += 1;
+        occ
+    }
+
+    fn content_spec(&mut self) -> anyhow::Result<ContentSpec> {
+        match self.next() {
+            Some(Token::Name(kw)) if kw == "EMPTY" => Ok(ContentSpec::Empty),
+            Some(Token::Name(kw)) if kw == "ANY" => Ok(ContentSpec::Any),
+            Some(Token::Open) if self.peek() == Some(&Token::Name("#PCDATA".into())) => {
+                self.pos += 1;
+                let mut names = vec![];
+
+                while self.peek() == Some(&Token::Pipe) {
+                    self.pos += 1;
+                    names.push(self.expect_name()?);
+                }
+
+                self.expect(Token::Close)?;
+
+                // (#PCDATA)* and (#PCDATA) are equivalent
+                if self.peek() == Some(&Token::Any) {
+                    self.pos += 1;
+                } else if !names.is_empty() {
+                    bail!("mixed content with elements must be repeatable: (#PCDATA | ..)*");
+                }
+
+                Ok(ContentSpec::Mixed(names))
+            }
+            Some(Token::Open) => Ok(ContentSpec::Children(self.group()?)),
+            other => Err(anyhow!("invalid content specification starting with {:?}", other)),
+        }
+    }
+
+    /// parse a sequence or choice group of which the '(' has already been consumed
+    fn group(&mut self) -> anyhow::Result<Particle> {
+        let mut items = vec![self.particle()?];
+        let mut separator = None;
+
+        loop {
+            match self.next() {
+                Some(Token::Close) => break,
+                Some(sep @ (Token::Comma | Token::Pipe)) => {
+                    if separator.get_or_insert(sep.clone()) != &sep {
+                        bail!("cannot mix ',' and '|' in a single content model group");
+                    }
+                    items.push(self.particle()?);
+                }
+                other => bail!("unexpected {:?} in content model", other),
+            }
+        }
+
+        let occurrence = self.occurrence();
+
+        Ok(match separator {
+            Some(Token::Pipe) => Particle::Choice(items, occurrence),
+            _ => Particle::Sequence(items, occurrence),
+        })
+    }
+
+    fn particle(&mut self) -> anyhow::Result<Particle> {
+        match self.next() {
+            Some(Token::Name(name)) => Ok(Particle::Name(name, self.occurrence())),
+            Some(Token::Open) => self.group(),
+            other => Err(anyhow!("expected element name or group but found {:?}", other)),
+        }
+    }
+
+    fn attribute_definition(&mut self) -> anyhow::Result<AttDef> {
+        let name = self.expect_name()?;
+
+        let ty = match self.next() {
+            Some(Token::Name(kw)) => match kw.as_str() {
+                "CDATA" => AttType::CData,
+                "ID" => AttType::Id,
+                "IDREF" => AttType::IdRef,
+                "IDREFS" => AttType::IdRefs,
+                "ENTITY" => AttType::Entity,
+                "ENTITIES" => AttType::Entities,
+                "NMTOKEN" => AttType::NameToken,
+                "NMTOKENS" => AttType::NameTokens,
+                "NOTATION" => {
+                    self.expect(Token::Open)?;
+                    AttType::Enumeration(self.enumeration()?)
+                }
+                other => bail!("unknown type '{}' for attribute '{}'", other, name),
+            },
+            Some(Token::Open) => AttType::Enumeration(self.enumeration()?),
+            other => bail!("expected type for attribute '{}' but found {:?}", name, other),
+        };
+
+        let default = match self.next() {
+            Some(Token::Name(kw)) if kw == "#REQUIRED" => AttDefault::Required,
+            Some(Token::Name(kw)) if kw == "#IMPLIED" => AttDefault::Implied,
+            Some(Token::Name(kw)) if kw == "#FIXED" => AttDefault::Fixed(self.expect_literal()?),
+            Some(Token::Literal(value)) => AttDefault::Value(value),
+            other => bail!("expected default for attribute '{}' but found {:?}", name, other),
+        };
+
+        Ok(AttDef { name, ty, default })
+    }
+
+    /// enumerated values of which the '(' has already been consumed
+    fn enumeration(&mut self) -> anyhow::Result<Vec<String>> {
+        let mut values = vec![self.expect_name()?];
+
+        loop {
+            match self.next() {
+                Some(Token::Pipe) => values.push(self.expect_name()?),
+                Some(Token::Close) => return Ok(values),
+                other => bail!("unexpected {:?} in enumeration", other),
+            }
+        }
+    }
+}
+
+//
+// MODEL CONSTRUCTION
+//
+
+/// turns the DTD declarations into model definitions
+struct DtdCompiler {
+    declarations: DtdDeclarations,
+
+    schema: model::Schema,
+
+    /// preliminary references to the named Types of elements
+    element_types: HashMap<String, Ref<model::Group>>,
+
+    /// names of elements that are used in the content model of another element
+    referenced: HashSet<String>,
+}
+
+impl DtdCompiler {
+    fn new(declarations: DtdDeclarations) -> Self {
+        Self {
+            declarations,
+            schema: default(),
+            element_types: default(),
+            referenced: default(),
+        }
+    }
+
+    fn compile(mut self) -> anyhow::Result<model::Schema> {
+        let elements = self.declarations.elements.clone();
+
+        // reserve Type names first so content models can refer to them recursively
+        let mut used_names = HashSet::new();
+
+        for (name, spec) in &elements {
+            if self.needs_named_type(name, spec) {
+                let typename = type_name_for_element(name, &mut used_names);
+                let reff = self.schema.register_preliminary_group_name(&typename)?;
+                self.element_types.insert(name.clone(), reff);
+            }
+        }
+
+        for (name, spec) in &elements {
+            if let Some(reff) = self.element_types.get(name).cloned() {
+                let group = self.compile_content(name, spec)?;
+                self.schema
+                    .register_preliminary_id_type(&reff, TypeRef::Group(group))?;
+            }
+        }
+
+        // elements that are not part of any content model are the candidate document elements.
+        // when every element is referenced, the first declared element is assumed to be the root
+        let roots = elements
+            .iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| !self.referenced.contains(name))
+            .collect::<Vec<_>>();
+
+        if roots.is_empty() {
+            if let Some((first, _)) = elements.first() {
+                self.element(first, Occurrence::Once)?;
+            }
+        }
+
+        for name in roots {
+            self.element(&name, Occurrence::Once)?;
+        }
+
+        Ok(self.schema)
+    }
+
+    fn needs_named_type(&self, element: &str, spec: &ContentSpec) -> bool {
+        let has_attributes = self
+            .declarations
+            .attlists
+            .get(element)
+            .is_some_and(|attlist| !attlist.is_empty());
+
+        match spec {
+            ContentSpec::Children(_) | ContentSpec::Any => true,
+            ContentSpec::Mixed(names) => has_attributes || !names.is_empty(),
+            ContentSpec::Empty => has_attributes,
+        }
+    }
+
+    fn content_spec(&self, element: &str) -> Option<&ContentSpec> {
+        self.declarations
+            .elements
+            .iter()
+            .find(|(name, _)| name == element)
+            .map(|(_, spec)| spec)
+    }
+
+    /// register an element reference with the given occurrence
+    fn element(
+        &mut self,
+        name: &str,
+        occurrence: Occurrence,
+    ) -> anyhow::Result<Ref<model::Element>> {
+        let typing = match self.element_types.get(name) {
+            Some(reff) => TypeRef::Group(reff.clone()),
+            None => match self.content_spec(name) {
+                Some(ContentSpec::Empty) => TypeRef::Group(
+                    self.schema
+                        .register_group(GroupBuilder::default().build()?)?,
+                ),
+                Some(_) => TypeRef::Simple(self.schema.register_simple_type(default())?),
+                None => {
+                    warn!("element '{}' is used but not declared. Assuming text content", name);
+                    TypeRef::Simple(self.schema.register_simple_type(default())?)
+                }
+            },
+        };
+
+        self.schema.register_element(
+            model::ElementBuilder::default()
+                .name(name.to_string())
+                .duplicity(occurrence.into())
+                .typing(typing)
+                .build()?,
+        )
+    }
+
+    /// create the group for the content model of a named element Type
+    fn compile_content(
+        &mut self,
+        element: &str,
+        spec: &ContentSpec,
+    ) -> anyhow::Result<Ref<model::Group>> {
+        let mut builder = GroupBuilder::default();
+
+        match spec {
+            ContentSpec::Empty => {}
+            ContentSpec::Any => {
+                let names = self
+                    .declarations
+                    .elements
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+
+                builder
+                    .mixed(true)
+                    .ty(GroupType::Choice)
+                    .duplicity(Duplicity::Any)
+                    .items(self.child_elements(&names)?);
+            }
+            ContentSpec::Mixed(names) => {
+                builder.mixed(true).items(self.child_elements(names)?);
+
+                if !names.is_empty() {
+                    builder.ty(GroupType::Choice).duplicity(Duplicity::Any);
+                }
+            }
+            ContentSpec::Children(particle) => {
+                // the occurrence of the outer group repeats the content model as a whole
+                let (ty, items, occ) = match particle.clone() {
+                    Particle::Name(name, occ) => (
+                        GroupType::Sequence,
+                        vec![Particle::Name(name, occ)],
+                        Occurrence::Once,
+                    ),
+                    Particle::Sequence(items, occ) => (GroupType::Sequence, items, occ),
+                    Particle::Choice(items, occ) => (GroupType::Choice, items, occ),
+                };
+
+                builder
+                    .ty(ty)
+                    .duplicity(occ.into())
+                    .items(self.group_items(items)?);
+            }
+        }
+
+        builder.attributes(self.attributes(element)?);
+
+        self.schema.register_group(builder.build()?)
+    }
+
+    /// the elements of a choice that repeats as a whole, like mixed content
+    fn child_elements(&mut self, names: &[String]) -> anyhow::Result<Vec<GroupItem>> {
+        names
+            .iter()
+            .map(|name| Ok(self.child_element(name, Occurrence::Once)?.into()))
+            .collect()
+    }
+
+    /// register an element that is part of a content model
+    fn child_element(
+        &mut self,
+        name: &str,
+        occurrence: Occurrence,
+    ) -> anyhow::Result<Ref<model::Element>> {
+        self.referenced.insert(name.to_string());
+        self.element(name, occurrence)
+    }
+
+    fn group_items(&mut self, particles: Vec<Particle>) -> anyhow::Result<Vec<GroupItem>> {
+        particles
+            .into_iter()
+            .map(|particle| {
+                Ok(match particle {
+                    Particle::Name(name, occ) => self.child_element(&name, occ)?.into(),
+                    Particle::Sequence(items, occ) => {
+                        let group = GroupBuilder::default()
+                            .ty(GroupType::Sequence)
+                            .duplicity(occ.into())
+                            .items(self.group_items(items)?)
+                            .build()?;
+                        self.schema.register_group(group)?.into()
+                    }
+                    Particle::Choice(items, occ) => {
+                        let group = GroupBuilder::default()
+                            .ty(GroupType::Choice)
+                            .duplicity(occ.into())
+                            .items(self.group_items(items)?)
+                            .build()?;
+                        self.schema.register_group(group)?.into()
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn attributes(&mut self, element: &str) -> anyhow::Result<model::Attributes> {
+        let attdefs = self
+            .declarations
+            .attlists
+            .get(element)
+            .cloned()
+            .unwrap_or_default();
+
+        let refs = attdefs
+            .iter()
+            .map(|attdef| self.attribute(attdef))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(model::Attributes::new(refs, &self.schema))
+    }
+
+    fn attribute(&mut self, attdef: &AttDef) -> anyhow::Result<Ref<model::Attribute>> {
+        let typing = match &attdef.ty {
+            AttType::CData | AttType::Entity | AttType::Entities => {
+                self.schema.register_primitive_type(PrimitiveType::String)?
+            }
+            AttType::Id => self.schema.register_primitive_type(PrimitiveType::ID)?,
+            AttType::IdRef => self.schema.register_primitive_type(PrimitiveType::IDRef)?,
+            AttType::IdRefs => self.schema.register_primitive_type(PrimitiveType::IDRefs)?,
+            AttType::NameToken => self.schema.register_primitive_type(PrimitiveType::NameToken)?,
+            AttType::NameTokens => {
+                self.schema.register_primitive_type(PrimitiveType::NameTokens)?
+            }
+            AttType::Enumeration(values) => {
+                let base = self.schema.register_primitive_type(PrimitiveType::String)?;
+                self.schema.register_simple_type(model::SimpleType::Derived {
+                    base,
+                    restrictions: SimpleTypeRestriction {
+                        enumeration: Some(values.clone()),
+                        ..default()
+                    },
+                    abstract_type: false,
+                })?
+            }
+        };
+
+        let (required, default_value) = match &attdef.default {
+            AttDefault::Required => (true, None),
+            AttDefault::Implied => (false, None),
+            AttDefault::Fixed(value) | AttDefault::Value(value) => (false, Some(value.clone())),
+        };
+
+        self.schema.register_attribute(
+            model::AttributeBuilder::default()
+                .name(attdef.name.clone())
+                .required(required)
+                .typing(typing)
+                .default_value(default_value)
+                .build()?,
+        )
+    }
+}
+
+/// derive a capitalized Type name from an element name, like 'list-item' => 'ListItem'
+fn type_name_for_element(element: &str, used: &mut HashSet<String>) -> String {
+    let mut base = element
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    if !base.starts_with(|c: char| c.is_ascii_uppercase()) {
+        base = format!("T{}", base);
+    }
+
+    if RESERVED_TYPE_NAMES.contains(&base.as_str()) {
+        base.push_str("Type");
+    }
+
+    let mut candidate = base.clone();
+    let mut counter = 2;
+
+    while used.contains(&candidate) {
+        candidate = format!("{}{}", base, counter);
+        counter += 1;
+    }
+
+    used.insert(candidate.clone());
+    candidate
+}
@@ -1,3 +1,4 @@
 mod common;
+mod dtd;
 
-pub use common::*;
+pub use {common::*, dtd::*};
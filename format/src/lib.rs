@@ -9,6 +9,7 @@ mod export;
 mod formats;
 mod import;
 pub mod model;
+mod project;
 mod sourced;
 pub(crate) mod tests;
 mod tools;
@@ -16,7 +17,15 @@ mod validation;
 
 use pest_derive::Parser;
 pub(crate) use tools::default;
-pub use {crate::model::*, validation::*};
+pub use {
+    crate::export::{
+        DocExporter, DocSite, Exporter, FontoSchemaExporter, RustExporter, SchematronExporter,
+        TypescriptExporter,
+    },
+    crate::model::*,
+    crate::sourced::{FileSystemSources, ImportSearch, Manifest, MemorySources, SourceProvider},
+    validation::*,
+};
 
 #[derive(Parser)]
 #[grammar = "../schema.pest"] // relative to src
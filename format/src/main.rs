@@ -10,6 +10,7 @@ mod export;
 mod formats;
 mod import;
 mod model;
+mod project;
 mod sourced;
 pub(crate) mod tests;
 mod tools;
@@ -23,7 +24,12 @@ use std::fs::File;
 use std::path::Path;
 use tools::default;
 
-use crate::export::{Exporter, FontoSchemaExporter, XsdExporter};
+use crate::export::{
+    DocExporter, Exporter, FontoSchemaExporter, RustExporter, SchematronExporter,
+    TypescriptExporter, WhasExporter, XsdExporter,
+};
+use crate::import::{DtdImporter, Importer};
+use crate::sourced::Manifest;
 use crate::tools::init_logger;
 pub(crate) use {ast::*, cli::*, validation::*};
 
@@ -36,15 +42,26 @@ fn main() -> anyhow::Result<()> {
 
     let args = cli::Args::get();
 
-    if args.fonto {
-        let schema = model::Schema::from_file(&args.input)?;
+    if let Some(command) = &args.command {
+        return run_command(&args, command);
+    }
+
+    let input = args.input()?;
+    let schema = compile(&args, input)?;
+
+    if args.lint {
+        for warning in compiler::lint_naming_convention(&schema) {
+            eprintln!("warning: {}", warning);
+        }
+    }
 
+    if args.fonto && !args.no_fonto {
         // save to file
         if let Some(ref dir) = args.output_dir {
             std::fs::create_dir_all(dir)?;
         }
 
-        let output_filename = Path::new(&args.input)
+        let output_filename = Path::new(input)
             .file_name()
             .unwrap()
             .to_str()
@@ -59,17 +76,19 @@ fn main() -> anyhow::Result<()> {
         )?;
     }
 
-    if args.xsd {
-        let schema = model::Schema::from_file(&args.input)?;
-
+    if args.xsd && !args.no_xsd {
         // Export to XSD
-        let xsd_output = XsdExporter::default().export_schema(&schema)?;
+        let exporter = match args.embed_schematron {
+            true => XsdExporter::default().with_embedded_schematron(),
+            false => XsdExporter::default(),
+        };
+        let xsd_output = exporter.export_schema(&schema)?;
 
         // Save to file
         if let Some(ref dir) = args.output_dir {
             std::fs::create_dir_all(dir)?;
 
-            let output_filename = Path::new(&args.input)
+            let output_filename = Path::new(input)
                 .file_stem()
                 .unwrap()
                 .to_str()
@@ -83,6 +102,96 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    if args.schematron {
+        let sch_output = SchematronExporter::default().export_schema(&schema)?;
+
+        match args.output_dir {
+            Some(ref dir) => {
+                std::fs::create_dir_all(dir)?;
+
+                let output_filename = Path::new(input)
+                    .file_stem()
+                    .unwrap()
+                    .to_str()
+                    .unwrap();
+
+                std::fs::write(format!("{}/{}.sch", dir, output_filename), sch_output)?;
+            }
+            None => println!("{}", sch_output),
+        }
+    }
+
+    Ok(())
+}
+
+fn run_command(args: &cli::Args, command: &cli::Command) -> anyhow::Result<()> {
+    match command {
+        cli::Command::Import { input, output } => {
+            let extension = Path::new(input)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or_default();
+
+            let schema = match extension {
+                "dtd" => DtdImporter::from_file(input)?.import_schema()?,
+                other => anyhow::bail!("cannot import schemas with extension '{}'", other),
+            };
+
+            let whas = WhasExporter::default().export_schema(&schema)?;
+            write_output(output, whas)?;
+        }
+        cli::Command::Doc { input, output } => {
+            let schema = compile(args, input)?;
+            let title = Path::new(input)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+
+            DocExporter::with_title(title)
+                .export_schema(&schema)?
+                .save_to_dir(output)?;
+        }
+        cli::Command::Build { manifest } => {
+            let manifest = Manifest::from_file_or_find(manifest.as_ref())?;
+
+            for path in project::build(&manifest, &args.include_paths)? {
+                println!("wrote {}", path.display());
+            }
+        }
+        cli::Command::Codegen { target } => match target {
+            cli::CodegenTarget::Rust { input, output } => {
+                let schema = compile(args, input)?;
+                write_output(output, RustExporter::default().export_schema(&schema)?)?;
+            }
+            cli::CodegenTarget::Typescript { input, output } => {
+                let schema = compile(args, input)?;
+                write_output(output, TypescriptExporter::default().export_schema(&schema)?)?;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// compile the schema, searching the include paths of the arguments for imports, and show
+/// its warnings
+fn compile(args: &cli::Args, input: &str) -> anyhow::Result<model::Schema> {
+    let schema = model::Schema::from_file_with_include_paths(input, &args.include_paths)?;
+
+    for warning in schema.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+
+    Ok(schema)
+}
+
+/// write generated output to the given file, or to stdout when there is none
+fn write_output(path: &Option<String>, content: String) -> anyhow::Result<()> {
+    match path {
+        Some(path) => std::fs::write(path, content)?,
+        None => print!("{}", content),
+    }
+
     Ok(())
 }
 
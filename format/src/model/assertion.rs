@@ -0,0 +1,245 @@
+use crate::ast;
+use crate::model::{Comment, qualify};
+use derive_getters::Getters;
+use std::fmt;
+
+/// co-occurrence rule on the attributes and children of the elements of a group,
+/// like xs:assert in XSD 1.1
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Getters)]
+pub struct Assertion {
+    test: Expr,
+
+    /// comments right above the assertion, which explain the rule
+    comments: Vec<Comment>,
+}
+
+/// expression of an assertion, evaluated on an element
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum Expr {
+    /// holds when the condition does not, or when the consequence does
+    If(Box<Expr>, Box<Expr>),
+    Or(Vec<Expr>),
+    And(Vec<Expr>),
+    Not(Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+    Paren(Box<Expr>),
+    /// number of child elements with this name
+    Count(String),
+    /// value of an attribute, when present
+    Attribute(String),
+    /// text of the child elements with this name
+    Element(String),
+    Number(String),
+    Literal(String),
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Assertion {
+    pub fn new(test: Expr, comments: Vec<Comment>) -> Self {
+        Self { test, comments }
+    }
+
+    /// the test as an XPath 2.0 expression on the element, as used by xs:assert and Schematron.
+    /// the child elements get the prefix of their namespace, if any
+    pub fn xpath_in(&self, prefix: Option<&str>) -> String {
+        self.test.xpath_in(prefix)
+    }
+
+    /// what the assertion is about: its comments, or otherwise the test itself
+    pub fn message(&self) -> String {
+        match self.comments.is_empty() {
+            true => self.test.to_string(),
+            false => self
+                .comments
+                .iter()
+                .map(Comment::content)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl Expr {
+    /// the expression in XPath, where the names of elements get the prefix
+    pub fn xpath_in(&self, prefix: Option<&str>) -> String {
+        let join = |exprs: &[Expr], op: &str| {
+            exprs
+                .iter()
+                .map(|expr| expr.xpath_in(prefix))
+                .collect::<Vec<_>>()
+                .join(op)
+        };
+
+        match self {
+            Expr::If(condition, then) => {
+                format!(
+                    "if ({}) then {} else true()",
+                    condition.xpath_in(prefix),
+                    then.xpath_in(prefix)
+                )
+            }
+            Expr::Or(exprs) => join(exprs, " or "),
+            Expr::And(exprs) => join(exprs, " and "),
+            Expr::Not(expr) => match expr.as_ref() {
+                Expr::Paren(inner) => format!("not({})", inner.xpath_in(prefix)),
+                expr => format!("not({})", expr.xpath_in(prefix)),
+            },
+            Expr::Compare(left, op, right) => {
+                format!("{} {} {}", left.xpath_in(prefix), op, right.xpath_in(prefix))
+            }
+            Expr::Paren(expr) => format!("({})", expr.xpath_in(prefix)),
+            Expr::Count(name) => format!("count({})", qualify(name, prefix)),
+            Expr::Attribute(name) => format!("@{}", name),
+            Expr::Element(name) => qualify(name, prefix),
+            Expr::Number(number) => number.clone(),
+            Expr::Literal(value) => quote(value),
+        }
+    }
+}
+
+/// string literal in quotes that do not occur in it, or with doubled quotes otherwise
+fn quote(value: &str) -> String {
+    match value.contains('\'') {
+        false => format!("'{}'", value),
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+    }
+}
+
+/// the assertion like it is written in WHAS
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assert {}", self.test)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |exprs: &[Expr], op: &str| {
+            exprs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(op)
+        };
+
+        match self {
+            Expr::If(condition, then) => write!(f, "if {} then {}", condition, then),
+            Expr::Or(exprs) => write!(f, "{}", join(exprs, " or ")),
+            Expr::And(exprs) => write!(f, "{}", join(exprs, " and ")),
+            Expr::Not(expr) => write!(f, "not {}", expr),
+            Expr::Compare(left, op, right) => write!(f, "{} {} {}", left, op, right),
+            Expr::Paren(expr) => write!(f, "({})", expr),
+            Expr::Count(name) => write!(f, "count(#{})", name),
+            Expr::Attribute(name) => write!(f, "@{}", name),
+            Expr::Element(name) => write!(f, "#{}", name),
+            Expr::Number(number) => write!(f, "{}", number),
+            Expr::Literal(value) => match (value.contains('\''), value.contains('"')) {
+                (false, _) => write!(f, "'{}'", value),
+                (true, false) => write!(f, "\"{}\"", value),
+                (true, true) => write!(f, "`{}`", value),
+            },
+        }
+    }
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompareOp::Eq => "=",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        })
+    }
+}
+
+impl From<&ast::AssertOp> for CompareOp {
+    fn from(op: &ast::AssertOp) -> Self {
+        match op.token.as_str() {
+            "=" => CompareOp::Eq,
+            "!=" => CompareOp::Ne,
+            "<" => CompareOp::Lt,
+            "<=" => CompareOp::Le,
+            ">" => CompareOp::Gt,
+            _ => CompareOp::Ge,
+        }
+    }
+}
+
+impl From<&ast::AssertExpr> for Expr {
+    fn from(expr: &ast::AssertExpr) -> Self {
+        match expr {
+            ast::AssertExpr::If(ast::AssertIf { condition, then }) => {
+                Expr::If(Box::new(condition.into()), Box::new(then.into()))
+            }
+            ast::AssertExpr::Or(or) => or.into(),
+        }
+    }
+}
+
+// single terms of `and` and `or` are not wrapped
+
+impl From<&ast::AssertOr> for Expr {
+    fn from(ast::AssertOr(terms): &ast::AssertOr) -> Self {
+        match terms.as_slice() {
+            [term] => term.into(),
+            terms => Expr::Or(terms.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<&ast::AssertAnd> for Expr {
+    fn from(ast::AssertAnd(terms): &ast::AssertAnd) -> Self {
+        match terms.as_slice() {
+            [term] => term.into(),
+            terms => Expr::And(terms.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<&ast::AssertNot> for Expr {
+    fn from(expr: &ast::AssertNot) -> Self {
+        match expr {
+            ast::AssertNot::Negation(ast::AssertNegation(negated)) => {
+                Expr::Not(Box::new(negated.as_ref().into()))
+            }
+            ast::AssertNot::Comparison(ast::AssertComparison { left, right: None }) => left.into(),
+            ast::AssertNot::Comparison(ast::AssertComparison {
+                left,
+                right: Some(right),
+            }) => Expr::Compare(
+                Box::new(left.into()),
+                (&right.op).into(),
+                Box::new((&right.operand).into()),
+            ),
+        }
+    }
+}
+
+impl From<&ast::AssertOperand> for Expr {
+    fn from(operand: &ast::AssertOperand) -> Self {
+        match operand {
+            ast::AssertOperand::Count(ast::AssertCount(element)) => {
+                Expr::Count(element.to_string())
+            }
+            ast::AssertOperand::Paren(ast::AssertParen(expr)) => {
+                Expr::Paren(Box::new(expr.as_ref().into()))
+            }
+            ast::AssertOperand::Attribute(attr) => Expr::Attribute(attr.to_string()),
+            ast::AssertOperand::Element(element) => Expr::Element(element.to_string()),
+            ast::AssertOperand::Number(number) => Expr::Number(number.value.clone()),
+            ast::AssertOperand::Literal(literal) => Expr::Literal(literal.unquoted().to_string()),
+        }
+    }
+}
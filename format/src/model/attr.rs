@@ -5,12 +5,12 @@ use derive_builder::Builder;
 use derive_getters::Getters;
 use itertools::Itertools;
 use std::cmp::Ordering;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
-pub struct Attributes(HashMap<String, Ref<Attribute>>);
+pub struct Attributes(BTreeMap<String, Ref<Attribute>>);
 
 impl Attributes {
     pub fn new(list: Vec<Ref<Attribute>>, schema: &model::Schema) -> Self {
@@ -51,7 +51,7 @@ impl Attributes {
 }
 
 impl Deref for Attributes {
-    type Target = HashMap<String, Ref<model::Attribute>>;
+    type Target = BTreeMap<String, Ref<model::Attribute>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -18,3 +18,51 @@ impl From<&ast::Comment> for Comment {
         }
     }
 }
+
+impl Comment {
+    /// the text of the comment without its delimiters and without the indentation it has
+    /// in the schema file. Markdown comments keep their Markdown, with escaped fences unescaped
+    pub fn content(&self) -> String {
+        let text = self.text.as_str();
+
+        let inner = if let Some(line) = text.strip_prefix("//") {
+            return line.strip_prefix(' ').unwrap_or(line).trim_end().to_string();
+        } else if let Some(wild) = text.strip_prefix("/*") {
+            wild.strip_suffix("*/").unwrap_or(wild).to_string()
+        } else if let Some(md) = text.strip_prefix("```") {
+            md.strip_suffix("```")
+                .unwrap_or(md)
+                .replace("$```", "```")
+        } else {
+            text.to_string()
+        };
+
+        dedent(&inner)
+    }
+}
+
+/// remove the indentation that all non-empty lines have in common, and surrounding empty lines
+fn dedent(text: &str) -> String {
+    let mut lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+
+    // text on the line of the opening delimiter is not indented like the rest
+    let first = match lines.first() {
+        Some(first) if !first.trim().is_empty() => Some(lines.remove(0).trim().to_string()),
+        _ => None,
+    };
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    first
+        .into_iter()
+        .chain(lines.iter().map(|line| line.get(indent..).unwrap_or("").to_string()))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
@@ -0,0 +1,180 @@
+use crate::ast;
+use crate::model::qualify;
+use itertools::Itertools;
+use derive_getters::Getters;
+use std::fmt;
+
+/// identity constraint on the values in the descendants of an element,
+/// like xs:unique, xs:key and xs:keyref
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Getters)]
+pub struct IdentityConstraint {
+    kind: ConstraintKind,
+
+    /// names of the elements that select the constrained nodes.
+    /// the first one is found at any depth, the next ones are its children
+    selector: Vec<String>,
+
+    /// where the value of a selected node is found
+    field: ConstraintField,
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum ConstraintKind {
+    /// values are unique when present
+    Unique,
+    /// values are unique and always present. The key can be referred to by its name
+    Key(String),
+    /// values are present in the key with this name
+    KeyRef(String),
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub enum ConstraintField {
+    Attribute(String),
+    Element(String),
+}
+
+impl IdentityConstraint {
+    /// name that identifies the constraint in other formats. Keys keep their own name,
+    /// the others are named after the element they are declared on and their path
+    pub fn name(&self, element: &str) -> String {
+        let suffix = match &self.kind {
+            ConstraintKind::Key(name) => return name.clone(),
+            ConstraintKind::Unique => "unique",
+            ConstraintKind::KeyRef(_) => "ref",
+        };
+
+        let field = match &self.field {
+            ConstraintField::Attribute(name) | ConstraintField::Element(name) => name,
+        };
+
+        format!(
+            "{}-{}-{}-{}",
+            element,
+            self.selector.join("-"),
+            field,
+            suffix
+        )
+    }
+
+    /// the path to the values, like `#chapter/@id`
+    pub fn path(&self) -> String {
+        let field = match &self.field {
+            ConstraintField::Attribute(name) => format!("@{}", name),
+            ConstraintField::Element(name) => format!("#{}", name),
+        };
+
+        self.selector
+            .iter()
+            .map(|step| format!("#{}/", step))
+            .chain([field])
+            .collect()
+    }
+}
+
+/// the constraint like it is written in WHAS
+impl fmt::Display for IdentityConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ConstraintKind::Unique => write!(f, "unique {}", self.path()),
+            ConstraintKind::Key(name) => write!(f, "key {}: {}", name, self.path()),
+            ConstraintKind::KeyRef(key) => write!(f, "keyref {}: {}", self.path(), key),
+        }
+    }
+}
+
+impl From<&ast::Constraint> for IdentityConstraint {
+    fn from(constraint: &ast::Constraint) -> Self {
+        let path = constraint.path();
+
+        Self {
+            kind: match constraint {
+                ast::Constraint::Unique(_) => ConstraintKind::Unique,
+                ast::Constraint::Key(key) => ConstraintKind::Key(key.name.value.clone()),
+                ast::Constraint::KeyRef(keyref) => ConstraintKind::KeyRef(keyref.key.value.clone()),
+            },
+            selector: path.selector.iter().map(ToString::to_string).collect(),
+            field: match &path.field {
+                ast::ConstraintField::Attribute(attr) => {
+                    ConstraintField::Attribute(attr.to_string())
+                }
+                ast::ConstraintField::Element(element) => {
+                    ConstraintField::Element(element.to_string())
+                }
+            },
+        }
+    }
+}
+
+/// editorial rule on an element or on its descendants, exported to Schematron
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Getters)]
+pub struct SchematronRule {
+    /// names of the elements from the element to the nodes the rule applies to.
+    /// the first one is found at any depth, the next ones are its children.
+    /// empty when the rule applies to the element itself
+    context: Vec<String>,
+
+    /// XPath expression that holds for valid documents
+    test: String,
+
+    /// message for when the test does not hold
+    message: String,
+}
+
+impl SchematronRule {
+    /// the context of the rule as an XPath pattern, like `/book//chapter/title`, below the
+    /// pattern of the element it is declared on. The names of the path get the prefix of
+    /// their namespace
+    pub fn context_xpath(&self, element: &str, prefix: Option<&str>) -> String {
+        match self.context.is_empty() {
+            true => element.to_string(),
+            false => format!(
+                "{}//{}",
+                element,
+                self.context.iter().map(|step| qualify(step, prefix)).join("/")
+            ),
+        }
+    }
+}
+
+/// the rule like it is written in WHAS
+impl fmt::Display for SchematronRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let context = self
+            .context
+            .iter()
+            .map(|step| format!("#{}", step))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        write!(f, "rule")?;
+        if !context.is_empty() {
+            write!(f, " {}", context)?;
+        }
+        write!(f, ": {} {}", quote(&self.test), quote(&self.message))
+    }
+}
+
+/// quote a string with the first delimiter of WHAS that does not occur in it
+fn quote(value: &str) -> String {
+    let delimiter = ['`', '"', '\'', '%']
+        .into_iter()
+        .find(|delimiter| !value.contains(*delimiter))
+        .unwrap_or('`');
+
+    format!("{}{}{}", delimiter, value, delimiter)
+}
+
+impl From<&ast::SchematronRule> for SchematronRule {
+    fn from(rule: &ast::SchematronRule) -> Self {
+        Self {
+            context: rule
+                .context
+                .iter()
+                .flat_map(|context| context.0.iter().map(ToString::to_string))
+                .collect(),
+            test: rule.test.0.unquoted().to_string(),
+            message: rule.message.0.unquoted().to_string(),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::model;
 use crate::model::attr::Attributes;
 use crate::model::duplicity::Duplicity;
 use crate::model::r#type::Type;
-use crate::model::{Comment, Ref, TypeRef};
+use crate::model::{Comment, IdentityConstraint, Ref, SchematronRule, TypeRef};
 use derive_builder::Builder;
 use derive_getters::Getters;
 
@@ -29,6 +29,23 @@ pub struct Element {
     /// comments associated with this attribute
     #[builder(default)]
     comments: Vec<Comment>,
+
+    /// identity constraints on the values in the descendants of the element
+    #[builder(default)]
+    constraints: Vec<IdentityConstraint>,
+
+    /// editorial rules on the element and its descendants
+    #[builder(default)]
+    rules: Vec<SchematronRule>,
+
+    /// name of the top-level head element this element can be used in place of
+    #[builder(default)]
+    substitutes: Option<String>,
+
+    /// whether this element refers to the top-level element with its name,
+    /// rather than declaring a local element
+    #[builder(default)]
+    global_ref: bool,
 }
 
 impl Element {
@@ -37,21 +54,26 @@ impl Element {
     }
 
     pub fn is_local(&self, schema: &model::Schema) -> bool {
-        let selfref = schema.get_element_ref(self).expect("element not found");
-
-        for group in schema.types_group().values() {
-            if group.contains_element(&selfref, schema) {
-                return true;
-            }
-        }
-
-        false
+        schema.is_local_element(self)
     }
 
     pub fn min_occurs(&self) -> usize {
         self.duplicity.min_occurs()
     }
 
+    /// whether the element is typed by an abstract block, so only elements of its
+    /// substitution group can appear in documents
+    pub fn is_abstract(&self, schema: &model::Schema) -> bool {
+        self.typing()
+            .grouptype(schema)
+            .is_some_and(model::Group::is_abstract)
+    }
+
+    /// whether other elements can be used in place of this one
+    pub fn is_substitution_head(&self, schema: &model::Schema) -> bool {
+        !schema.get_substitutes(self.name()).is_empty()
+    }
+
     pub fn max_occurs(&self) -> Option<usize> {
         self.duplicity.max_occurs()
     }
@@ -7,6 +7,7 @@ use derive_getters::Getters;
 use enum_variant_macros::FromVariants;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
+use std::ops::Deref;
 
 /// group of elements in some order
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Builder, Getters)]
@@ -21,6 +22,10 @@ pub struct Group {
     #[builder(setter(into))]
     ty: GroupType,
 
+    /// how often the group repeats as a whole, like a choice that can be made again
+    #[builder(default)]
+    duplicity: model::Duplicity,
+
     /// whether the group allows mixed content (plain text nodes in between elements)
     #[builder(default)]
     mixed: bool,
@@ -37,6 +42,14 @@ pub struct Group {
     /// probably also needs control flow objects like groups themselves
     #[builder(default)]
     items: Vec<GroupItem>,
+
+    /// comments inside the block that do not document any of its items
+    #[builder(default)]
+    comments: Vec<model::Comment>,
+
+    /// rules on the attributes and children of the elements with this group as their content
+    #[builder(default)]
+    assertions: Vec<model::Assertion>,
 }
 
 /// group of elements in some order
@@ -79,14 +92,109 @@ impl Group {
         self.abstract_type
     }
 
+    pub fn min_occurs(&self) -> usize {
+        self.duplicity.min_occurs()
+    }
+
+    pub fn max_occurs(&self) -> Option<usize> {
+        self.duplicity.max_occurs()
+    }
+
     pub fn extends(&self) -> bool {
         self.base_type.is_some()
     }
 
+    /// whether elements of this group cannot have any content, neither text nor children.
+    /// Groups that are splatted into it or inherited from count as well
+    pub fn is_empty_content(&self, schema: &model::Schema) -> bool {
+        !self.mixed
+            && self.items.iter().all(|item| match item {
+                GroupItem::Element(_) => false,
+                GroupItem::Group(g) => g.resolve(schema).is_empty_content(schema),
+            })
+            && self
+                .base_type
+                .as_ref()
+                .is_none_or(|base| base.resolve(schema).is_empty_content(schema))
+    }
+
     pub fn contains_element(&self, element: &Ref<model::Element>, schema: &model::Schema) -> bool {
         self.items.iter().any(|item| match item {
             GroupItem::Element(e) => e == element,
             GroupItem::Group(g) => g.resolve(schema).contains_element(element, schema),
         })
     }
+
+    /// assertions of the group and of the groups splatted into it
+    pub fn nested_assertions<'a>(&'a self, schema: &'a model::Schema) -> Vec<&'a model::Assertion> {
+        let mut assertions = self.assertions.iter().collect::<Vec<_>>();
+
+        for item in &self.items {
+            if let GroupItem::Group(g) = item {
+                assertions.extend(g.resolve(schema).nested_assertions(schema));
+            }
+        }
+
+        assertions
+    }
+
+    /// comments of the group and of the groups splatted into it
+    pub fn nested_comments<'a>(&'a self, schema: &'a model::Schema) -> Vec<&'a model::Comment> {
+        let mut comments = self.comments.iter().collect::<Vec<_>>();
+
+        for item in &self.items {
+            if let GroupItem::Group(g) = item {
+                comments.extend(g.resolve(schema).nested_comments(schema));
+            }
+        }
+
+        comments
+    }
+
+    /// all assertions that apply to the group, including those inherited from the base type
+    pub fn merged_assertions<'a>(&'a self, schema: &'a model::Schema) -> Vec<&'a model::Assertion> {
+        let mut assertions = self.nested_assertions(schema);
+
+        if let Some(base) = &self.base_type {
+            assertions.extend(base.resolve(schema).merged_assertions(schema));
+        }
+
+        assertions
+    }
+
+    /// definition of the attribute with this name, from the base type too
+    pub fn find_attribute<'a>(
+        &self,
+        name: &str,
+        schema: &'a model::Schema,
+    ) -> Option<&'a model::Attribute> {
+        self.attributes
+            .deref()
+            .get(name)
+            .map(|attr| attr.resolve(schema))
+            .or_else(|| {
+                self.base_type
+                    .as_ref()
+                    .and_then(|base| base.resolve(schema).find_attribute(name, schema))
+            })
+    }
+
+    /// definition of the child element with this name, from nested groups or the base type too
+    pub fn find_element<'a>(
+        &self,
+        name: &str,
+        schema: &'a model::Schema,
+    ) -> Option<&'a model::Element> {
+        self.items
+            .iter()
+            .find_map(|item| match item {
+                GroupItem::Element(e) => Some(e.resolve(schema)).filter(|el| el.name() == name),
+                GroupItem::Group(g) => g.resolve(schema).find_element(name, schema),
+            })
+            .or_else(|| {
+                self.base_type
+                    .as_ref()
+                    .and_then(|base| base.resolve(schema).find_element(name, schema))
+            })
+    }
 }
@@ -0,0 +1,106 @@
+use crate::model::{SchemaObjId, TypeHash};
+use std::collections::{BTreeSet, HashMap};
+
+/// lookup tables of a Schema that are kept up to date while registering, so lookups
+/// do not have to scan the whole schema
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub(crate) struct SchemaIndex {
+    /// all ID's that refer to a type definition hash. The lowest one is the canonical ID
+    ids_by_hash: HashMap<TypeHash, BTreeSet<SchemaObjId>>,
+
+    /// ID's of the types with a certain name or alias
+    ids_by_name: HashMap<String, BTreeSet<SchemaObjId>>,
+
+    /// hashes of the element definitions with a certain name, in order of registration
+    elements_by_name: HashMap<String, Vec<TypeHash>>,
+
+    /// hashes of the groups that directly contain an element, by element hash
+    element_parents: HashMap<TypeHash, BTreeSet<TypeHash>>,
+
+    /// ID's of the elements that are typed by a type ID
+    typed_elements: HashMap<SchemaObjId, BTreeSet<SchemaObjId>>,
+
+    /// hashes of the elements that directly substitute a head element, by head name
+    substitutes: HashMap<String, Vec<TypeHash>>,
+}
+
+impl SchemaIndex {
+    pub(crate) fn add_id(&mut self, hash: TypeHash, id: SchemaObjId) {
+        self.ids_by_hash.entry(hash).or_default().insert(id);
+    }
+
+    pub(crate) fn add_name(&mut self, name: String, id: SchemaObjId) {
+        self.ids_by_name.entry(name).or_default().insert(id);
+    }
+
+    pub(crate) fn add_element(&mut self, name: &str, hash: TypeHash) {
+        let hashes = self.elements_by_name.entry(name.to_string()).or_default();
+
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    }
+
+    pub(crate) fn add_element_parent(&mut self, element: TypeHash, group: TypeHash) {
+        self.element_parents
+            .entry(element)
+            .or_default()
+            .insert(group);
+    }
+
+    pub(crate) fn add_typed_element(&mut self, typing: SchemaObjId, element: SchemaObjId) {
+        self.typed_elements
+            .entry(typing)
+            .or_default()
+            .insert(element);
+    }
+
+    pub(crate) fn add_substitute(&mut self, head: &str, member: TypeHash) {
+        let hashes = self.substitutes.entry(head.to_string()).or_default();
+
+        if !hashes.contains(&member) {
+            hashes.push(member);
+        }
+    }
+
+    pub(crate) fn id_for_hash(&self, hash: &TypeHash) -> Option<&SchemaObjId> {
+        self.ids_by_hash.get(hash).and_then(|ids| ids.first())
+    }
+
+    pub(crate) fn ids_for_hash(&self, hash: &TypeHash) -> impl Iterator<Item = &SchemaObjId> {
+        self.ids_by_hash.get(hash).into_iter().flatten()
+    }
+
+    pub(crate) fn id_for_name(&self, name: &str) -> Option<&SchemaObjId> {
+        self.ids_by_name.get(name).and_then(|ids| ids.first())
+    }
+
+    pub(crate) fn elements_by_name(&self, name: &str) -> &[TypeHash] {
+        self.elements_by_name
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn element_parents(&self, element: &TypeHash) -> impl Iterator<Item = &TypeHash> {
+        self.element_parents.get(element).into_iter().flatten()
+    }
+
+    pub(crate) fn is_local_element(&self, element: &TypeHash) -> bool {
+        self.element_parents.contains_key(element)
+    }
+
+    pub(crate) fn typed_elements(
+        &self,
+        typing: &SchemaObjId,
+    ) -> impl Iterator<Item = &SchemaObjId> {
+        self.typed_elements.get(typing).into_iter().flatten()
+    }
+
+    pub(crate) fn substitutes(&self, head: &str) -> &[TypeHash] {
+        self.substitutes
+            .get(head)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
@@ -1,10 +1,14 @@
+mod assertion;
 mod attr;
 mod comment;
+mod constraint;
 mod duplicity;
 mod element;
 mod group;
+mod index;
 mod prelude;
 mod primitive;
+mod qname;
 mod refs;
 pub mod restriction;
 mod schema;
@@ -13,6 +17,6 @@ mod r#type;
 mod typehash;
 
 pub use {
-    attr::*, comment::*, element::*, group::*, primitive::*, r#type::*, schema::*, simpletype::*,
-    typehash::*,
+    assertion::*, attr::*, comment::*, constraint::*, duplicity::*, element::*, group::*,
+    primitive::*, qname::*, r#type::*, schema::*, simpletype::*, typehash::*,
 };
@@ -65,6 +65,22 @@ impl PrimitiveType {
         // })
         Ok(Self::from_str(ast.value.as_str())?)
     }
+
+    /// whether the values are numbers, which are compared by value instead of as text
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::Int
+                | Self::Float
+                | Self::Double
+                | Self::Short
+                | Self::Decimal
+                | Self::IntNeg
+                | Self::IntNonNeg
+                | Self::IntPos
+                | Self::UnsignedLong
+        )
+    }
 }
 
 impl From<&ast::Primitive> for PrimitiveType {
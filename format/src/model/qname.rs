@@ -0,0 +1,76 @@
+/// namespace of the `xml:` prefix, which is bound by definition
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+pub const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+pub const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// prefixes that names can use, with their namespace and the location of its XSD.
+/// Documents can bind other prefixes to these namespaces
+pub const KNOWN_NAMESPACES: [(&str, &str, &str); 4] = [
+    ("xml", XML_NAMESPACE, "http://www.w3.org/2001/xml.xsd"),
+    ("xlink", XLINK_NAMESPACE, "http://www.w3.org/1999/xlink.xsd"),
+    ("dc", DC_NAMESPACE, "http://dublincore.org/schemas/xmls/qdc/2008/02/11/dc.xsd"),
+    ("mml", MATHML_NAMESPACE, "http://www.w3.org/Math/XMLSchema/mathml3/mathml3.xsd"),
+];
+
+/// prefix of the target namespace of a schema in XPaths, which do not use the default namespace
+pub const TARGET_PREFIX: &str = "tns";
+
+/// the name of an element in an XPath, with the prefix when its namespace has one.
+/// names that have a prefix already keep it, like 'dc:title'
+pub fn qualify(name: &str, prefix: Option<&str>) -> String {
+    match (prefix, split_qname(name)) {
+        (Some(prefix), (None, local)) => format!("{}:{}", prefix, local),
+        _ => name.to_string(),
+    }
+}
+
+/// 'xlink:href' => (Some('xlink'), 'href')
+pub fn split_qname(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, name),
+    }
+}
+
+/// the name without its prefix
+pub fn local_name(name: &str) -> &str {
+    split_qname(name).1
+}
+
+/// the namespace of a prefixed name, when the prefix is known
+pub fn namespace_uri(name: &str) -> Option<&'static str> {
+    let prefix = split_qname(name).0?;
+
+    KNOWN_NAMESPACES
+        .iter()
+        .find(|(known, ..)| *known == prefix)
+        .map(|(_, uri, _)| *uri)
+}
+
+/// the prefix that the schema uses for the namespace, when it is known
+pub fn known_prefix(uri: &str) -> Option<&'static str> {
+    KNOWN_NAMESPACES
+        .iter()
+        .find(|(_, known, _)| *known == uri)
+        .map(|(prefix, ..)| *prefix)
+}
+
+/// the known prefixes, for error messages
+pub fn known_prefixes() -> String {
+    KNOWN_NAMESPACES.iter().map(|(prefix, ..)| *prefix).collect::<Vec<_>>().join(", ")
+}
+
+/// the convention for element and attribute names is lowercase words joined by dashes,
+/// like 'list-item'
+pub fn follows_naming_convention(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && !name.ends_with('-')
+        && !name.contains("--")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
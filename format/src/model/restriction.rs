@@ -53,3 +53,207 @@ pub struct SimpleTypeRestriction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fraction_digits: Option<usize>,
 }
+
+impl SimpleTypeRestriction {
+    /// the names of the facets that are set
+    pub fn facet_names(&self) -> Vec<&'static str> {
+        [
+            ("length", self.length.is_some()),
+            ("minLength", self.min_length.is_some()),
+            ("maxLength", self.max_length.is_some()),
+            ("pattern", self.pattern.is_some()),
+            ("enumeration", self.enumeration.is_some()),
+            ("whiteSpace", self.white_space.is_some()),
+            ("minInclusive", self.min_inclusive.is_some()),
+            ("maxInclusive", self.max_inclusive.is_some()),
+            ("minExclusive", self.min_exclusive.is_some()),
+            ("maxExclusive", self.max_exclusive.is_some()),
+            ("totalDigits", self.total_digits.is_some()),
+            ("fractionDigits", self.fraction_digits.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(facet, _)| facet)
+        .collect()
+    }
+
+    /// check that the lower bounds are not above the upper bounds, which no value could meet
+    pub fn validate_bounds(&self) -> anyhow::Result<()> {
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length)
+            && min > max
+        {
+            return Err(anyhow::anyhow!(
+                "minLength {} is greater than maxLength {}",
+                min,
+                max
+            ));
+        }
+
+        let number = |value: &Option<String>| value.as_ref().and_then(|v| v.parse::<f64>().ok());
+
+        for (min_facet, min, max_facet, max) in [
+            ("minInclusive", &self.min_inclusive, "maxInclusive", &self.max_inclusive),
+            ("minInclusive", &self.min_inclusive, "maxExclusive", &self.max_exclusive),
+            ("minExclusive", &self.min_exclusive, "maxInclusive", &self.max_inclusive),
+            ("minExclusive", &self.min_exclusive, "maxExclusive", &self.max_exclusive),
+        ] {
+            let (Some(min_value), Some(max_value)) = (number(min), number(max)) else {
+                continue;
+            };
+
+            let inclusive = min_facet == "minInclusive" && max_facet == "maxInclusive";
+            if min_value > max_value || !inclusive && min_value == max_value {
+                return Err(anyhow::anyhow!(
+                    "{} {} is greater than {} {}",
+                    min_facet,
+                    min.as_deref().unwrap_or_default(),
+                    max_facet,
+                    max.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// check that these restrictions only narrow the values allowed by the restrictions of a base
+    /// type. bounds that are not numbers, like dates, are not compared
+    pub fn validate_narrows(&self, base: &SimpleTypeRestriction) -> anyhow::Result<()> {
+        let widens =
+            |facet: &str, value: &dyn ToString, base_facet: &str, base_value: &dyn ToString| {
+                anyhow::anyhow!(
+                    "{} {} is wider than the {} {} of the base type",
+                    facet,
+                    value.to_string(),
+                    base_facet,
+                    base_value.to_string()
+                )
+            };
+
+        // lengths
+        if let (Some(length), Some(base_length)) = (self.length, base.length)
+            && length != base_length
+        {
+            return Err(widens("length", &length, "length", &base_length));
+        }
+        for (facet, value) in [
+            ("length", self.length),
+            ("minLength", self.min_length),
+            ("maxLength", self.max_length),
+        ] {
+            let Some(value) = value else { continue };
+
+            if let Some(base_min) = base.min_length
+                && value < base_min
+            {
+                return Err(widens(facet, &value, "minLength", &base_min));
+            }
+            if let Some(base_max) = base.max_length
+                && value > base_max
+            {
+                return Err(widens(facet, &value, "maxLength", &base_max));
+            }
+        }
+
+        // value bounds, an exclusive bound of the base excludes the bound itself
+        let number = |value: &Option<String>| value.as_ref().and_then(|v| v.parse::<f64>().ok());
+
+        for (facet, value) in [
+            ("minInclusive", &self.min_inclusive),
+            ("minExclusive", &self.min_exclusive),
+        ] {
+            let Some(value) = number(value) else { continue };
+            let inclusive = facet == "minInclusive";
+
+            if let Some(base_min) = number(&base.min_inclusive)
+                && value < base_min
+            {
+                return Err(widens(facet, &value, "minInclusive", &base_min));
+            }
+            if let Some(base_min) = number(&base.min_exclusive)
+                && (value < base_min || inclusive && value == base_min)
+            {
+                return Err(widens(facet, &value, "minExclusive", &base_min));
+            }
+        }
+        for (facet, value) in [
+            ("maxInclusive", &self.max_inclusive),
+            ("maxExclusive", &self.max_exclusive),
+        ] {
+            let Some(value) = number(value) else { continue };
+            let inclusive = facet == "maxInclusive";
+
+            if let Some(base_max) = number(&base.max_inclusive)
+                && value > base_max
+            {
+                return Err(widens(facet, &value, "maxInclusive", &base_max));
+            }
+            if let Some(base_max) = number(&base.max_exclusive)
+                && (value > base_max || inclusive && value == base_max)
+            {
+                return Err(widens(facet, &value, "maxExclusive", &base_max));
+            }
+        }
+
+        // digits
+        if let (Some(digits), Some(base_digits)) = (self.total_digits, base.total_digits)
+            && digits > base_digits
+        {
+            return Err(widens("totalDigits", &digits, "totalDigits", &base_digits));
+        }
+        if let (Some(digits), Some(base_digits)) = (self.fraction_digits, base.fraction_digits)
+            && digits > base_digits
+        {
+            return Err(widens(
+                "fractionDigits",
+                &digits,
+                "fractionDigits",
+                &base_digits,
+            ));
+        }
+
+        // whitespace can only be normalized further
+        if let (Some(white_space), Some(base_white_space)) = (self.white_space, base.white_space)
+            && white_space.strictness() < base_white_space.strictness()
+        {
+            return Err(widens(
+                "whiteSpace",
+                &format!("{:?}", white_space).to_lowercase(),
+                "whiteSpace",
+                &format!("{:?}", base_white_space).to_lowercase(),
+            ));
+        }
+
+        if let (Some(values), Some(base_values)) = (&self.enumeration, &base.enumeration) {
+            validate_enumeration(values, base_values)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// values of a derived enumeration have to be values of the base enumeration
+pub(crate) fn validate_enumeration(
+    values: &[String],
+    base_values: &[String],
+) -> anyhow::Result<()> {
+    match values.iter().find(|value| !base_values.contains(value)) {
+        Some(value) => Err(anyhow::anyhow!(
+            "enumeration value '{}' is not one of the values of the base type: '{}'",
+            value,
+            base_values.join("', '")
+        )),
+        None => Ok(()),
+    }
+}
+
+impl WhiteSpaceHandling {
+    /// how much the whitespace is normalized
+    fn strictness(self) -> u8 {
+        match self {
+            WhiteSpaceHandling::Preserve => 0,
+            WhiteSpaceHandling::Replace => 1,
+            WhiteSpaceHandling::Collapse => 2,
+        }
+    }
+}
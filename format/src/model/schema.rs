@@ -2,22 +2,24 @@ use crate::ast::TypeDef;
 use crate::model::attr::Attribute;
 use crate::model::element::Element;
 use crate::model::group::Group;
+use crate::model::index::SchemaIndex;
 use crate::model::primitive::PrimitiveType;
 use crate::model::r#type::TypeMap;
 use crate::model::simpletype::SimpleType;
 use crate::model::typehash::{GetTypeHash, TypeHash};
 use crate::model::{primitive, simpletype, Comment, TypeBor, TypeRef, TypeVariant};
-use crate::sourced::{SchemaFileManager, SourcedSchemaFile};
-use crate::validation::ValidationError;
+use crate::sourced::{
+    ImportSearch, SchemaFileManager, SourceProvider, SourcedSchemaFile, absolute_schema_path,
+};
+use crate::validation::{ValidationError, Validator};
 use crate::Rule::typedef;
 use crate::{ast, compiler, model, tools::default};
 use anyhow::anyhow;
 use derive_getters::Getters;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 use std::marker::PhantomData;
 use std::ops::Deref;
-use std::path::Path;
-use std::sync::atomic::AtomicU64;
+use std::path::{Path, PathBuf};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
@@ -38,7 +40,7 @@ pub struct Schema {
     /// neccessary internally. The ID is the important part.
     /// In the compiler we should only use these ID's to make sure that every Type cannot be used
     /// as-is but has to go through this Schema to be resolved
-    mapping_type_id_name: IdMap<HashSet<String>>,
+    mapping_type_id_name: IdMap<BTreeSet<String>>,
 
     /// mapping from id to type definition hash. The hash has to be checked in all three
     /// type maps.
@@ -47,9 +49,43 @@ pub struct Schema {
     /// element definitions
     elements: TypeMap<Element>,
 
-    /// buffer that builds comment elements until a new breaking element is registered
-    /// after which the comments are cleared and assignrd to that new element
-    _buffer_comments: Vec<Comment>,
+    /// typing of the top-level elements by name. It is known before the typing is resolved,
+    /// so references to an element can be compiled from within its own block
+    #[getter(skip)]
+    mapping_element_name_typing: BTreeMap<String, TypeRef>,
+
+    /// the types that are being splatted while compiling, with the elements in between.
+    /// A type that ends up splatted into itself would never finish compiling
+    #[getter(skip)]
+    splat_path: Vec<String>,
+
+    /// the schema file and the name of the definition that each type name was compiled from.
+    /// Imported types keep their name, unless they are imported under another name or alias
+    #[getter(skip)]
+    mapping_type_name_source: BTreeMap<String, (PathBuf, String)>,
+
+    /// comments that document named type definitions, by the ID of the definition.
+    /// They are kept by ID so that an alias is documented separately from the type it refers to
+    mapping_type_id_comments: IdMap<Vec<Comment>>,
+
+    /// comments that document the schema itself, like a file header
+    comments: Vec<Comment>,
+
+    /// errors of the items that failed to compile. The compiler continues after them with
+    /// placeholders, so one run reports all of them
+    errors: Vec<String>,
+
+    /// problems that do not stop the schema from compiling, but are likely mistakes
+    warnings: Vec<String>,
+
+    /// the next logical ID to hand out. Every schema counts on its own, so compiling
+    /// the same source twice gives the same ID's
+    #[getter(skip)]
+    next_id: u64,
+
+    /// lookup tables derived from the maps above
+    #[getter(skip)]
+    index: SchemaIndex,
 }
 
 impl Default for Schema {
@@ -61,7 +97,15 @@ impl Default for Schema {
             mapping_type_id_name: Default::default(),
             mapping_type_id_hash: Default::default(),
             elements: Default::default(),
-            _buffer_comments: vec![],
+            mapping_element_name_typing: Default::default(),
+            splat_path: vec![],
+            mapping_type_name_source: Default::default(),
+            mapping_type_id_comments: Default::default(),
+            comments: vec![],
+            errors: vec![],
+            warnings: vec![],
+            next_id: 0,
+            index: Default::default(),
         };
 
         // register simple types
@@ -85,6 +129,27 @@ impl Schema {
         compiler::compile(&SchemaFileManager::from_root_schema(path)?)
     }
 
+    /// compile the entry schema at the path of the sources, like an in-memory map of
+    /// paths to the content of the schemas. The search finds the imports in those sources
+    pub fn from_sources(
+        path: impl AsRef<Path>,
+        sources: impl SourceProvider + 'static,
+        search: ImportSearch,
+    ) -> anyhow::Result<Self> {
+        compiler::compile(&SchemaFileManager::from_sources(path, sources, search)?)
+    }
+
+    /// like `from_file`, searching the include paths for imports
+    pub fn from_file_with_include_paths(
+        path: impl AsRef<Path>,
+        include_paths: &[PathBuf],
+    ) -> anyhow::Result<Self> {
+        compiler::compile(&SchemaFileManager::from_root_schema_with_include_paths(
+            path,
+            include_paths,
+        )?)
+    }
+
     //
     // MAIN REGISTRATION FUNCTIONS
     //
@@ -96,8 +161,9 @@ impl Schema {
         &mut self,
         type_id: &SchemaObjId,
         top_level_de: &ast::TypeDef,
+        source: &SourcedSchemaFile,
     ) -> anyhow::Result<&SchemaObjId> {
-        self.register_type_name(&type_id, top_level_de.ident_nonprim().to_string())
+        self.register_type_name(&type_id, source.type_name(top_level_de))
     }
 
     pub fn register_attribute(
@@ -125,9 +191,24 @@ impl Schema {
 
     pub fn register_group(&mut self, top_level_de: model::Group) -> anyhow::Result<Ref<Group>> {
         let hash = top_level_de.id();
+        let elements = top_level_de
+            .items()
+            .iter()
+            .filter_map(|item| match item {
+                model::GroupItem::Element(el) => self.typehash_for_id(el).copied(),
+                model::GroupItem::Group(_) => None,
+            })
+            .collect::<Vec<_>>();
+
         self.types_group.insert(hash, top_level_de);
         let new_id = self.register_type_mapping(hash)?;
-        Ok(Ref(new_id.clone(), default()))
+        let group_ref = Ref(*new_id, default());
+
+        for element in elements {
+            self.index.add_element_parent(element, hash);
+        }
+
+        Ok(group_ref)
     }
 
     /// register element and generate id for it
@@ -136,9 +217,123 @@ impl Schema {
         top_level_de: model::Element,
     ) -> anyhow::Result<Ref<Element>> {
         let hash = top_level_de.id();
-        self.elements.insert(hash.clone(), top_level_de);
-        let id = self.register_type_mapping(hash.clone())?;
-        Ok(Ref(id.clone(), default()))
+        let typing = *top_level_de.typing().schema_object_id();
+
+        self.index.add_element(top_level_de.name(), hash);
+        if let Some(head) = top_level_de.substitutes() {
+            self.index.add_substitute(head, hash);
+        }
+        self.elements.insert(hash, top_level_de);
+        let id = *self.register_type_mapping(hash)?;
+        self.index.add_typed_element(typing, id);
+
+        Ok(Ref(id, default()))
+    }
+
+    /// register the typing of a top-level element, to be used by references to the element
+    pub fn register_element_typing(&mut self, name: &str, typing: TypeRef) {
+        self.mapping_element_name_typing
+            .insert(name.to_string(), typing);
+    }
+
+    /// allocate an ID for the block of a top-level element before it is resolved.
+    /// Bind the block afterwards with `register_preliminary_id_type`
+    pub fn register_preliminary_element_block(&mut self, name: &str) -> TypeRef {
+        let typing: TypeRef = Ref::<Group>(self.new_id(), default()).into();
+        self.register_element_typing(name, typing.clone());
+        typing
+    }
+
+    /// typing of the top-level element with the name, if it has been registered already
+    pub fn get_element_typing(&self, name: &str) -> Option<&TypeRef> {
+        self.mapping_element_name_typing.get(name)
+    }
+
+    /// remember the definition that the type name is compiled from. Definitions of different
+    /// schemas can only share a name when one of them is imported under another name
+    pub fn register_type_name_source(
+        &mut self,
+        name: &str,
+        source: &SourcedSchemaFile,
+        definition: &TypeDef,
+    ) -> anyhow::Result<()> {
+        let path = absolute_schema_path(source.path()).unwrap_or_else(|_| source.path().clone());
+        let definition = (path, definition.ident_nonprim().to_string());
+
+        match self.mapping_type_name_source.get(name) {
+            Some(known) if *known != definition => Err(anyhow!(
+                "type '{}' is defined in both {} and {}, import one of them under another name, like {{{} as Other{}}}, or with an alias",
+                name,
+                known.0.display(),
+                definition.0.display(),
+                definition.1,
+                definition.1
+            )),
+            Some(_) => Ok(()),
+            None => {
+                self.mapping_type_name_source.insert(name.to_string(), definition);
+                Ok(())
+            }
+        }
+    }
+
+    /// start splatting the type. Fails with the path of the cycle when the type is
+    /// already being splatted
+    pub fn enter_splat(&mut self, type_name: &str) -> anyhow::Result<()> {
+        if let Some(pos) = self.splat_path.iter().position(|step| step == type_name) {
+            return Err(anyhow!(
+                "splatting '{}' creates a cycle: {} > {}",
+                type_name,
+                self.splat_path[pos..].join(" > "),
+                type_name
+            ));
+        }
+
+        self.splat_path.push(type_name.to_string());
+        Ok(())
+    }
+
+    /// an element with an inline block is compiled within the splats, as a step in their path
+    pub fn enter_splat_element(&mut self, name: &str) {
+        self.splat_path.push(format!("#{}", name));
+    }
+
+    pub fn leave_splat(&mut self) {
+        self.splat_path.pop();
+    }
+
+    /// named types are compiled on their own, outside of the splats that refer to them
+    /// through an element. Returns the path to restore afterwards
+    pub fn suspend_splats(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.splat_path)
+    }
+
+    pub fn resume_splats(&mut self, path: Vec<String>) {
+        self.splat_path = path;
+    }
+
+    /// report the error of an item that failed to compile, so the compiler can continue with
+    /// the next item. Errors that are reported again, like those of a type that is splatted
+    /// in several places, are kept once
+    pub fn recover<T>(&mut self, result: anyhow::Result<T>) -> Option<T> {
+        match result {
+            Ok(target) => Some(target),
+            Err(error) => {
+                let message = format!("{:#}", error);
+
+                if !self.errors.contains(&message) {
+                    self.errors.push(message);
+                }
+                None
+            }
+        }
+    }
+
+    /// report a likely mistake that does not stop the schema from compiling, once
+    pub fn warn(&mut self, message: String) {
+        if !self.warnings.contains(&message) {
+            self.warnings.push(message);
+        }
     }
 
     /// register a primitive as a SimpleType. Since primitives have inherent names, we
@@ -176,25 +371,55 @@ impl Schema {
         }
         self.mapping_type_id_hash
             .insert(id.clone(), target_typehash);
+        self.index.add_id(target_typehash, *id);
         Ok(target_ty)
     }
 
-    pub fn push_comment(&mut self, comment: model::Comment) {
-        self._buffer_comments.push(comment);
+    /// allocate an ID for a named group whose definition is not known yet, so it can be referenced
+    /// before it is resolved. Bind the definition afterwards with `register_preliminary_id_type`
+    pub fn register_preliminary_group_name(
+        &mut self,
+        name: impl AsRef<str>,
+    ) -> anyhow::Result<Ref<Group>> {
+        let id = self.new_id();
+        self.register_type_name(&id, name)?;
+        Ok(Ref(id, default()))
+    }
+
+    /// generate a new logical ID for a type, to refer to it before it is resolved
+    pub fn new_id(&mut self) -> SchemaObjId {
+        let id = SchemaObjId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// attach the comments that document a named type definition to its ID
+    pub fn register_type_comments(&mut self, type_id: &SchemaObjId, comments: Vec<Comment>) {
+        if !comments.is_empty() {
+            self.mapping_type_id_comments
+                .insert(type_id.clone(), comments);
+        }
+    }
+
+    pub fn register_schema_comments(&mut self, comments: Vec<Comment>) {
+        self.comments.extend(comments);
     }
 
     //
     // HELPERS
     //
 
-    /// request a priliminary id for a type that is not resolved yet, needed as circuit breaker
+    /// request a priliminary id for a type that is not resolved yet, needed as circuit breaker.
+    /// fails when the type is an alias of a type that does not exist
     pub fn preliminary_ref_for_typename(
         &self,
         typedefinition: &TypeDef,
         source: &SourcedSchemaFile,
-    ) -> Option<PreliminaryId> {
-        let type_id = self.id_for_type_definition(typedefinition)?;
-        PreliminaryId(match typedefinition.type_variant(source).unwrap() {
+    ) -> anyhow::Result<Option<PreliminaryId>> {
+        let Some(type_id) = self.id_for_type_definition(typedefinition, source) else {
+            return Ok(None);
+        };
+        Ok(Some(PreliminaryId(match source.type_variant(typedefinition)? {
             TypeVariant::Simple => {
                 let rf: Ref<SimpleType> = Ref(type_id.clone(), default());
                 rf.into()
@@ -203,8 +428,7 @@ impl Schema {
                 let rf: Ref<Group> = Ref(type_id.clone(), default());
                 rf.into()
             }
-        })
-        .into()
+        })))
     }
 
     pub fn has_type_definition(&self, hash: &TypeHash) -> bool {
@@ -219,12 +443,12 @@ impl Schema {
     }
 
     /// lookup the ID for a named type definition
-    pub fn id_for_type_definition(&self, typedefinition: &ast::TypeDef) -> Option<&SchemaObjId> {
-        let typedef_name = typedefinition.ident_nonprim().to_string();
-        self.mapping_type_id_name
-            .iter()
-            .find(|(_, names)| names.contains(&typedef_name))
-            .map(|(id, _)| id)
+    pub fn id_for_type_definition(
+        &self,
+        typedefinition: &ast::TypeDef,
+        source: &SourcedSchemaFile,
+    ) -> Option<&SchemaObjId> {
+        self.id_for_type_name(&source.type_name(typedefinition))
     }
 
     /// given a certain Type definition, retrieve the ID that is associated with it, if any
@@ -250,21 +474,20 @@ impl Schema {
     pub fn assert_type_definition(&self, hash: &TypeHash) -> anyhow::Result<&Self> {
         self.has_type_definition(hash)
             .then_some(self)
-            .ok_or(anyhow!("no type found with type hash {}", hash))
+            .ok_or_else(|| anyhow!("no type found with type hash {}", hash))
     }
 
     pub fn assert_type_name(&self, name: &str) -> anyhow::Result<&Self> {
         self.id_for_type_name(name)
             .map(|res| self)
-            .ok_or(anyhow!("no type found with name '{}'", name))
+            .ok_or_else(|| anyhow!("no type found with name '{}'", name))
     }
 
     pub fn assert_element_name(&self, name: &str) -> anyhow::Result<&Self> {
-        self.elements
-            .values()
-            .find(|el| el.name() == name)
-            .map(|_| self)
-            .ok_or(anyhow!("no element found with name '{}'", name))
+        match self.index.elements_by_name(name).is_empty() {
+            false => Ok(self),
+            true => Err(anyhow!("no element found with name '{}'", name)),
+        }
     }
 
     //
@@ -279,6 +502,17 @@ impl Schema {
         self.get_attribute(rf).map(|attr| &attr.name)
     }
 
+    /// whether any attribute is in the namespace, by the prefix of its name
+    pub fn uses_namespace(&self, uri: &str) -> bool {
+        self.types_attribute
+            .values()
+            .any(|attr| model::namespace_uri(&attr.name) == Some(uri))
+            || self
+                .elements
+                .values()
+                .any(|el| model::namespace_uri(el.name()) == Some(uri))
+    }
+
     pub fn get_simpletype(&self, rf: &Ref<SimpleType>) -> Option<&model::SimpleType> {
         self.types_simple.get(&*self.typehash_for_id(&rf.0)?)
     }
@@ -289,13 +523,8 @@ impl Schema {
     }
 
     pub fn get_simpletype_by_name(&self, target: impl AsRef<str>) -> Option<&SimpleType> {
-        for (id, names) in &self.mapping_type_id_name {
-            if names.contains(target.as_ref()) {
-                return self.get_simpletype(&Ref(id.clone(), default()));
-            }
-        }
-
-        None
+        self.id_for_type_name(target.as_ref())
+            .and_then(|id| self.get_simpletype(&Ref(*id, default())))
     }
 
     pub fn get_group(&self, rf: &Ref<Group>) -> Option<&Group> {
@@ -303,13 +532,8 @@ impl Schema {
     }
 
     pub fn get_group_by_name(&self, target: impl AsRef<str>) -> Option<&Group> {
-        for (id, names) in &self.mapping_type_id_name {
-            if names.contains(target.as_ref()) {
-                return self.get_group(&Ref(id.clone(), default()));
-            }
-        }
-
-        None
+        self.id_for_type_name(target.as_ref())
+            .and_then(|id| self.get_group(&Ref(*id, default())))
     }
 
     /// Get the type name for a given Group reference (for XSD export)
@@ -328,6 +552,29 @@ impl Schema {
             .and_then(|names| names.iter().next().cloned())
     }
 
+    /// comments that document the named type a Group reference refers to
+    pub fn get_comments_for_group(&self, group_ref: &Ref<Group>) -> &[Comment] {
+        self.get_comments_for_type_id(&group_ref.0)
+    }
+
+    /// comments that document the named type a SimpleType reference refers to
+    pub fn get_comments_for_simpletype(&self, simple_ref: &Ref<SimpleType>) -> &[Comment] {
+        self.get_comments_for_type_id(&simple_ref.0)
+    }
+
+    pub fn get_comments_for_type_name(&self, name: &str) -> &[Comment] {
+        self.id_for_type_name(name)
+            .map(|id| self.get_comments_for_type_id(id))
+            .unwrap_or_default()
+    }
+
+    fn get_comments_for_type_id(&self, id: &SchemaObjId) -> &[Comment] {
+        self.mapping_type_id_comments
+            .get(id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     pub fn get_element(&self, rf: &Ref<Element>) -> Option<&Element> {
         self.elements.get(&*self.typehash_for_id(&rf.0)?)
     }
@@ -338,27 +585,97 @@ impl Schema {
     }
 
     pub fn get_elements_by_name(&self, name: &str) -> Vec<&Element> {
-        self.elements
-            .values()
-            .filter(|el| el.name() == name)
+        self.index
+            .elements_by_name(name)
+            .iter()
+            .filter_map(|hash| self.elements.get(hash))
             .collect()
     }
 
     /// get all localName elements that only exist in Group definitions
-    /// todo: determine this when compiling
     pub fn get_elements_local(&self) -> Vec<&Element> {
         self.elements
-            .values()
-            .filter(|el| el.is_local(self))
+            .iter()
+            .filter(|(hash, _)| self.index.is_local_element(hash))
+            .map(|(_, el)| el)
             .collect()
     }
 
     /// get all elements that are defined in the root of the schema
     pub fn get_elements_root(&self) -> Vec<&Element> {
-        let local = self.get_elements_local();
         self.elements
-            .values()
-            .filter(|el| !local.contains(el))
+            .iter()
+            .filter(|(hash, _)| !self.index.is_local_element(hash))
+            .map(|(_, el)| el)
+            .collect()
+    }
+
+    /// the element that is defined in the root of the schema with the name
+    pub fn get_element_root_by_name(&self, name: &str) -> Option<&Element> {
+        self.index
+            .elements_by_name(name)
+            .iter()
+            .filter(|hash| !self.index.is_local_element(hash))
+            .find_map(|hash| self.elements.get(hash))
+    }
+
+    /// the top-level elements that can be used in place of the head element,
+    /// directly or through the substitution group of another member
+    pub fn get_substitutes(&self, head: &str) -> Vec<&Element> {
+        let mut members: Vec<&Element> = vec![];
+        let mut heads = vec![head.to_string()];
+
+        while let Some(head) = heads.pop() {
+            for member in self
+                .index
+                .substitutes(&head)
+                .iter()
+                .filter_map(|hash| self.elements.get(hash))
+            {
+                if *member.name() != head && !members.contains(&member) {
+                    heads.push(member.name().clone());
+                    members.push(member);
+                }
+            }
+        }
+
+        members
+    }
+
+    /// the top-level head element a local element stands for. Local elements with the name
+    /// and the type of a head element accept the members of its substitution group too
+    pub fn get_substitution_head(&self, element: &Element) -> Option<&Element> {
+        self.get_element_root_by_name(element.name())
+            .filter(|head| head.typing().typehash(self) == element.typing().typehash(self))
+            .filter(|head| head.is_substitution_head(self))
+    }
+
+    /// whether the element is defined in a Group, rather than in the root of the schema
+    pub fn is_local_element(&self, element: &Element) -> bool {
+        self.index.is_local_element(&element.id())
+    }
+
+    /// the groups that directly contain the element
+    pub fn get_element_parents(&self, element: &Element) -> Vec<Ref<Group>> {
+        self.index
+            .element_parents(&element.id())
+            .filter_map(|hash| self.id_for_type_hash(hash))
+            .map(|id| Ref(*id, default()))
+            .collect()
+    }
+
+    /// the elements that are typed by the type a reference refers to, under any of its names
+    pub fn get_elements_typed_by(&self, typing: &TypeRef) -> Vec<&Element> {
+        let Some(hash) = self.typehash_for_id(typing.schema_object_id()) else {
+            return vec![];
+        };
+
+        self.index
+            .ids_for_hash(hash)
+            .flat_map(|id| self.index.typed_elements(id))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|id| self.get_element(&Ref(*id, default())))
             .collect()
     }
 
@@ -383,20 +700,9 @@ impl Schema {
     // VALIDATION
     //
 
+    /// validate an XML instance against the schema, starting at its root element
     pub fn validate(&self, xml: &String) -> Result<(), Vec<ValidationError>> {
-        // let doc = roxmltree::Document::parse(&xml)?;
-        // let root = doc.root_element();
-        // let root_name = root.tag_name().name();
-        // let root_element = self.get_element_by_name(root_name).ok_or(anyhow!(
-        //     "root element '{}' not found in schema",
-        //     root_name
-        // ))?;
-        //
-        // self.validate_element(root_element, root)?;
-
-        // todo
-
-        Ok(())
+        Validator::new(self).validate(xml)
     }
 
     //
@@ -404,17 +710,11 @@ impl Schema {
     //
 
     fn id_for_type_hash(&self, typehash: &TypeHash) -> Option<&SchemaObjId> {
-        self.mapping_type_id_hash
-            .iter()
-            .find(|(_, hash)| hash == &typehash)
-            .map(|(id, _)| id)
+        self.index.id_for_hash(typehash)
     }
 
     fn id_for_type_name(&self, typename: &str) -> Option<&SchemaObjId> {
-        self.mapping_type_id_name
-            .iter()
-            .find(|(_, tnames)| tnames.contains(typename))
-            .map(|(id, _)| id)
+        self.index.id_for_name(typename)
     }
 
     /// associate a type ID with a concrete type definition hash
@@ -422,26 +722,13 @@ impl Schema {
         // assert that a type referenced by this hash already exists
         self.assert_type_definition(&hash)?;
 
-        let mut insert_new = true;
-
-        for (id, typehash) in &self.mapping_type_id_hash {
-            if typehash == &hash {
-                insert_new = false;
-            }
-        }
-
-        if insert_new {
-            self.mapping_type_id_hash
-                .insert(SchemaObjId::new(), hash.clone());
-        }
-
-        for (id, typename) in &self.mapping_type_id_hash {
-            if typename == &hash {
-                return Ok(id);
-            }
+        if self.index.id_for_hash(&hash).is_none() {
+            let id = self.new_id();
+            self.mapping_type_id_hash.insert(id, hash);
+            self.index.add_id(hash, id);
         }
 
-        unreachable!()
+        Ok(self.index.id_for_hash(&hash).unwrap())
     }
 
     /// register a type name (identifier) and generate an ID for it
@@ -452,70 +739,21 @@ impl Schema {
     ) -> anyhow::Result<&SchemaObjId> {
         let top_level_def_name = top_level_def_name.as_ref().to_string();
 
-        let mut insert_new = true;
-
-        for (existing_id, names) in &self.mapping_type_id_name {
-            let name_match = names.contains(&top_level_def_name);
-            let id_match = existing_id == id;
-
-            // identical already exists
-            if name_match && id_match {
-                insert_new = false;
-            }
-            // name already exists but under other ID
-            // else if name_match {
-            //     Err(anyhow!(
-            //         "type name '{}' already exists with different ID",
-            //         top_level_def_name
-            //     ))?;
-            // }
-            // name is new but ID is already used for something else
-            // else if id_match {
-            //     Err(anyhow!(
-            //         "type ID '{}' for name '{}' already exists with different name ({})",
-            //         id.0,
-            //         top_level_def_name,
-            //         name
-            //     ))?;
-            // }
-        }
-
-        //  make sure the set is initialized
-        if !self.mapping_type_id_name.contains_key(id) {
-            self.mapping_type_id_name.insert(id.clone(), HashSet::new());
-        }
-
-        // no match found
-        // create ID=> name mapping
-        if insert_new {
-            self.mapping_type_id_name
-                .get_mut(id)
-                .unwrap()
-                .insert(top_level_def_name);
-        }
-
-        for key in self.mapping_type_id_name.keys() {
-            if key == id {
-                return Ok(key);
-            }
-        }
+        self.index.add_name(top_level_def_name.clone(), *id);
+        self.mapping_type_id_name
+            .entry(*id)
+            .or_default()
+            .insert(top_level_def_name);
 
-        unreachable!()
+        Ok(self.mapping_type_id_name.get_key_value(id).unwrap().0)
     }
 }
 
-/// simple counter for the generation of logical ID's for encountered types
-static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
-
 /// identifier for structures that cant be hashed due to recursion errors
 #[derive(Debug, Hash, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
 pub struct SchemaObjId(u64);
 
 impl SchemaObjId {
-    pub fn new() -> Self {
-        SchemaObjId(ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst))
-    }
-
     pub fn value(&self) -> u64 {
         self.0
     }
@@ -537,7 +775,7 @@ impl PreliminaryId {
 }
 
 /// map ordered by logical ID
-pub type IdMap<T> = HashMap<SchemaObjId, T>;
+pub type IdMap<T> = BTreeMap<SchemaObjId, T>;
 
 /// type reference. this was created to force us to retrieve actual
 /// type definitions from the centralized collection so we wouldnt be creating conflicting
@@ -127,6 +127,27 @@ impl SimpleType {
         matches!(self, Self::Derived { .. })
     }
 
+    /// the primitive that the type restricts, if it is not a union or a list
+    pub fn primitive(&self, schema: &model::Schema) -> Option<PrimitiveType> {
+        match self {
+            SimpleType::Builtin { name } => Some(*name),
+            SimpleType::Derived { base, .. } => base.resolve(schema).primitive(schema),
+            SimpleType::Union { .. } | SimpleType::List { .. } => None,
+        }
+    }
+
+    /// whether the values are lists of items, also when restricted or when builtin like IDRefs
+    pub fn is_list(&self, schema: &model::Schema) -> bool {
+        match self {
+            SimpleType::List { .. } => true,
+            SimpleType::Builtin { name } => {
+                matches!(name, PrimitiveType::IDRefs | PrimitiveType::NameTokens)
+            }
+            SimpleType::Derived { base, .. } => base.resolve(schema).is_list(schema),
+            SimpleType::Union { .. } => false,
+        }
+    }
+
     pub fn restrictions(&self) -> Option<&SimpleTypeRestriction> {
         match self {
             SimpleType::Derived { restrictions, .. } => Some(restrictions),
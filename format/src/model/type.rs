@@ -5,11 +5,12 @@ use crate::model::typehash::TypeHash;
 use crate::model::{GetTypeHash, Ref, SchemaObjId};
 use crate::{default, model};
 use enum_variant_macros::FromVariants;
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use std::hash::{Hash, Hasher};
 
-/// map ordered by type hash
-pub type TypeMap<T> = HashMap<TypeHash, T>;
+/// map keyed by type hash, ordered by the moment a type was first registered.
+/// Compilation registers types in source order, so iterating it is deterministic
+pub type TypeMap<T> = IndexMap<TypeHash, T>;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum TypeVariant {
@@ -100,6 +101,50 @@ impl TypeRef {
             TypeRef::Group(group) => *group.resolve(schema).mixed(),
         }
     }
+
+    /// whether the type is the base type, or inherits from it through `<`
+    pub fn derives_from(&self, base: &TypeRef, schema: &model::Schema) -> bool {
+        let base_hash = base.typehash(schema);
+
+        match self {
+            TypeRef::Simple(simple) => {
+                let mut current = simple.resolve(schema);
+                loop {
+                    if current.id() == base_hash {
+                        return true;
+                    }
+                    match current {
+                        SimpleType::Derived { base, .. } => current = base.resolve(schema),
+                        _ => return false,
+                    }
+                }
+            }
+            TypeRef::Group(group) => {
+                let mut current = group.resolve(schema);
+                loop {
+                    if current.id() == base_hash {
+                        return true;
+                    }
+                    match current.base_type() {
+                        Some(base) => current = base.resolve(schema),
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+
+    /// user-facing name of the type, for error messages
+    pub fn display_name(&self, schema: &model::Schema) -> String {
+        match self {
+            TypeRef::Simple(simple) => schema
+                .get_type_name_for_simpletype(simple)
+                .unwrap_or_else(|| simple.resolve(schema).to_type_name(schema)),
+            TypeRef::Group(group) => schema
+                .get_type_name_for_group(group)
+                .unwrap_or_else(|| "an anonymous block".to_string()),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
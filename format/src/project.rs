@@ -0,0 +1,145 @@
+use crate::export::{Exporter, FontoSchemaExporter, SchematronExporter, XsdExporter};
+use crate::formats::{FontoSchemaCompilerVersion, FontoVersion};
+use crate::model;
+use crate::sourced::{Manifest, ManifestSchema};
+use anyhow::{Context, anyhow};
+use log::warn;
+use std::path::{Path, PathBuf};
+
+/// compile all entry schemas of the manifest to their targets. Returns the written files
+pub fn build(manifest: &Manifest, include_paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    if manifest.schemas.is_empty() {
+        return Err(anyhow!(
+            "{} has no schemas to build, declare them in [[schema]] tables",
+            manifest.dir.join(Manifest::FILE_NAME).display()
+        ));
+    }
+
+    let mut written: Vec<PathBuf> = vec![];
+
+    for entry in &manifest.schemas {
+        let input = manifest.dir.join(&entry.input);
+        let schema = model::Schema::from_file_with_include_paths(&input, include_paths)
+            .context(format!("building {}", input.display()))?;
+
+        for warning in schema.warnings() {
+            eprintln!("warning: {}: {}", input.display(), warning);
+        }
+
+        for (path, output) in build_schema(manifest, entry, &schema)? {
+            // schemas that share an output dir need different file names
+            if written.contains(&path) {
+                return Err(anyhow!(
+                    "{} is written twice, give the targets of the schemas another output-dir or file",
+                    path.display()
+                ));
+            }
+
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, output)?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+/// the outputs of the targets of the entry schema, by the path to write them to
+fn build_schema(
+    manifest: &Manifest,
+    entry: &ManifestSchema,
+    schema: &model::Schema,
+) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let stem = entry
+        .input
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let output_dir = manifest.dir.join(&entry.output_dir);
+    let output_path = |file: &Option<PathBuf>, default: String| {
+        output_dir.join(file.clone().unwrap_or(PathBuf::from(default)))
+    };
+
+    for root in &entry.roots {
+        if schema.get_element_root_by_name(root).is_none() {
+            return Err(anyhow!(
+                "root element '#{}' is not a top-level element of {}",
+                root,
+                entry.input.display()
+            ));
+        }
+    }
+
+    let mut outputs = vec![];
+
+    if let Some(xsd) = &entry.xsd {
+        let exporter = match &entry.namespace {
+            Some(namespace) => XsdExporter::with_namespace(namespace),
+            None => XsdExporter::default(),
+        };
+        let exporter = match xsd.embed_schematron {
+            true => exporter.with_embedded_schematron(),
+            false => exporter,
+        };
+
+        outputs.push((
+            output_path(&xsd.file, format!("{}.xsd", stem)),
+            exporter
+                .with_roots(entry.roots.clone())
+                .export_schema(schema)?,
+        ));
+    }
+
+    if let Some(fonto) = &entry.fonto {
+        let version = fonto_schema_version(fonto.version.as_deref(), &entry.input)?;
+        let exporter = FontoSchemaExporter::with_version(version).with_roots(entry.roots.clone());
+        let exporter = match &entry.namespace {
+            Some(namespace) => exporter.with_namespace(namespace),
+            None => exporter,
+        };
+        let exported = exporter.export_schema(schema)?;
+
+        outputs.push((
+            output_path(&fonto.file, "fonto.schema.json".to_string()),
+            serde_json::to_string(&exported)?,
+        ));
+    }
+
+    if let Some(schematron) = &entry.schematron {
+        outputs.push((
+            output_path(&schematron.file, format!("{}.sch", stem)),
+            match &entry.namespace {
+                Some(namespace) => SchematronExporter::with_namespace(namespace),
+                None => SchematronExporter::default(),
+            }
+            .export_schema(schema)?,
+        ));
+    }
+
+    if outputs.is_empty() {
+        warn!(
+            "{} has no targets, add [schema.xsd], [schema.fonto] or [schema.schematron]",
+            entry.input.display()
+        );
+    }
+
+    Ok(outputs)
+}
+
+fn fonto_schema_version(
+    version: Option<&str>,
+    input: &Path,
+) -> anyhow::Result<FontoSchemaCompilerVersion> {
+    Ok(match version {
+        Some(version) => FontoVersion::try_from_str(version)?.min_schema_compiler_version(),
+        None => {
+            warn!(
+                "assuming default Fonto schema version for {}",
+                input.display()
+            );
+            FontoSchemaCompilerVersion::default()
+        }
+    })
+}
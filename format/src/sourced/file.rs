@@ -1,10 +1,10 @@
-use crate::ast;
-use crate::ast::{SchemaFile, TypeDef};
-use crate::sourced::SchemaFileManager;
+use crate::ast::{Import, SchemaFile, TypeDef, TypeDefInlineTyping};
+use crate::sourced::{SchemaFileManager, absolute_schema_path};
+use crate::{ast, model};
+use anyhow::anyhow;
 use derive_getters::Getters;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Deref;
-use std::path;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -20,6 +20,13 @@ pub struct SourcedSchemaFile {
 
     /// the manager that loaded the schema
     pub manager: Arc<SchemaFileManager>,
+
+    /// the alias that qualifies the names of the types of this schema, like 'dc' when it
+    /// was imported using `import "./dc.whas" as dc`
+    pub qualifier: Option<String>,
+
+    /// names that the importing schema gave to types of this schema, like `{Title as DcTitle}`
+    pub renames: BTreeMap<String, String>,
 }
 
 impl SourcedSchemaFile {
@@ -28,18 +35,235 @@ impl SourcedSchemaFile {
             schema: Arc::new(schema),
             path: Default::default(),
             manager: Arc::new(SchemaFileManager::new()),
+            qualifier: None,
+            renames: BTreeMap::new(),
         }
     }
 
-    // resolve across imports
+    /// the types defined by this schema. Imported types are compiled once they are referred to
     pub fn types(&self) -> Vec<&TypeDef> {
-        let own_types = self.schema.types_own();
+        self.schema.types_own()
+    }
+
+    /// name of the type definition of this schema as the compiled schema knows it, which is
+    /// the name under which it was imported
+    pub fn type_name(&self, typedef: &TypeDef) -> String {
+        self.qualified_name(typedef.ident_nonprim().as_ref())
+    }
+
+    /// the name of a type of this schema, as the compiled schema knows it
+    pub fn qualified_name(&self, name: &str) -> String {
+        let local = self.renames.get(name).map_or(name, String::as_str);
+
+        match &self.qualifier {
+            Some(qualifier) => format!("{}.{}", qualifier, local),
+            None => local.to_string(),
+        }
+    }
+
+    /// the type definition that the name refers to, along with the schema that defines it.
+    /// The name can be an imported type, under another name or qualified like dc.Title
+    pub fn resolve_type(&self, name: impl AsRef<str>) -> Option<(SourcedSchemaFile, &TypeDef)> {
+        self.resolve_type_in(&self.schema, &self.path, self.scope(false), name.as_ref(), &mut vec![])
+            .map(|(source, typedef, _)| (source, typedef))
+    }
+
+    /// like resolve_type, failing with the error of not_found when there is no such type.
+    /// A name that refers to a private type of an imported schema fails because it is private
+    pub fn require_type(
+        &self,
+        name: impl AsRef<str>,
+        not_found: impl FnOnce() -> anyhow::Error,
+    ) -> anyhow::Result<(SourcedSchemaFile, &TypeDef)> {
+        if let Some(found) = self.resolve_type(name.as_ref()) {
+            return Ok(found);
+        }
+
+        // look again with the private types, to tell why the name is not there
+        let hidden = self.resolve_type_in(
+            &self.schema,
+            &self.path,
+            self.scope(true),
+            name.as_ref(),
+            &mut vec![],
+        );
+
+        match hidden {
+            Some((source, typedef, Some(importer))) if typedef.is_private() => Err(
+                private_import_error(typedef.ident_nonprim().as_ref(), &source.path, &importer),
+            ),
+            _ => Err(not_found()),
+        }
+    }
+
+    fn scope(&self, private: bool) -> Scope {
+        Scope {
+            qualifier: self.qualifier.clone(),
+            renames: self.renames.clone(),
+            imported: false,
+            importer: None,
+            private,
+        }
+    }
+
+    /// whether the type definition is a simple type or a group, following aliases across imports
+    pub fn type_variant(&self, typedef: &TypeDef) -> anyhow::Result<model::TypeVariant> {
+        let alias = match typedef {
+            TypeDef::Inline(inline) => match &inline.typing {
+                TypeDefInlineTyping::Typename(ty) => ty.ident_regular(),
+                _ => None,
+            },
+            TypeDef::Block(_) => None,
+        };
+
+        match alias {
+            Some(alias) => {
+                let (source, aliased) = self
+                    .require_type(alias, || anyhow!("could not find Type declaration for '{}'", alias))?;
+                source.type_variant(aliased)
+            }
+            None => typedef.type_variant(&self.schema),
+        }
+    }
 
-        if self.schema.has_imports() {
-            todo!()
+    /// look the name up in the schema at the path, along with the schema that imported the
+    /// one that defines it. The visited names prevent endless lookups in cyclic imports
+    fn resolve_type_in<'a>(
+        &'a self,
+        schema: &'a Arc<SchemaFile>,
+        path: &Path,
+        scope: Scope,
+        name: &str,
+        visited: &mut Vec<(PathBuf, String)>,
+    ) -> Option<Resolved<'a>> {
+        let step = (
+            absolute_schema_path(path).unwrap_or_else(|_| path.to_path_buf()),
+            name.to_string(),
+        );
+        if visited.contains(&step) {
+            return None;
         }
+        visited.push(step);
 
-        own_types
+        // only the imports with that alias provide qualified names
+        let (alias, name) = match name.split_once('.') {
+            Some((alias, name)) => (Some(alias), name),
+            None => (None, name),
+        };
+
+        // importing schemas cannot see the private types
+        let own = match alias {
+            None => schema
+                .find_type_by_name(name)
+                .filter(|typedef| !scope.imported || scope.private || !typedef.is_private()),
+            Some(_) => None,
+        };
+
+        if let Some(typedef) = own {
+            let source = SourcedSchemaFile {
+                schema: schema.clone(),
+                path: path.to_path_buf(),
+                manager: self.manager.clone(),
+                qualifier: scope.qualifier,
+                renames: scope.renames,
+            };
+            return Some((source, typedef, scope.importer));
+        }
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        // and only the imports that are exported again
+        schema
+            .imports
+            .iter()
+            .filter(|import| import.alias() == alias)
+            .filter(|import| !scope.imported || import.is_reexport())
+            .find_map(|import| {
+                let scope = match alias {
+                    Some(alias) => Scope {
+                        qualifier: Some(
+                            scope
+                                .qualifier
+                                .as_ref()
+                                .map_or(alias.to_string(), |q| format!("{}.{}", q, alias)),
+                        ),
+                        renames: BTreeMap::new(),
+                        imported: true,
+                        importer: Some(path.to_path_buf()),
+                        private: scope.private,
+                    },
+                    None => Scope {
+                        imported: true,
+                        importer: Some(path.to_path_buf()),
+                        ..scope.clone()
+                    },
+                };
+
+                self.resolve_import(dir, import, scope, name, visited)
+            })
+    }
+
+    /// look the name up in the selection of the import
+    fn resolve_import<'a>(
+        &'a self,
+        dir: &Path,
+        import: &Import,
+        mut scope: Scope,
+        name: &str,
+        visited: &mut Vec<(PathBuf, String)>,
+    ) -> Option<Resolved<'a>> {
+        let selected = import.selected_items();
+        let target_name = match selected.iter().find(|item| item.local_name() == name) {
+            Some(item) => {
+                // the type keeps the name that the importing schema knows it by
+                if item.name() != name {
+                    let local = scope
+                        .renames
+                        .remove(name)
+                        .unwrap_or_else(|| name.to_string());
+                    scope.renames = BTreeMap::from([(item.name().to_string(), local)]);
+                }
+                item.name()
+            }
+            None if import.is_wildcard() => name,
+            None => return None,
+        };
+
+        self.manager
+            .import_paths(import, dir)
+            .ok()?
+            .into_iter()
+            .find_map(|path| {
+                let schema = self.manager.get(&path)?;
+                self.resolve_type_in(schema, &path, scope.clone(), target_name, visited)
+            })
+    }
+
+    /// the types that the imports select have to be public in the imported schemas,
+    /// for all of the loaded schemas
+    pub fn validate_import_selections(&self) -> anyhow::Result<()> {
+        for (path, schema) in self.manager.files() {
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+            for import in &schema.imports {
+                for target in self.manager.import_paths(import, dir)? {
+                    let Some(imported) = self.manager.get(&target) else {
+                        continue;
+                    };
+
+                    for item in import.selected_items() {
+                        if imported
+                            .find_type_by_name(item.name())
+                            .is_some_and(TypeDef::is_private)
+                        {
+                            return Err(private_import_error(item.name(), &target, path));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Count types across all schemas loaded by the manager
@@ -48,6 +272,36 @@ impl SourcedSchemaFile {
     }
 }
 
+fn private_import_error(name: &str, target: &Path, importer: &Path) -> anyhow::Error {
+    let absolute = |path: &Path| absolute_schema_path(path).unwrap_or_else(|_| path.to_path_buf());
+
+    anyhow!(
+        "cannot import '{}' from {} into {}, because it is private",
+        name,
+        absolute(target).display(),
+        absolute(importer).display()
+    )
+}
+
+/// a type found by a lookup, with the schema that imported the schema defining it
+type Resolved<'a> = (SourcedSchemaFile, &'a TypeDef, Option<PathBuf>);
+
+/// how the types of a schema are named and which of them are visible, during a lookup
+#[derive(Clone)]
+struct Scope {
+    qualifier: Option<String>,
+    renames: BTreeMap<String, String>,
+
+    /// whether the schema is looked into by a schema that imports it
+    imported: bool,
+
+    /// the schema that imports the schema, if any
+    importer: Option<PathBuf>,
+
+    /// whether private types of imported schemas are found too
+    private: bool,
+}
+
 impl From<ast::SchemaFile> for SourcedSchemaFile {
     fn from(schema: ast::SchemaFile) -> Self {
         Self::from_ast_schema(schema)
@@ -1,5 +1,8 @@
 use crate::ast::SchemaFile;
-use crate::sourced::SourcedSchemaFile;
+use crate::ast::Import;
+use crate::sourced::{
+    FileSystemSources, ImportSearch, SourceProvider, SourcedSchemaFile, is_std_path, std_module,
+};
 use anyhow::Context;
 use derive_getters::Getters;
 use std::collections::HashMap;
@@ -15,6 +18,12 @@ pub struct SchemaFileManager {
 
     /// collection of all schema files that have been loaded
     map: HashMap<PathBuf, Arc<SchemaFile>>,
+
+    /// where the imports find their schemas
+    search: ImportSearch,
+
+    /// where the schema files are read from
+    sources: Arc<dyn SourceProvider>,
 }
 
 impl SchemaFileManager {
@@ -23,29 +32,68 @@ impl SchemaFileManager {
         Self {
             root: PathBuf::new(),
             map: HashMap::new(),
+            search: ImportSearch::default(),
+            sources: Arc::new(FileSystemSources),
         }
     }
 
     pub fn from_root_schema(path: impl AsRef<Path>) -> anyhow::Result<SourcedSchemaFile> {
-        let root = path
-            .as_ref()
+        Self::from_root_schema_with_include_paths(path, &[])
+    }
+
+    /// like `from_root_schema`, searching the include paths for imports that are not found
+    /// next to the importing schema
+    pub fn from_root_schema_with_include_paths(
+        path: impl AsRef<Path>,
+        include_paths: &[PathBuf],
+    ) -> anyhow::Result<SourcedSchemaFile> {
+        let root = path::absolute(path.as_ref())?
+            .parent()
+            .ok_or(anyhow::anyhow!("parent dir of entry schema not found"))?
+            .to_path_buf();
+
+        let man = Self {
+            search: ImportSearch::new(&root, include_paths)?,
+            root,
+            map: HashMap::new(),
+            sources: Arc::new(FileSystemSources),
+        };
+
+        man.load_root_schema(path)
+    }
+
+    /// read the entry schema and its imports from the sources instead of the file system.
+    /// The search finds the imports in the include paths and packages of the sources
+    pub fn from_sources(
+        path: impl AsRef<Path>,
+        sources: impl SourceProvider + 'static,
+        search: ImportSearch,
+    ) -> anyhow::Result<SourcedSchemaFile> {
+        let root = sources
+            .absolute(path.as_ref())?
             .parent()
             .ok_or(anyhow::anyhow!("parent dir of entry schema not found"))?
             .to_path_buf();
 
-        let mut man = Self {
+        let man = Self {
             root,
             map: HashMap::new(),
+            search,
+            sources: Arc::new(sources),
         };
 
-        let schema = man.add_schema_file_path(&path)?;
+        man.load_root_schema(path)
+    }
 
-        let singled_manager = Arc::new(man);
+    fn load_root_schema(mut self, path: impl AsRef<Path>) -> anyhow::Result<SourcedSchemaFile> {
+        let schema = self.add_schema_file_path(&path)?;
 
         Ok(SourcedSchemaFile {
             schema,
-            path: path.as_ref().to_path_buf(),
-            manager: singled_manager,
+            path: self.absolute(path.as_ref())?,
+            manager: Arc::new(self),
+            qualifier: None,
+            renames: Default::default(),
         })
     }
 
@@ -53,7 +101,7 @@ impl SchemaFileManager {
         &mut self,
         path: impl AsRef<Path>,
     ) -> anyhow::Result<Arc<SchemaFile>> {
-        let path = path::absolute(path.as_ref())?;
+        let path = self.absolute(path.as_ref())?;
 
         // parent dir of the schema file
         let schema_dir = path
@@ -68,9 +116,15 @@ impl SchemaFileManager {
 
         // Parse the file WITHOUT validating imports (to avoid recursion issues)
         // We resolve the file path first (handles .whas extension)
-        let resolved_path = SchemaFile::resolve_file_path(&path)?;
-        let content = std::fs::read_to_string(&resolved_path)
-            .context(format!("reading schema from {}", resolved_path.display()))?;
+        // the modules of the standard library are bundled with the compiler
+        let (resolved_path, content) = match is_std_path(&path) {
+            true => (path.clone(), std_module(&path)?.to_string()),
+            false => {
+                let resolved_path = self.sources.resolve_file_path(&path)?;
+                let content = self.sources.read(&resolved_path)?;
+                (resolved_path, content)
+            }
+        };
         let schema = SchemaFile::parse(&content)
             .context(format!("parsing schema from {}", resolved_path.display()))?;
 
@@ -83,16 +137,41 @@ impl SchemaFileManager {
         // NOW recursively process imports (cycle detection works!)
         let schema_ref = self.map.get(&path).unwrap().clone();
         for import in &schema_ref.imports {
-            // absolute path of the target schema that we want to import
-            let import_abspath = import.absolute_path(&schema_dir);
-
-            // add it to the manager (will use cache if already loaded)
-            self.add_schema_file_path(import_abspath)?;
+            // absolute paths of the target schemas that we want to import
+            for import_abspath in self.search.resolve(import, &schema_dir, &*self.sources)? {
+                // add it to the manager (will use cache if already loaded)
+                self.add_schema_file_path(import_abspath)?;
+            }
         }
 
         Ok(schema_arc)
     }
 
+    /// the files that the import in a schema in the dir refers to
+    pub fn import_paths(&self, import: &Import, dir: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
+        self.search.resolve(import, dir, &*self.sources)
+    }
+
+    /// the loaded schema file at the path, as it was passed to `add_schema_file_path`
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&Arc<SchemaFile>> {
+        self.map.get(&self.absolute(path.as_ref()).ok()?)
+    }
+
+    /// the path that identifies the schema file. The paths of bundled modules are kept as they are
+    fn absolute(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        match is_std_path(path) {
+            true => Ok(path.to_path_buf()),
+            false => self.sources.absolute(path),
+        }
+    }
+
+    /// all loaded schema files by their path, in the order of their paths
+    pub fn files(&self) -> Vec<(&PathBuf, &Arc<SchemaFile>)> {
+        let mut files = self.map.iter().collect::<Vec<_>>();
+        files.sort_by_key(|(path, _)| *path);
+        files
+    }
+
     pub fn types_count(&self) -> usize {
         self.map.values().map(|schema| schema.types_count()).sum()
     }
@@ -0,0 +1,136 @@
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path;
+use std::path::{Path, PathBuf};
+
+/// the optional whas.toml of a project, which is found in the dir of the entry schema
+/// or in one of the dirs above it
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Manifest {
+    /// the dir of the manifest, which its paths are relative to
+    #[serde(skip)]
+    pub dir: PathBuf,
+
+    /// package names with the dirs of their schemas, so that `shared = "../vocab"`
+    /// lets schemas import "@shared/xlink" from "../vocab/xlink.whas"
+    #[serde(default)]
+    pub packages: BTreeMap<String, PathBuf>,
+
+    /// dirs to search for imports, after those given to the compiler
+    #[serde(default)]
+    pub include_paths: Vec<PathBuf>,
+
+    /// the entry schemas that `whas build` compiles, from the [[schema]] tables
+    #[serde(default, rename = "schema")]
+    pub schemas: Vec<ManifestSchema>,
+}
+
+/// an entry schema of the project, with the targets to compile it to
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ManifestSchema {
+    /// path of the entry schema
+    pub input: PathBuf,
+
+    /// dir to write the outputs to, which is the dir of the manifest by default
+    #[serde(default)]
+    pub output_dir: PathBuf,
+
+    /// the target namespace of the XSD
+    pub namespace: Option<String>,
+
+    /// the top-level elements that documents can start with, all of them when empty
+    #[serde(default)]
+    pub roots: Vec<String>,
+
+    pub xsd: Option<XsdTarget>,
+    pub fonto: Option<FontoTarget>,
+    pub schematron: Option<SchematronTarget>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct XsdTarget {
+    /// file name in the output dir, '<input stem>.xsd' by default
+    pub file: Option<PathBuf>,
+
+    /// embed the Schematron rules of elements in the xs:appinfo
+    #[serde(default)]
+    pub embed_schematron: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FontoTarget {
+    /// file name in the output dir, 'fonto.schema.json' by default
+    pub file: Option<PathBuf>,
+
+    /// version of the Fonto instance, like "8.8"
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SchematronTarget {
+    /// file name in the output dir, '<input stem>.sch' by default
+    pub file: Option<PathBuf>,
+}
+
+impl Manifest {
+    pub const FILE_NAME: &'static str = "whas.toml";
+
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path::absolute(path.as_ref())?;
+        let content = std::fs::read_to_string(&path)
+            .context(format!("reading manifest from {}", path.display()))?;
+
+        let mut manifest: Self = toml::from_str(&content)
+            .context(format!("parsing manifest from {}", path.display()))?;
+        manifest.dir = path
+            .parent()
+            .ok_or(anyhow!("dir of manifest not found"))?
+            .to_path_buf();
+
+        Ok(manifest)
+    }
+
+    /// the closest manifest in the dir or in one of its parents
+    pub fn find(dir: impl AsRef<Path>) -> anyhow::Result<Option<Self>> {
+        let dir = path::absolute(dir.as_ref())?;
+
+        dir.ancestors()
+            .map(|dir| dir.join(Self::FILE_NAME))
+            .find(|path| path.is_file())
+            .map(Self::from_file)
+            .transpose()
+    }
+
+    /// the manifest at the path, or the closest one to the current dir when there is none
+    pub fn from_file_or_find(path: Option<impl AsRef<Path>>) -> anyhow::Result<Self> {
+        match path {
+            Some(path) => Self::from_file(path),
+            None => Self::find(".")?.ok_or(anyhow!(
+                "no {} found in the current dir or in one of the dirs above it",
+                Self::FILE_NAME
+            )),
+        }
+    }
+
+    /// the include paths, relative to the current dir instead of the manifest
+    pub fn include_dirs(&self) -> Vec<PathBuf> {
+        self.include_paths
+            .iter()
+            .map(|dir| self.dir.join(dir))
+            .collect()
+    }
+
+    /// the dir of the package, like for 'shared' in "@shared/xlink"
+    pub fn package_dir(&self, name: &str) -> Option<PathBuf> {
+        self.packages
+            .iter()
+            .find(|(package, _)| package.strip_prefix('@').unwrap_or(package) == name)
+            .map(|(_, dir)| self.dir.join(dir))
+    }
+}
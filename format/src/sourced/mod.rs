@@ -1,4 +1,8 @@
 mod file;
 mod manager;
+mod manifest;
+mod provider;
+mod search;
+mod stdlib;
 
-pub use {file::*, manager::*};
+pub use {file::*, manager::*, manifest::*, provider::*, search::*, stdlib::*};
@@ -0,0 +1,143 @@
+use crate::ast::files_at;
+use anyhow::{Context, anyhow};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path;
+use std::path::{Component, Path, PathBuf};
+use wax::{Glob, Pattern};
+
+/// where the compiler reads the schema files from, like the file system, or the unsaved
+/// buffers of an editor
+pub trait SourceProvider: Debug + Send + Sync {
+    /// the content of the schema file at the absolute path
+    fn read(&self, path: &Path) -> anyhow::Result<String>;
+
+    /// whether there is a schema file at the absolute path
+    fn exists(&self, path: &Path) -> bool;
+
+    /// the files at the path in the dir, which are all matching files for glob patterns
+    fn files_at(&self, dir: &Path, path: &Path) -> anyhow::Result<Vec<PathBuf>>;
+
+    /// the absolute path, which identifies the schema file
+    fn absolute(&self, path: &Path) -> anyhow::Result<PathBuf>;
+
+    /// the path of the schema file, which may leave out the .whas extension
+    fn resolve_file_path(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        let with_ext = path.with_extension("whas");
+
+        if self.exists(path) {
+            Ok(path.to_path_buf())
+        } else if self.exists(&with_ext) {
+            Ok(with_ext)
+        } else {
+            Err(anyhow!(
+                "file not found at paths: {}, {}",
+                path.display(),
+                with_ext.display()
+            ))
+        }
+    }
+}
+
+/// reads the schema files from disk
+#[derive(Debug, Default)]
+pub struct FileSystemSources;
+
+impl SourceProvider for FileSystemSources {
+    fn read(&self, path: &Path) -> anyhow::Result<String> {
+        std::fs::read_to_string(path).context(format!("reading schema from {}", path.display()))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn files_at(&self, dir: &Path, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        files_at(dir, path)
+    }
+
+    fn absolute(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        Ok(path::absolute(path)?)
+    }
+}
+
+/// schema files by their path, without any files on disk. Relative paths start at the root,
+/// so "main.whas" and "/main.whas" are the same file
+#[derive(Debug, Default, Clone)]
+pub struct MemorySources {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemorySources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_file(mut self, path: impl AsRef<Path>, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    /// add the file, or replace its content when it exists
+    pub fn insert(&mut self, path: impl AsRef<Path>, content: impl Into<String>) {
+        self.files.insert(normalize(path.as_ref()), content.into());
+    }
+}
+
+impl SourceProvider for MemorySources {
+    fn read(&self, path: &Path) -> anyhow::Result<String> {
+        self.files
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| anyhow!("no schema at {}", path.display()))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    fn files_at(&self, dir: &Path, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let path_str = path.to_str().unwrap_or("");
+
+        if !path_str.contains('*') {
+            return Ok(vec![normalize(&dir.join(path))]);
+        }
+
+        let normalized_pattern = path_str.strip_prefix("./").unwrap_or(path_str);
+        let glob = Glob::new(normalized_pattern)
+            .context(format!("invalid glob pattern: {}", normalized_pattern))?;
+        let dir = normalize(dir);
+
+        // the map is ordered by path, like the files of a directory walk
+        Ok(self
+            .files
+            .keys()
+            .filter(|file| {
+                file.strip_prefix(&dir)
+                    .is_ok_and(|relative| glob.is_match(relative))
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn absolute(&self, path: &Path) -> anyhow::Result<PathBuf> {
+        Ok(normalize(path))
+    }
+}
+
+/// the path from the root, without the "." and ".." steps
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::from("/");
+
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => normalized.push(name),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => {}
+        }
+    }
+
+    normalized
+}
@@ -0,0 +1,103 @@
+use crate::ast::Import;
+use crate::sourced::{Manifest, SourceProvider, is_std_path};
+use anyhow::anyhow;
+use std::path;
+use std::path::{Path, PathBuf};
+
+/// environment variable with include paths, separated like the PATH
+pub const WHAS_PATH: &str = "WHAS_PATH";
+
+/// where imports find the schemas they refer to
+#[derive(Debug, Default)]
+pub struct ImportSearch {
+    /// dirs to search for imports like "xlink.whas", after the dir of the importing schema
+    include_paths: Vec<PathBuf>,
+
+    /// the manifest with the packages for imports like "@shared/xlink"
+    manifest: Manifest,
+}
+
+impl ImportSearch {
+    /// the given include paths are searched before those of the whas.toml in the dir of the
+    /// entry schema or above it, and then those of WHAS_PATH. The packages come from that
+    /// whas.toml too
+    pub fn new(root: impl AsRef<Path>, include_paths: &[PathBuf]) -> anyhow::Result<Self> {
+        let from_env = std::env::var_os(WHAS_PATH)
+            .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let manifest = Manifest::find(root)?.unwrap_or_default();
+
+        let include_paths = include_paths
+            .iter()
+            .chain(&manifest.include_dirs())
+            .chain(&from_env)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(path::absolute)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            include_paths,
+            manifest,
+        })
+    }
+
+    /// the include paths and then those of the manifest, with the packages of the manifest.
+    /// Nothing is looked up on disk or in WHAS_PATH, so the paths can be those of any sources
+    pub fn from_manifest(manifest: Manifest, include_paths: &[PathBuf]) -> Self {
+        Self {
+            include_paths: include_paths
+                .iter()
+                .cloned()
+                .chain(manifest.include_dirs())
+                .collect(),
+            manifest,
+        }
+    }
+
+    /// the files that the import in a schema in the dir refers to.
+    /// "./" and "../" paths are relative to that dir, "@package/" paths to the package dir,
+    /// and other relative paths are searched in that dir and then in the include paths.
+    /// "std:" paths refer to the bundled modules
+    pub fn resolve(
+        &self,
+        import: &Import,
+        dir: impl AsRef<Path>,
+        sources: &dyn SourceProvider,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        if is_std_path(import.path()) {
+            return Ok(vec![import.path().to_path_buf()]);
+        }
+
+        if let Some((package, path)) = import.package() {
+            let package_dir = self.manifest.package_dir(package).ok_or(anyhow!(
+                "unknown package '@{}' in import \"{}\", declare it in the [packages] of a {}",
+                package,
+                import.path().display(),
+                Manifest::FILE_NAME
+            ))?;
+            return sources.files_at(&package_dir, path);
+        }
+
+        if import.is_absolute() || import.is_explicitly_relative() {
+            return sources.files_at(dir.as_ref(), import.path());
+        }
+
+        for search_dir in
+            std::iter::once(dir.as_ref()).chain(self.include_paths.iter().map(PathBuf::as_path))
+        {
+            let files = sources.files_at(search_dir, import.path())?;
+
+            if !files.is_empty()
+                && files
+                    .iter()
+                    .all(|file| sources.resolve_file_path(file).is_ok())
+            {
+                return Ok(files);
+            }
+        }
+
+        // not found anywhere, reading it from the dir of the schema reports the missing file
+        sources.files_at(dir.as_ref(), import.path())
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::anyhow;
+use std::path;
+use std::path::{Path, PathBuf};
+
+/// prefix of the import paths of the modules that are bundled with the compiler
+pub const STD_PREFIX: &str = "std:";
+
+/// the bundled modules by name, so that `import {XLink} from "std:xlink"` needs no files
+const MODULES: [(&str, &str); 5] = [
+    ("dc", include_str!("../../std/dc.whas")),
+    ("iso", include_str!("../../std/iso.whas")),
+    ("mathml", include_str!("../../std/mathml.whas")),
+    ("xlink", include_str!("../../std/xlink.whas")),
+    ("xml", include_str!("../../std/xml.whas")),
+];
+
+/// whether the path refers to a bundled module, like "std:xlink"
+pub fn is_std_path(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| path.starts_with(STD_PREFIX))
+}
+
+/// the source of the bundled module at the path
+pub fn std_module(path: &Path) -> anyhow::Result<&'static str> {
+    let name = path
+        .to_str()
+        .and_then(|path| path.strip_prefix(STD_PREFIX))
+        .unwrap_or_default();
+
+    MODULES
+        .iter()
+        .find(|(module, _)| *module == name)
+        .map(|(_, source)| *source)
+        .ok_or_else(|| {
+            anyhow!(
+                "unknown module '{}', the standard library has {}",
+                path.display(),
+                MODULES
+                    .iter()
+                    .map(|(module, _)| format!("{}{}", STD_PREFIX, module))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// the absolute path of a schema file. The paths of bundled modules are kept as they are
+pub fn absolute_schema_path(path: &Path) -> std::io::Result<PathBuf> {
+    match is_std_path(path) {
+        true => Ok(path.to_path_buf()),
+        false => path::absolute(path),
+    }
+}
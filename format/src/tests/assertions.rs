@@ -0,0 +1,214 @@
+use crate::export::{Exporter, SchematronExporter, WhasExporter};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/assertions.whas")
+}
+
+fn assertions(schema: &model::Schema, type_name: &str) -> Vec<String> {
+    schema
+        .get_group_by_name(type_name)
+        .unwrap_or_else(|| panic!("type {} not found", type_name))
+        .merged_assertions(schema)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn errors(schema: &model::Schema, xml: &str) -> Vec<String> {
+    match schema.validate(&xml.to_string()) {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_compile_assertions() -> Result<()> {
+    let schema = compile()?;
+
+    assert_eq!(
+        assertions(&schema, "Figure"),
+        vec![
+            "assert if @type = 'figure' then #caption",
+            "assert count(#img) <= 3 or @type = 'table'",
+        ]
+    );
+
+    // assertions of splatted blocks and of the base type apply too
+    assert_eq!(
+        assertions(&schema, "Part"),
+        vec![
+            "assert not (#note and @status = 'draft')",
+            "assert @end >= @start",
+        ]
+    );
+    Ok(())
+}
+
+/// the comments right above an assertion explain it
+#[test]
+fn test_assertion_comments() -> Result<()> {
+    let schema = compile()?;
+    let figure = schema.get_group_by_name("Figure").unwrap();
+
+    assert_eq!(
+        figure.assertions()[0].message(),
+        "figures of type 'figure' need a caption"
+    );
+    assert_eq!(
+        figure.assertions()[1].message(),
+        "count(#img) <= 3 or @type = 'table'"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_assertion_xpath() {
+    let ast = ast::SchemaFile::parse(
+        r#"
+@a?
+@b?
+Test {
+    #c*: String
+    assert if @a = "it's" then (#c or @b != 'x') and not count(#c) >= 2
+}
+"#,
+    )
+    .unwrap();
+
+    let schema = compiler::compile(&ast.into()).unwrap();
+    let test = schema.get_group_by_name("Test").unwrap();
+
+    assert_eq!(
+        test.assertions()[0].xpath_in(None),
+        r#"if (@a = "it's") then (c or @b != 'x') and not(count(c) >= 2) else true()"#
+    );
+}
+
+#[test]
+fn test_validate_assertions_ok() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<doc>
+    <figure type="figure"><caption>A</caption><img>a.png</img></figure>
+    <figure type="inline"><img>b.png</img></figure>
+    <figure type="table"><img>1</img><img>2</img><img>3</img><img>4</img></figure>
+    <part start="1" end="3" status="final"><title>I</title><note>n</note></part>
+</doc>"#;
+
+    assert_eq!(errors(&schema, xml), Vec::<String>::new());
+    Ok(())
+}
+
+#[test]
+fn test_validate_assertions_violated() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<doc>
+    <figure type="figure"><img>a.png</img></figure>
+    <figure><img>1</img><img>2</img><img>3</img><img>4</img></figure>
+    <part start="10" end="9" status="draft"><title>I</title><note>n</note></part>
+</doc>"#;
+
+    assert_eq!(
+        errors(&schema, xml),
+        vec![
+            "line 2: #figure does not satisfy 'assert if @type = 'figure' then #caption'",
+            "line 3: #figure does not satisfy 'assert count(#img) <= 3 or @type = 'table''",
+            "line 4: #part does not satisfy 'assert not (#note and @status = 'draft')'",
+            "line 4: #part does not satisfy 'assert @end >= @start'",
+        ]
+    );
+    Ok(())
+}
+
+/// numbers are compared by value, so 10 is not before 9
+#[test]
+fn test_validate_assertions_numeric() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<doc><part start="9" end="10"><title>I</title></part></doc>"#;
+
+    assert_eq!(errors(&schema, xml), Vec::<String>::new());
+    Ok(())
+}
+
+/// values of a string type are compared as text, like the exported xs:assert and Schematron
+/// do, so "10" is before "9". Only values of a numeric type are compared as numbers
+#[test]
+fn test_validate_assertions_typed() -> Result<()> {
+    let ast = ast::SchemaFile::parse(
+        "@from: String\n@to: String\nSpan {\n    #min: Int\n    #max: Int\n    assert @to >= @from\n    assert #max >= #min\n}\n#span: Span\n",
+    )?;
+    let schema = compiler::compile(&ast.into())?;
+
+    let xml = r#"<span from="9" to="10"><min>9</min><max>10</max></span>"#;
+
+    assert_eq!(
+        errors(&schema, xml),
+        vec!["line 1: #span does not satisfy 'assert @to >= @from'"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_schematron_export() -> Result<()> {
+    let schema = compile()?;
+    let sch = SchematronExporter::default().export_schema(&schema)?;
+
+    assert!(sch.contains(r#"queryBinding="xslt2""#), "{}", sch);
+    assert!(sch.contains(r#"<sch:rule context="/doc/figure">"#), "{}", sch);
+    assert!(
+        sch.contains(
+            r#"<sch:assert test="if (@type = &apos;figure&apos;) then caption else true()">figures of type 'figure' need a caption</sch:assert>"#
+        ),
+        "{}",
+        sch
+    );
+    assert!(
+        sch.contains(r#"<sch:assert test="@end &gt;= @start">"#),
+        "{}",
+        sch
+    );
+    Ok(())
+}
+
+/// the context is the path of the elements with the type, so that elements with the same name
+/// and another type are not checked. Recursive elements continue at any depth
+#[test]
+fn test_schematron_assertion_contexts() -> Result<()> {
+    let ast = ast::SchemaFile::parse(
+        "Period {\n    #from: Int\n    #to: Int\n    assert #to >= #from\n}\n\n\
+         Section {\n    #title: String\n    #section*: Section\n    assert count(#title) = 1\n}\n\n\
+         #doc {\n    #period: Period\n    #note {\n        #period: String\n    }\n    #section*: Section\n}\n",
+    )?;
+    let schema = compiler::compile(&ast.into())?;
+    let sch = SchematronExporter::default().export_schema(&schema)?;
+
+    let contexts = sch
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("<sch:rule context=\""))
+        .map(|line| line.trim_end_matches("\">"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        contexts,
+        vec!["/doc/period", "/doc/section", "/doc/section//section"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_whas_export_assertions() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    assert!(
+        whas.contains("    assert if @type = 'figure' then #caption\n"),
+        "{}",
+        whas
+    );
+    assert!(whas.contains("    assert @end >= @start\n"), "{}", whas);
+    Ok(())
+}
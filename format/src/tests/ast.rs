@@ -80,9 +80,14 @@ fn test_idents() {
     assert_ast::<IdentLowercase>(Rule::ident_lowercase, "attr-ident");
     assert_ast::<IdentTypeNonPrimitive>(Rule::ident_type_nonprimitive, "StringIntTimeType");
     assert_ast::<IdentTypeNonPrimitive>(Rule::ident_type_nonprimitive, "Type");
+    assert_ast::<IdentTypeNonPrimitive>(Rule::ident_type_nonprimitive, "dc.Title");
     assert_ast::<IdentType>(Rule::ident_type, "Type");
     assert_ast::<IdentAttr>(Rule::ident_attr, "@attr-ident");
     assert_ast::<IdentElement>(Rule::ident_element, "#elem-ident");
+    assert_ast::<IdentElement>(Rule::ident_element, "#dmCode");
+    assert_ast::<IdentElement>(Rule::ident_element, "#_private.v2");
+    assert_ast::<IdentAttr>(Rule::ident_attr, "@xlink:href");
+    assert_ast::<IdentName>(Rule::ident_name, "größe");
     assert_ast::<Ident>(Rule::ident, "#elem-ident");
 }
 
@@ -152,6 +157,12 @@ fn test_imports() {
     assert_ast::<ImportExtended>(Rule::import_extended, "import from './filepath.whas' *");
 
     assert_ast::<Import>(Rule::import, "import {Type} from './filepath.whas'");
+    assert_ast::<Import>(Rule::import, "import {Type as Other} from './filepath.whas'");
+    assert_ast::<Import>(Rule::import, "import './filepath.whas' as lib");
+    assert_ast::<Import>(Rule::import, "import * from './filepath.whas' as lib");
+    assert_ast::<Import>(Rule::import, "export {Type} from './filepath.whas'");
+    assert_ast::<Import>(Rule::import, "export from './filepath.whas' {Type}");
+    assert_ast::<ImportSelector>(Rule::import_selector, "{Definition as Other, Definition2}");
     assert_ast::<Import>(Rule::import, "import from './filepath.whas' {Type, Type2}");
 
     // todo
@@ -196,6 +207,8 @@ fn test_typedef_inline() {
     assert_ast::<TypeDefInline>(Rule::typedef_inline, "Type(arg1): String");
     assert_ast::<TypeDefInline>(Rule::typedef_inline, "Type(arg1, arg2): String");
     assert_ast::<TypeDefInline>(Rule::typedef_inline, "Type(arg): arg");
+    assert_ast::<TypeDefInline>(Rule::typedef_inline, "private Type: String");
+    assert_ast::<TypeDefBlock>(Rule::typedef_block, "@id\nprivate Type { #item: String }");
 }
 
 #[test]
@@ -214,6 +227,19 @@ fn test_block() {
     assert_ast::<Block>(Rule::block, "!{}");
     assert_ast::<Block>(Rule::block, "x!x{}");
 
+    // the block repeats as a whole
+    let repeated = assert_ast::<Block>(Rule::block, "?{ #a: String, #b: String }*");
+    assert!(matches!(repeated.duplicity, Some(ModDuplicity::Any(_))));
+    assert_ast::<Block>(Rule::block, "{ #a: String }[2..3]");
+
+    // a choice splatted below another block is not the duplicity of that block
+    let nested = assert_ast::<Block>(Rule::block, "{ #a { #b: String }\n...?{ #c: String } }");
+    assert_eq!(nested.items.len(), 2);
+    match &nested.items[0] {
+        BlockItem::Element(element) => assert_eq!(element.block().unwrap().duplicity, None),
+        item => panic!("{:?}", item),
+    }
+
     assert_ast::<Block>(
         Rule::block,
         "{\
@@ -228,6 +254,10 @@ fn test_block() {
     }",
     );
 
+    // attributes of the block itself, after the elements or ended by a comma
+    assert_ast::<Block>(Rule::block, "{ #element: String @attr? }");
+    assert_ast::<Block>(Rule::block, "{ @attr: Int, #element: String }");
+
     assert_ast::<Block>(
         Rule::block,
         "{\
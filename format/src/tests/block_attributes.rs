@@ -0,0 +1,162 @@
+use crate::export::{Exporter, WhasExporter};
+use crate::sourced::SourcedSchemaFile;
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/block_attributes.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+/// names of the attributes of the element, including those of its type
+fn attribute_names(schema: &model::Schema, element: &model::Element) -> Vec<String> {
+    element
+        .group_merged_attributes(schema)
+        .get(schema)
+        .iter()
+        .map(|attr| attr.name().clone())
+        .collect()
+}
+
+#[test]
+fn test_compile_block_attributes() -> Result<()> {
+    let schema = compile()?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let group = doc.typing().grouptype(&schema).unwrap();
+
+    assert_eq!(attribute_names(&schema, doc), vec!["status", "version"]);
+
+    let element = |name: &str| group.find_element(name, &schema).unwrap();
+    assert_eq!(
+        attribute_names(&schema, element("figure")),
+        vec!["id", "lang"]
+    );
+    assert_eq!(attribute_names(&schema, element("img")), vec!["alt", "src"]);
+
+    // the comma keeps the attribute above it in the block
+    assert!(attribute_names(&schema, element("title")).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_header_attributes_in_blocks() -> Result<()> {
+    let schema = compile_str("#doc {\n    @lang\n    #title: String\n}")?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let title = doc
+        .typing()
+        .grouptype(&schema)
+        .unwrap()
+        .find_element("title", &schema)
+        .unwrap();
+
+    // attributes right above an element are still declared on that element, which is
+    // easily mistaken for attributes of the block when they open it
+    assert!(attribute_names(&schema, doc).is_empty());
+    assert_eq!(attribute_names(&schema, title), vec!["lang"]);
+    assert_eq!(
+        schema.warnings(),
+        &vec![
+            "attribute @lang at the start of the block is declared on #title, the element right below. End it with a comma to declare it on the block"
+                .to_string()
+        ]
+    );
+
+    let schema = compile_str("#doc {\n    @lang\n    @id\n    #title: String\n}")?;
+    assert_eq!(
+        schema.warnings(),
+        &vec![
+            "attributes @lang, @id at the start of the block are declared on #title, the element right below. End them with a comma to declare them on the block"
+                .to_string()
+        ]
+    );
+    Ok(())
+}
+
+/// an empty line does not separate attributes from the element below them, only a comma does
+#[test]
+fn test_separated_attributes_in_blocks() -> Result<()> {
+    let schema = compile_str(
+        "#doc {\n    @lang\n\n    #title: String\n    @class,\n    #p: String\n}",
+    )?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let group = doc.typing().grouptype(&schema).unwrap();
+
+    assert_eq!(attribute_names(&schema, doc), vec!["class"]);
+    assert_eq!(
+        attribute_names(&schema, group.find_element("title", &schema).unwrap()),
+        vec!["lang"]
+    );
+    assert!(attribute_names(&schema, group.find_element("p", &schema).unwrap()).is_empty());
+    assert_eq!(schema.warnings().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_attributes() {
+    let error = |whas: &str| compile_str(whas).unwrap_err().to_string();
+
+    assert!(
+        error("@id\n#doc {\n    #title: String\n    @id?\n}")
+            .contains("@id is declared more than once on #doc")
+    );
+    assert!(
+        error("@id\nFigure { @id: ID }\n#figure: Figure")
+            .contains("@id is declared more than once on type 'Figure'")
+    );
+    assert!(error("@id\n@id\n#doc: String").contains("@id is declared more than once on #doc"));
+}
+
+/// the attributes of the element override those of its type
+#[test]
+fn test_compile_element_attributes() -> Result<()> {
+    let ast = ast::SchemaFile::parse(
+        "Figure {\n    #caption: String\n    @id: ID\n}\n@id?\n#figure: Figure\n#img { @src }",
+    )?;
+    let source: SourcedSchemaFile = ast.into();
+    let mut schema = model::Schema::default();
+
+    let attrs = |name: &str, schema: &mut model::Schema| -> Result<Vec<(String, bool)>> {
+        let element = source
+            .elements_top_level()
+            .into_iter()
+            .find(|el| el.name() == name)
+            .unwrap();
+        let attrs = compiler::compile_element_attributes(&source, element, schema)?;
+
+        Ok(attrs
+            .target
+            .get(schema)
+            .iter()
+            .map(|attr| (attr.name().clone(), *attr.required()))
+            .collect())
+    };
+
+    assert_eq!(
+        attrs("figure", &mut schema)?,
+        vec![("id".to_string(), false)]
+    );
+    assert_eq!(attrs("img", &mut schema)?, vec![("src".to_string(), true)]);
+    Ok(())
+}
+
+#[test]
+fn test_block_attributes_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+    assert!(
+        whas.contains("    #img* {\n        @alt?\n        @src\n    }\n"),
+        "{}",
+        whas
+    );
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    let doc = reparsed.get_element_root_by_name("doc").unwrap();
+    assert_eq!(attribute_names(&reparsed, doc), vec!["status", "version"]);
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use crate::export::{Exporter, RustExporter, TypescriptExporter};
+use crate::model;
+use anyhow::Result;
+
+fn generate_rust() -> Result<String> {
+    let schema = model::Schema::from_file("src/tests/schemas/codegen/library.whas")?;
+    RustExporter::default().export_schema(&schema)
+}
+
+fn generate_typescript() -> Result<String> {
+    let schema = model::Schema::from_file("src/tests/schemas/codegen/library.whas")?;
+    TypescriptExporter::default().export_schema(&schema)
+}
+
+/// groups become structs with attribute and element fields
+#[test]
+fn test_rust_structs() -> Result<()> {
+    let code = generate_rust()?;
+
+    assert!(code.contains("pub struct Chapter {"));
+    assert!(code.contains("    #[serde(rename = \"@id\")]\n    pub id: String,"));
+    assert!(code.contains("    pub status: Option<PublishStatus>,"));
+    assert!(code.contains("    pub title: String,"));
+    assert!(code.contains("    pub author: Option<Username>,"));
+    assert!(code.contains("    pub chapter: Vec<Chapter>,"));
+
+    // anonymous element types are named after the element
+    assert!(code.contains("pub struct Book {"));
+    assert!(code.contains("pub const ROOT_ELEMENTS: &[&str] = &[\"book\", \"paragraph\"];"));
+
+    Ok(())
+}
+
+/// types that contain themselves without a Vec in between are boxed
+#[test]
+fn test_rust_recursion() -> Result<()> {
+    let code = generate_rust()?;
+
+    assert!(code.contains("    pub note: Option<Box<Note>>,"));
+    assert!(code.contains("    pub chapter: Option<Box<Chapter>>,"));
+
+    Ok(())
+}
+
+/// choices and mixed content become enums
+#[test]
+fn test_rust_choices() -> Result<()> {
+    let code = generate_rust()?;
+
+    assert!(code.contains("pub enum ChapterChoice {"));
+    assert!(code.contains("    #[serde(rename = \"para\")]\n    Para(String),"));
+    assert!(code.contains("    pub content: Vec<ChapterChoice>,"));
+
+    assert!(code.contains("pub enum ParagraphContent {"));
+    assert!(code.contains("    #[serde(rename = \"$text\")]\n    Text(String),"));
+
+    Ok(())
+}
+
+/// simple types become enums or validating newtypes
+#[test]
+fn test_rust_simple_types() -> Result<()> {
+    let code = generate_rust()?;
+
+    assert!(code.contains("pub enum PublishStatus {"));
+    assert!(code.contains("    #[serde(rename = \"review\")]\n    Review,"));
+
+    assert!(code.contains("pub struct Username(String);"));
+    assert!(code.contains("if !(value.chars().count() >= 3) {"));
+    assert!(code.contains("regex::Regex::new(r\"^(?:[a-z_]+)$\")"));
+
+    assert!(code.contains("pub struct Age(i64);"));
+    assert!(code.contains("if !(value <= 150) {"));
+
+    // unions are parsed from text by trying every member
+    assert!(code.contains("pub enum Port {\n    Int(i64),\n    Auto(PortAuto),\n}"));
+
+    Ok(())
+}
+
+/// the generated code is the expected code, which is compiled with the tests below
+#[test]
+fn test_rust_matches_expected() -> Result<()> {
+    let code = generate_rust()?;
+    let expected = std::fs::read_to_string("src/tests/schemas/codegen/expected/library.rs")?;
+
+    assert_eq!(code, expected);
+    Ok(())
+}
+
+/// the expected code of the library schema, to check that generated code compiles
+#[allow(dead_code, clippy::all)]
+mod generated {
+    include!("schemas/codegen/expected/library.rs");
+}
+
+/// the generated types validate their text
+#[test]
+fn test_rust_generated_code() {
+    use generated::*;
+
+    assert!("ab".parse::<Username>().is_err());
+    assert!("Ab_c".parse::<Username>().is_err());
+    assert_eq!("ab_c".parse::<Username>().unwrap().value(), "ab_c");
+    assert!("151".parse::<Age>().is_err());
+    assert!("5.5".parse::<Rating>().is_err());
+
+    assert_eq!("80".parse::<Port>(), Ok(Port::Int(80)));
+    assert_eq!("auto".parse::<Port>(), Ok(Port::Auto(PortAuto::Auto)));
+    assert!("manual".parse::<Port>().is_err());
+    assert_eq!("x".parse::<Label>(), Ok(Label::String("x".to_string())));
+    assert_eq!(Port::Int(80).to_string(), "80");
+}
+
+/// simple types become aliases of string literal unions
+#[test]
+fn test_typescript_simple_types() -> Result<()> {
+    let code = generate_typescript()?;
+
+    assert!(code.contains("export type PublishStatus = \"draft\" | \"review\" | \"published\";"));
+    assert!(code.contains("export type Port = `${number}` | \"auto\";"));
+    assert!(code.contains(
+        "/** minLength: 3, maxLength: 20, pattern: /[a-z_]+/ */\nexport type Username = string;"
+    ));
+
+    Ok(())
+}
+
+/// groups become interfaces with attribute and element properties
+#[test]
+fn test_typescript_interfaces() -> Result<()> {
+    let code = generate_typescript()?;
+
+    assert!(code.contains("export interface Chapter {"));
+    assert!(code.contains("    \"@id\": string;\n    \"@status\"?: PublishStatus;"));
+    assert!(code.contains("    \"author\"?: Username;"));
+    assert!(code.contains("    \"chapter\"?: Chapter[];"));
+
+    // anonymous element types are named after the element
+    assert!(
+        code.contains(
+            "export interface Book {\n    \"port\": Port;\n    \"chapter\": Chapter[];\n}"
+        )
+    );
+    assert!(code.contains("    \"#text\"?: string[];"));
+
+    Ok(())
+}
+
+/// element names and their attributes are available as lookup types
+#[test]
+fn test_typescript_element_maps() -> Result<()> {
+    let code = generate_typescript()?;
+
+    assert!(code.contains("    \"paragraph\": Paragraph;"));
+    assert!(code.contains(
+        "    \"chapter\": {\n        \"id\": string;\n        \"status\"?: PublishStatus;\n    };"
+    ));
+    assert!(code.contains("export type ElementName = keyof Elements;"));
+    assert!(code.contains("export type RootElementName = \"book\" | \"paragraph\";"));
+
+    Ok(())
+}
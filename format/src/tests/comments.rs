@@ -0,0 +1,125 @@
+use crate::export::{Exporter, XsdExporter};
+use crate::model;
+use anyhow::Result;
+use itertools::Itertools;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/comments.whas")
+}
+
+fn contents(comments: &[model::Comment]) -> Vec<String> {
+    comments.iter().map(model::Comment::content).collect()
+}
+
+fn element<'s>(schema: &'s model::Schema, name: &str) -> &'s model::Element {
+    schema
+        .elements()
+        .values()
+        .find(|el| el.name() == name)
+        .unwrap_or_else(|| panic!("element {} not found", name))
+}
+
+/// comments followed by an empty line at the top level document the schema
+#[test]
+fn test_schema_comments() -> Result<()> {
+    let schema = compile()?;
+    assert_eq!(
+        contents(schema.comments()),
+        vec!["the schema of a tiny library"]
+    );
+    Ok(())
+}
+
+/// leading and trailing comments of type definitions, and those around their attributes
+#[test]
+fn test_type_comments() -> Result<()> {
+    let schema = compile()?;
+
+    assert_eq!(
+        contents(schema.get_comments_for_type_name("PersonId")),
+        vec!["id of a person"]
+    );
+    assert_eq!(
+        contents(schema.get_comments_for_type_name("Status")),
+        vec!["the state of a publication", "no other states"]
+    );
+    assert_eq!(
+        contents(schema.get_comments_for_type_name("Author")),
+        vec![
+            "somebody who writes",
+            "comment between the attributes and the type"
+        ]
+    );
+
+    let attributes = schema
+        .get_group_by_name("Author")
+        .unwrap()
+        .attributes()
+        .get(&schema)
+        .into_iter()
+        .map(|attr| (attr.name.clone(), contents(&attr.comments)))
+        .sorted()
+        .collect_vec();
+
+    assert_eq!(
+        attributes,
+        vec![
+            (
+                "id".to_string(),
+                vec!["documents the id attribute".to_string()]
+            ),
+            (
+                "name".to_string(),
+                vec!["documents the attribute".to_string()]
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+/// comments of the items in a block, and the detached ones that document the block itself
+#[test]
+fn test_block_comments() -> Result<()> {
+    let schema = compile()?;
+
+    let group = schema.get_group_by_name("Author").unwrap();
+    assert_eq!(contents(group.comments()), vec!["about the block itself"]);
+
+    assert_eq!(
+        contents(element(&schema, "display").comments()),
+        vec!["the full name, for display"]
+    );
+    assert_eq!(
+        contents(element(&schema, "initials").comments()),
+        vec!["like \"J.R.R.\""]
+    );
+    assert!(element(&schema, "mail").comments().is_empty());
+
+    let book = element(&schema, "book");
+    assert_eq!(contents(book.comments()), vec!["a book"]);
+
+    let lang = book.attributes().get(&schema);
+    assert_eq!(contents(&lang[0].comments), vec!["language of the book"]);
+
+    Ok(())
+}
+
+/// comments are exported as xs:annotation as the first child of what they document
+#[test]
+fn test_xsd_annotations() -> Result<()> {
+    let schema = compile()?;
+    let xsd = XsdExporter::default().export_schema(&schema)?;
+    let xsd = xsd.lines().map(str::trim).collect::<String>();
+
+    for expected in [
+        r#"<xs:simpleType name="PersonId"><xs:annotation><xs:documentation>id of a person</xs:documentation></xs:annotation>"#,
+        r#"<xs:annotation><xs:documentation>the state of a publication</xs:documentation><xs:documentation>no other states</xs:documentation></xs:annotation>"#,
+        r#"<xs:sequence><xs:annotation><xs:documentation>about the block itself</xs:documentation></xs:annotation>"#,
+        r#"<xs:annotation><xs:documentation>language of the book</xs:documentation></xs:annotation></xs:attribute>"#,
+    ] {
+        assert!(xsd.contains(expected), "missing {} in {}", expected, xsd);
+    }
+
+    Ok(())
+}
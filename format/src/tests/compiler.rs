@@ -127,12 +127,17 @@ fn test_block_attributes() {
 
 #[test]
 fn test_schema_object_id_gen() {
-    let first = SchemaObjId::new().value();
-    let second = SchemaObjId::new().value();
-    let third = SchemaObjId::new().value();
+    let mut sch = model::Schema::default();
+    let first = sch.new_id().value();
+    let second = sch.new_id().value();
+    let third = sch.new_id().value();
 
     assert!(second > first);
     assert!(third > second);
+
+    // every schema counts on its own
+    let mut other = model::Schema::default();
+    assert_eq!(other.new_id().value(), first);
 }
 
 #[test]
@@ -548,3 +553,50 @@ fn compile_test_schema_determinism_5x() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// elements know which groups contain them and which elements are typed by a type
+#[test]
+fn test_element_relations() -> anyhow::Result<()> {
+    let sch = model::Schema::from_file("src/tests/schemas/codegen/library.whas")?;
+
+    let title = sch.get_elements_by_name("title");
+    assert_eq!(title.len(), 1);
+    assert!(title[0].is_local(&sch));
+
+    let parents = sch.get_element_parents(title[0]);
+    assert_eq!(parents.len(), 1);
+    assert_eq!(
+        sch.get_type_name_for_group(&parents[0]).as_deref(),
+        Some("Chapter")
+    );
+
+    let book = sch.get_elements_by_name("book");
+    assert!(!book[0].is_local(&sch));
+    assert!(sch.get_element_parents(book[0]).is_empty());
+
+    // #chapter is declared with different duplicities, but always with the Chapter type
+    let chapter_type = book[0]
+        .typing()
+        .grouptype(&sch)
+        .unwrap()
+        .items()
+        .iter()
+        .find_map(|item| match item {
+            GroupItem::Element(el) if el.resolve(&sch).name() == "chapter" => {
+                Some(el.resolve(&sch).typing().clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    let typed = sch
+        .get_elements_typed_by(&chapter_type)
+        .into_iter()
+        .map(|el| el.name().as_str())
+        .collect_vec();
+
+    assert_eq!(typed.len(), sch.get_elements_by_name("chapter").len());
+    assert!(typed.iter().all(|name| *name == "chapter"));
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::export::{Exporter, WhasExporter, XsdExporter};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/constraints.whas")
+}
+
+fn errors(schema: &model::Schema, xml: &str) -> Vec<String> {
+    match schema.validate(&xml.to_string()) {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_compile_constraints() -> Result<()> {
+    let schema = compile()?;
+    let book = schema
+        .elements()
+        .values()
+        .find(|el| el.name() == "book")
+        .unwrap();
+
+    let constraints = book
+        .constraints()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        constraints,
+        vec![
+            "key chapter-id: #chapter/@id",
+            "unique #chapter/#title",
+            "keyref #xref/@target: chapter-id",
+        ]
+    );
+    Ok(())
+}
+
+/// key references have to refer to a key on the same element
+#[test]
+fn test_compile_unknown_key() {
+    let ast = ast::SchemaFile::parse(
+        r#"
+@target
+Xref {}
+
+#book {
+    #xref*: Xref
+}
+    keyref #xref/@target: chapter-id
+"#,
+    )
+    .unwrap();
+
+    let err = compiler::compile(&ast.into()).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("refers to key 'chapter-id'"),
+        "{:#}",
+        err
+    );
+}
+
+#[test]
+fn test_validate_constraints_ok() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<book>
+    <chapter id="intro"><title>Intro</title><para>see <xref target="end"/></para></chapter>
+    <chapter id="end"><title>End</title><para>back to <xref target="intro"/></para></chapter>
+</book>"#;
+
+    assert_eq!(errors(&schema, xml), Vec::<String>::new());
+    Ok(())
+}
+
+#[test]
+fn test_validate_constraints_violated() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<book>
+    <chapter id="intro"><title>Intro</title></chapter>
+    <chapter id="intro"><title>Intro</title></chapter>
+    <chapter id="end"><title>End</title><para><xref target="nowhere"/></para></chapter>
+</book>"#;
+
+    assert_eq!(
+        errors(&schema, xml),
+        vec![
+            "line 3: duplicate value 'intro' for 'key chapter-id: #chapter/@id' within #book",
+            "line 3: duplicate value 'Intro' for 'unique #chapter/#title' within #book",
+            "line 4: 'nowhere' of #xref/@target does not refer to any 'chapter-id' within #book",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_validate_missing_key() -> Result<()> {
+    let schema = compile()?;
+
+    let xml = r#"<book>
+    <chapter><title>Intro</title></chapter>
+</book>"#;
+
+    assert_eq!(
+        errors(&schema, xml),
+        vec!["line 2: missing value for 'key chapter-id: #chapter/@id' within #book"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_validate_unknown_root() -> Result<()> {
+    let schema = compile()?;
+
+    assert_eq!(
+        errors(&schema, "<library/>"),
+        vec!["line 1: root element #library is not defined in the schema"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_whas_export_constraints() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    assert!(
+        whas.contains("    key chapter-id: #chapter/@id\n"),
+        "{}",
+        whas
+    );
+    assert!(whas.contains("    unique #chapter/#title\n"), "{}", whas);
+    assert!(
+        whas.contains("    keyref #xref/@target: chapter-id\n"),
+        "{}",
+        whas
+    );
+    Ok(())
+}
+
+/// the names of identity constraints are global in XSD, so the same key on two elements,
+/// and an element that is exported in two places, get numbered names
+#[test]
+fn test_xsd_export_constraint_names() -> Result<()> {
+    let whas = "@id\nItem {}\n\n@to\nRef {}\n\n\
+                #list {\n    #item*: Item\n    #ref*: Ref\n}\n    key id: #item/@id\n    keyref #ref/@to: id\n\n\
+                #set {\n    #item*: Item\n    #ref*: Ref\n}\n    key id: #item/@id\n    keyref #ref/@to: id\n\n\
+                #doc {\n    #a {\n        #entry {\n            #item*: Item\n        }\n            unique #item/@id\n    }\n\
+                    #b {\n        #entry {\n            #item*: Item\n        }\n            unique #item/@id\n    }\n}\n";
+    let schema = compiler::compile(&ast::SchemaFile::parse(whas)?.into())?;
+    let xsd = XsdExporter::default().export_schema(&schema)?;
+
+    for name in [
+        r#"<xs:key name="id">"#,
+        r#"<xs:keyref name="list-ref-to-ref" refer="id">"#,
+        r#"<xs:key name="id2">"#,
+        r#"<xs:keyref name="set-ref-to-ref" refer="id2">"#,
+        r#"<xs:unique name="entry-item-id-unique">"#,
+        r#"<xs:unique name="entry-item-id-unique2">"#,
+    ] {
+        assert!(xsd.contains(name), "missing '{}' in {}", name, xsd);
+    }
+    Ok(())
+}
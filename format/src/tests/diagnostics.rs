@@ -0,0 +1,162 @@
+use crate::{ast, compiler};
+
+fn error(whas: &str) -> String {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into()).unwrap_err().to_string()
+}
+
+fn syntax_error(whas: &str) -> String {
+    ast::SchemaFile::parse(whas).unwrap_err().to_string()
+}
+
+#[test]
+fn test_compile_reports_all_errors() {
+    let error = error(
+        "#doc {\n    #a: Unknown\n    #b: AlsoUnknown\n    @lang: Block\n}\n\n\
+         Block {\n    #c: String\n    ...Missing\n}\n\n\
+         Title: String<..40>\nLong: Title<10..50>\n\n\
+         #title: Long\n#other: Missing2\n",
+    );
+
+    assert!(error.contains("6 errors"), "{}", error);
+
+    for message in [
+        "Type definition not found for NonPrimitive 'Unknown'",
+        "Type definition not found for NonPrimitive 'AlsoUnknown'",
+        "group Type not supported for Attribute",
+        "cannot splat 'Missing', because it is not defined",
+        "maxLength 50 is wider than the maxLength 40 of the base type",
+        "Type definition not found for NonPrimitive 'Missing2'",
+    ] {
+        assert!(
+            error.contains(message),
+            "missing '{}' in {}",
+            message,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_failed_type_is_reported_once() {
+    // the types and elements that use a failed type continue with a placeholder
+    let error = error(
+        "Item {\n    #a: Unknown\n}\n\nList {\n    #item+: Item\n}\n\n\
+         #first: Item\n#list: List\n@id\n@id\n#dup: String\n",
+    );
+
+    assert!(error.contains("2 errors"), "{}", error);
+    assert_eq!(error.matches("'Unknown'").count(), 1, "{}", error);
+    assert!(
+        error.contains("@id is declared more than once on #dup"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_single_error_is_kept() {
+    assert_eq!(
+        error("#doc: Unknown"),
+        "Type definition not found for NonPrimitive 'Unknown'"
+    );
+}
+
+#[test]
+fn test_parse_reports_all_syntax_errors() {
+    let error = syntax_error(
+        "// header\n#first: String\n\n#broken {\n    #oops: @@@\n}\n\n\
+         Good {\n    #x: String\n}\n\n#also_bad: String String\n\nBad = {\n\n#last: Int\n",
+    );
+
+    assert!(error.starts_with("3 syntax errors"), "{}", error);
+
+    // the lines of the whole input, not of the items
+    for line in ["--> 5:12", "--> 12:19", "--> 14:5"] {
+        assert!(error.contains(line), "missing '{}' in {}", line, error);
+    }
+}
+
+#[test]
+fn test_parse_single_syntax_error() {
+    let error = syntax_error("#first: String\n#second: @@@\n");
+
+    assert!(!error.contains("syntax errors"), "{}", error);
+    assert!(error.contains("--> 2:10"), "{}", error);
+}
+
+#[test]
+fn test_parse_reports_a_mistake_once() {
+    // recovering at a line inside the block should not fail at the same spot again
+    for (whas, line) in [
+        ("#doc {\n    #a: String\n#b String\n}\n", "--> 3:4"),
+        ("#doc {\n    #a: String\n#b: String\n    #c @@@\n}\n", "--> 4:8"),
+    ] {
+        let error = syntax_error(whas);
+
+        assert!(!error.contains("syntax errors"), "{}", error);
+        assert_eq!(error.matches("-->").count(), 1, "{}", error);
+        assert!(error.contains(line), "{}", error);
+    }
+}
+
+#[test]
+fn test_alias_of_unknown_type_is_reported() {
+    assert_eq!(error("A: Unknown1\n"), "could not find Type declaration for 'Unknown1'");
+    assert_eq!(
+        error("A: Unknown1\n#a: A\n"),
+        "could not find Type declaration for 'Unknown1'"
+    );
+}
+
+#[test]
+fn test_duplicate_definitions_are_reported() {
+    assert_eq!(
+        error("A: String\nA: Int\n#a: A\n"),
+        "type 'A' is defined more than once"
+    );
+    assert_eq!(
+        error("A {\n    #x: String\n}\n\nA {\n    #y: String\n}\n\n#a: A\n"),
+        "type 'A' is defined more than once"
+    );
+    assert_eq!(
+        error("#a: String\n#a: Int\n"),
+        "#a is declared more than once at the top level"
+    );
+}
+
+#[test]
+fn test_invalid_facet_ranges_are_reported() {
+    assert_eq!(error("#a: String<5..2>"), "minLength 5 is greater than maxLength 2");
+    assert_eq!(error("#a: [Int]<5..2>"), "minLength 5 is greater than maxLength 2");
+    assert_eq!(error("#a: Int<5..2>"), "minInclusive 5 is greater than maxInclusive 2");
+    assert!(
+        error("Code: String<5..2>\n#a: Code")
+            .ends_with("minLength 5 is greater than maxLength 2")
+    );
+}
+
+#[test]
+fn test_compile_reports_different_errors_at_once() {
+    let error = error(
+        "A: Unknown1\nB: String\nB: Int\n\n#doc {\n    #a: Missing\n    #b: String<5..2>\n}\n\n\
+         #doc: String\n",
+    );
+
+    assert!(error.contains("5 errors"), "{}", error);
+
+    for message in [
+        "could not find Type declaration for 'Unknown1'",
+        "type 'B' is defined more than once",
+        "#doc is declared more than once at the top level",
+        "Type definition not found for NonPrimitive 'Missing'",
+        "minLength 5 is greater than maxLength 2",
+    ] {
+        assert!(
+            error.contains(message),
+            "missing '{}' in {}",
+            message,
+            error
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::export::{DocExporter, DocSite, Exporter};
+use crate::{ast, compiler, model};
+use crate::model::CommentBuilder;
+use anyhow::Result;
+
+fn generate_site() -> Result<DocSite> {
+    let schema = model::Schema::from_file("src/tests/schemas/codegen/library.whas")?;
+    DocExporter::with_title("library").export_schema(&schema)
+}
+
+/// there is a page per element name and per named type, next to the assets
+#[test]
+fn test_doc_pages() -> Result<()> {
+    let site = generate_site()?;
+    let pages = site.pages();
+
+    for page in [
+        "index.html",
+        "search.js",
+        "search-index.js",
+        "style.css",
+        "element-book.html",
+        "element-chapter.html",
+        "type-Chapter.html",
+        "type-PublishStatus.html",
+    ] {
+        assert!(pages.contains_key(page), "missing page {}", page);
+    }
+
+    let index = &pages["index.html"];
+    assert!(index.contains("<title>library - library</title>"));
+    assert!(index.contains("<li><a href=\"element-book.html\"><code>&lt;book&gt;</code></a></li>"));
+
+    let search = &pages["search-index.js"];
+    assert!(search.starts_with("window.WHAS_SEARCH_INDEX = ["));
+    assert!(search.contains("\"href\": \"type-Username.html\""));
+
+    Ok(())
+}
+
+/// element pages show the content model, attributes and where the element is used
+#[test]
+fn test_doc_element_page() -> Result<()> {
+    let site = generate_site()?;
+    let page = &site.pages()["element-chapter.html"];
+
+    // declarations that only differ in occurrence are documented once
+    assert_eq!(page.matches("<section class=\"declaration\"").count(), 1);
+
+    assert!(
+        page.contains(
+            "<dt>Type</dt><dd><a href=\"type-Chapter.html\"><code>Chapter</code></a></dd>"
+        )
+    );
+    assert!(page.contains(
+        "<div class=\"particle\"><a href=\"element-author.html\"><code>&lt;author&gt;</code></a><span class=\"occurs\">0..1</span></div>"
+    ));
+    assert!(page.contains("<div class=\"model choice\">"));
+    assert!(page.contains(
+        "<tr id=\"attr-status\"><td><code>@status</code></td><td><a href=\"type-PublishStatus.html\"><code>PublishStatus</code></a></td><td>optional</td>"
+    ));
+    assert!(page.contains("<li><a href=\"element-note.html\"><code>&lt;note&gt;</code></a></li>"));
+    assert!(page.contains("<li><a href=\"type-Chapter.html\"><code>Chapter</code></a></li>"));
+
+    Ok(())
+}
+
+/// type pages list facets and back-references
+#[test]
+fn test_doc_type_page() -> Result<()> {
+    let site = generate_site()?;
+
+    let username = &site.pages()["type-Username.html"];
+    assert!(username.contains("<tr><td>minLength</td><td><code>3</code></td></tr>"));
+    assert!(username.contains("<tr><td>pattern</td><td><code>/[a-z_]+/</code></td></tr>"));
+    assert!(
+        username
+            .contains("<li><a href=\"element-author.html\"><code>&lt;author&gt;</code></a></li>")
+    );
+
+    let status = &site.pages()["type-PublishStatus.html"];
+    assert!(
+        status.contains("<li><a href=\"type-Chapter.html\"><code>Chapter/@status</code></a></li>")
+    );
+
+    Ok(())
+}
+
+/// names that only differ in characters that are not safe in file names, or in case, get
+/// pages and anchors of their own
+#[test]
+fn test_doc_colliding_names() -> Result<()> {
+    let ast = ast::SchemaFile::parse(
+        "#doc {\n    #größe: String\n    #gr__e: String\n    #Item: String\n    #item: String\n    @xlink:href: String\n    @xlink_href: String\n}\n",
+    )?;
+    let schema = compiler::compile(&ast.into())?;
+    let site = DocExporter::with_title("colliding").export_schema(&schema)?;
+    let pages = site.pages();
+
+    for page in [
+        "element-gr_f6__df_e.html",
+        "element-gr____e.html",
+        "element-Item.html",
+        "element-item2.html",
+    ] {
+        assert!(pages.contains_key(page), "missing page {}", page);
+    }
+
+    let doc = &pages["element-doc.html"];
+    assert!(doc.contains("<tr id=\"attr-xlink_3a_href\">"));
+    assert!(doc.contains("<tr id=\"attr-xlink__href\">"));
+
+    Ok(())
+}
+
+/// comment delimiters and indentation are removed, Markdown is kept
+#[test]
+fn test_comment_content() -> Result<()> {
+    let comment = |text: &str| CommentBuilder::default().text(text.to_string()).build();
+
+    assert_eq!(comment("// a line comment")?.content(), "a line comment");
+    assert_eq!(comment("/* a wild comment */")?.content(), "a wild comment");
+    assert_eq!(
+        comment(
+            "```\n        a **Markdown** comment\n\n            indented\n        $```\n    ```"
+        )?
+        .content(),
+        "a **Markdown** comment\n\n    indented\n```"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use crate::export::{Exporter, WhasExporter, XsdExporter};
+use crate::import::{DtdImporter, Importer};
+use crate::model;
+use crate::model::{Duplicity, GroupType, PrimitiveType, SimpleType, TypeBor};
+use anyhow::Result;
+
+fn import_book() -> Result<model::Schema> {
+    DtdImporter::from_file("src/tests/schemas/dtd/book.dtd")?.import_schema()
+}
+
+/// element declarations become named Types, including those from conditional sections
+#[test]
+fn test_dtd_element_types() -> Result<()> {
+    let schema = import_book()?;
+
+    for name in ["Book", "Chapter", "Section", "Para", "List", "Item", "Img"] {
+        schema.assert_type_name(name)?;
+    }
+
+    // text-only elements without attributes do not need a named type
+    assert!(schema.get_type_by_name("Title").is_none());
+
+    // elements in IGNORE sections are skipped
+    assert!(schema.get_elements_by_name("unused").is_empty());
+
+    let Some(TypeBor::Group(book)) = schema.get_type_by_name("Book") else {
+        panic!("Book should be a group")
+    };
+    assert_eq!(*book.ty(), GroupType::Sequence);
+    assert_eq!(book.items().len(), 3);
+
+    let author = schema.get_elements_by_name("author");
+    assert_eq!(author.len(), 1);
+    assert_eq!(*author[0].duplicity(), Duplicity::Min1);
+
+    Ok(())
+}
+
+/// mixed content maps to a mixed choice of repeatable elements
+#[test]
+fn test_dtd_mixed_content() -> Result<()> {
+    let schema = import_book()?;
+
+    let Some(TypeBor::Group(para)) = schema.get_type_by_name("Para") else {
+        panic!("Para should be a group")
+    };
+
+    assert!(*para.mixed());
+    assert_eq!(*para.ty(), GroupType::Choice);
+    assert_eq!(para.items().len(), 2);
+
+    Ok(())
+}
+
+/// attribute lists, including the ones from external parameter entities
+#[test]
+fn test_dtd_attributes() -> Result<()> {
+    let schema = import_book()?;
+
+    let Some(TypeBor::Group(book)) = schema.get_type_by_name("Book") else {
+        panic!("Book should be a group")
+    };
+
+    let attrs = book.attributes();
+    let mut names = attrs.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["edition", "id", "lang", "refs", "status"]);
+
+    let id = attrs["id"].resolve(&schema);
+    assert!(!id.required());
+    assert_eq!(
+        *id.typing.resolve(&schema),
+        SimpleType::Builtin {
+            name: PrimitiveType::ID
+        }
+    );
+
+    let status = attrs["status"].resolve(&schema);
+    assert_eq!(status.default_value, Some("draft".to_string()));
+    assert_eq!(
+        status
+            .typing
+            .resolve(&schema)
+            .restrictions()
+            .and_then(|r| r.enumeration.clone()),
+        Some(vec!["draft".into(), "review".into(), "published".into()])
+    );
+
+    let Some(TypeBor::Group(img)) = schema.get_type_by_name("Img") else {
+        panic!("Img should be a group")
+    };
+    assert!(img.attributes()["src"].resolve(&schema).required());
+
+    Ok(())
+}
+
+#[test]
+fn test_dtd_undeclared_entity() {
+    let result = DtdImporter::new("<!ELEMENT a (%missing;)>").import_schema();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_dtd_recursive_entity() -> Result<()> {
+    let result = DtdImporter::from_file("src/tests/schemas/dtd/recursive.dtd")?.import_schema();
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// the imported schema can be printed as WHAS and compiled again
+#[test]
+fn test_dtd_to_whas() -> Result<()> {
+    let schema = import_book()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    assert!(whas.contains("Book {"));
+    assert!(whas.contains("Para: x?{"));
+    assert!(whas.contains("#author+: String"));
+    assert!(whas.contains("@status?: \"draft\" | \"review\" | \"published\""));
+    assert!(whas.contains("#book: Book"));
+
+    let path = std::env::temp_dir().join("whas_test_dtd_book.whas");
+    std::fs::write(&path, &whas)?;
+
+    let recompiled = model::Schema::from_file(&path)?;
+    recompiled.assert_type_name("Chapter")?;
+    recompiled.assert_element_name("book")?;
+
+    Ok(())
+}
+
+fn dtd_to_xsd(dtd: &str) -> Result<String> {
+    let schema = DtdImporter::new(dtd).import_schema()?;
+    XsdExporter::default().export_schema(&schema)
+}
+
+/// the occurrence of a group repeats the group as a whole, not each of its items
+#[test]
+fn test_dtd_group_occurrence() -> Result<()> {
+    let choice = dtd_to_xsd(
+        "<!ELEMENT doc (p|list)*>\n<!ELEMENT p (#PCDATA)>\n<!ELEMENT list (#PCDATA)>",
+    )?;
+    assert!(choice.contains(r#"<xs:choice minOccurs="0" maxOccurs="unbounded">"#), "{}", choice);
+    assert!(choice.contains(r#"<xs:element name="p" minOccurs="1" maxOccurs="1""#), "{}", choice);
+
+    let sequence = dtd_to_xsd(
+        "<!ELEMENT doc (t, (a,b)+)>\n<!ELEMENT t (#PCDATA)>\n\
+         <!ELEMENT a (#PCDATA)>\n<!ELEMENT b (#PCDATA)>",
+    )?;
+    assert!(sequence.contains(r#"<xs:sequence minOccurs="1" maxOccurs="unbounded">"#), "{}", sequence);
+    assert!(sequence.contains(r#"<xs:element name="a" minOccurs="1" maxOccurs="1""#), "{}", sequence);
+    assert!(sequence.contains(r#"<xs:element name="b" minOccurs="1" maxOccurs="1""#), "{}", sequence);
+
+    Ok(())
+}
+
+/// ANY and mixed content allow any number of children of every kind
+#[test]
+fn test_dtd_repeated_mixed_content() -> Result<()> {
+    for dtd in [
+        "<!ELEMENT doc ANY>\n<!ELEMENT a (#PCDATA)>\n<!ELEMENT b (#PCDATA)>",
+        "<!ELEMENT doc (#PCDATA|a|b)*>\n<!ELEMENT a (#PCDATA)>\n<!ELEMENT b (#PCDATA)>",
+    ] {
+        let xsd = dtd_to_xsd(dtd)?;
+
+        assert!(xsd.contains(r#"<xs:complexType name="Doc" mixed="true">"#), "{}", xsd);
+        assert!(xsd.contains(r#"<xs:choice minOccurs="0" maxOccurs="unbounded">"#), "{}", xsd);
+        for name in ["a", "b"] {
+            let element = format!(r#"<xs:element name="{}" minOccurs="1" maxOccurs="1""#, name);
+            assert!(xsd.contains(&element), "{}", xsd);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::export::{Exporter, FontoSchemaExporter, WhasExporter};
+use crate::formats::fonto;
+use crate::{ast, compiler, model};
+use anyhow::Result;
+use itertools::Itertools;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/element_refs.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+/// the references to a top-level element in the blocks of the schema
+fn references<'a>(schema: &'a model::Schema, name: &str) -> Vec<&'a model::Element> {
+    schema
+        .get_elements_local()
+        .into_iter()
+        .filter(|el| el.name() == name && *el.global_ref())
+        .collect()
+}
+
+#[test]
+fn test_compile_element_refs() -> Result<()> {
+    let schema = compile()?;
+    let title = schema.get_element_root_by_name("title").unwrap();
+
+    // the references share the type and attributes of the top-level element
+    let refs = references(&schema, "title");
+    assert_eq!(refs.len(), 2);
+    for reference in &refs {
+        assert_eq!(reference.typing(), title.typing());
+        assert_eq!(reference.attributes(), title.attributes());
+    }
+
+    // with the duplicity of the reference
+    assert_eq!(
+        refs.iter().map(|el| el.min_occurs()).sorted().collect_vec(),
+        vec![0, 1]
+    );
+
+    // the block of #section refers to the element itself
+    let section = schema.get_element_root_by_name("section").unwrap();
+    let nested = references(&schema, "section");
+    assert!(nested.iter().all(|el| el.typing() == section.typing()));
+    Ok(())
+}
+
+#[test]
+fn test_element_ref_errors() {
+    let error = compile_str("#doc { ref #missing }")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains(
+            "ref #missing refers to an element that is not defined in the root of the schema"
+        ),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_validate_element_refs() -> Result<()> {
+    let schema = compile_str(
+        "#chapter {\n    #section*: { #title: String }\n}\n    unique #section/#title\n\
+         #doc { ref #chapter* }",
+    )?;
+
+    // the constraints of the top-level element apply to the references
+    let errors = schema
+        .validate(&"<doc><chapter><section><title>a</title></section><section><title>a</title></section></chapter></doc>".to_string())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("within #chapter"));
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_element_refs() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+
+    let refs = fonto_schema
+        .content_models()
+        .iter()
+        .flat_map(|cm| match cm {
+            fonto::ContentModel::Sequence { items, .. } => items.as_slice(),
+            _ => &[],
+        })
+        .filter_map(|cm| match cm {
+            fonto::ContentModel::Element { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .sorted()
+        .dedup()
+        .collect_vec();
+
+    assert_eq!(refs, vec!["section", "title"]);
+    Ok(())
+}
+
+#[test]
+fn test_element_refs_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+    assert!(whas.contains("    ref #title?\n"), "{}", whas);
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    assert_eq!(references(&reparsed, "section").len(), 2);
+    Ok(())
+}
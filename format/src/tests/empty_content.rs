@@ -0,0 +1,84 @@
+use crate::export::{Exporter, FontoSchemaExporter};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/empty.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+fn is_empty_content(schema: &model::Schema, name: &str) -> bool {
+    schema
+        .get_element_root_by_name(name)
+        .unwrap()
+        .typing()
+        .grouptype(schema)
+        .is_some_and(|group| group.is_empty_content(schema))
+}
+
+#[test]
+fn test_compile_empty_content() -> Result<()> {
+    let schema = compile()?;
+
+    for name in ["br", "hr", "img"] {
+        assert!(is_empty_content(&schema, name), "#{}", name);
+    }
+
+    // mixed content allows text, even without child elements
+    let schema = compile_str("#plain x{}")?;
+    assert!(!is_empty_content(&schema, "plain"));
+
+    // an empty block splatted into another adds no content
+    let schema = compile_str("Attrs {}\n#link { ...Attrs }")?;
+    assert!(is_empty_content(&schema, "link"));
+    Ok(())
+}
+
+/// the core types can be redefined by the schema
+#[test]
+fn test_redefine_empty() -> Result<()> {
+    let schema = compile_str("Empty { #nothing?: String }\n#br: Empty")?;
+    assert!(!is_empty_content(&schema, "br"));
+    Ok(())
+}
+
+#[test]
+fn test_validate_empty_content() -> Result<()> {
+    let schema = compile()?;
+    let errors = |xml: &str| match schema.validate(&xml.to_string()) {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    };
+
+    assert!(errors(r#"<p>a <br/> b <img src="a.png"/></p>"#).is_empty());
+    assert_eq!(
+        errors("<p><br>text</br><br><hr/></br></p>"),
+        vec![
+            "line 1: #br cannot have any content",
+            "line 1: #br cannot have any content"
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_empty_content() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+    let empty = fonto_schema.get_content_model_empty_idx();
+
+    let json = serde_json::to_value(&fonto_schema)?;
+    for element in json["elements"].as_array().unwrap() {
+        let content_model_ref = element["contentModelRef"].as_u64().unwrap() as usize;
+
+        match element["localName"].as_str().unwrap() {
+            "p" => assert_ne!(content_model_ref, empty),
+            name => assert_eq!(content_model_ref, empty, "#{}", name),
+        }
+    }
+    Ok(())
+}
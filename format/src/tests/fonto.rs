@@ -37,3 +37,18 @@ fn test_export() {
 
     // todo: other validation
 }
+
+/// compiling and exporting the same schema twice gives byte-identical output
+#[test]
+fn test_export_deterministic() {
+    let export = || {
+        let schema = crate::tests::get_compiled_schema();
+        let fonto_schema = FontoSchemaExporter::default()
+            .export_schema(&schema)
+            .unwrap();
+
+        serde_json::to_string(&fonto_schema).unwrap()
+    };
+
+    assert_eq!(export(), export());
+}
@@ -62,3 +62,207 @@ fn test_import_cyclic() {
     // Should have types from both files
     assert_eq!(schema.types_count(), 2, "Should load types from both cyclic schemas");
 }
+
+/// types of two imports that define the same name are told apart by an alias and a rename
+#[test]
+fn test_import_qualified_and_renamed() -> anyhow::Result<()> {
+    let schema = model::Schema::from_file("./src/tests/schemas/xsd/qualified_imports.whas")?;
+
+    let names = schema.all_type_names();
+    for name in ["TopicTitle", "dc.Meta", "dc.Title", "dc.Creator"] {
+        assert!(names.contains(&&name.to_string()), "{:?}", names);
+    }
+    assert!(!names.contains(&&"Title".to_string()), "{:?}", names);
+
+    let topic = schema.get_element_root_by_name("topic").unwrap();
+    let heading = topic
+        .typing()
+        .grouptype(&schema)
+        .unwrap()
+        .find_element("heading", &schema)
+        .unwrap();
+    let title = heading.typing().grouptype(&schema).unwrap();
+    assert!(title.find_element("sub", &schema).is_some());
+    Ok(())
+}
+
+#[test]
+fn test_import_name_conflict() {
+    let error = model::Schema::from_file("./src/tests/schemas/imports/conflict.whas")
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("type 'Title' is defined in both"), "{}", error);
+    assert!(error.contains("conflict.whas") && error.contains("dc.whas"), "{}", error);
+}
+
+/// qualified names only refer to the types of an import with that alias
+#[test]
+fn test_import_qualified_errors() {
+    let error = |whas: &str| {
+        let ast = ast::SchemaFile::parse(whas).unwrap();
+        crate::compiler::compile(&ast.into()).unwrap_err().to_string()
+    };
+
+    assert!(
+        error("#doc: dc.Title").contains("Type definition not found for NonPrimitive 'dc.Title'")
+    );
+    assert!(error("dc.Title: String\n#doc: String").contains(
+        "type definition 'dc.Title' cannot have a qualified name, only types of an import with an alias can"
+    ));
+}
+
+/// code generators join qualified names into one identifier
+#[test]
+fn test_import_qualified_codegen() -> anyhow::Result<()> {
+    use crate::export::{Exporter, TypescriptExporter};
+
+    let schema = model::Schema::from_file("./src/tests/schemas/xsd/qualified_imports.whas")?;
+    let ts = TypescriptExporter::default().export_schema(&schema)?;
+
+    assert!(ts.contains("export interface DcMeta {"), "{}", ts);
+    assert!(ts.contains("\"meta\": DcMeta;"), "{}", ts);
+    Ok(())
+}
+
+/// imported schemas only offer their public types, and the imports they export again
+#[test]
+fn test_import_visibility() -> anyhow::Result<()> {
+    let schema = model::Schema::from_file("./src/tests/schemas/imports/visibility.whas")?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let group = doc.typing().grouptype(&schema).unwrap();
+
+    // the re-exported Title, and the private Text that the public Note uses
+    let title = group.find_element("title", &schema).unwrap();
+    assert!(title.typing().grouptype(&schema).is_some());
+    let note = group.find_element("note", &schema).unwrap();
+    let note = note.typing().grouptype(&schema).unwrap();
+    assert!(note.find_element("text", &schema).is_some());
+
+    let error = |path: &str| model::Schema::from_file(path).unwrap_err().to_string();
+
+    // private types are reported as such, once, however they are referred to
+    for path in ["private-import", "private-wildcard", "private-alias"] {
+        let error = error(&format!("./src/tests/schemas/imports/{}.whas", path));
+        assert!(error.starts_with("cannot import 'Text' from"), "{}", error);
+        assert!(error.contains("library.whas into"), "{}", error);
+        assert!(!error.contains("not found"), "{}", error);
+    }
+    assert!(
+        error("./src/tests/schemas/imports/private-reexport.whas")
+            .contains("Type definition not found for NonPrimitive 'Creator'")
+    );
+    Ok(())
+}
+
+/// imports find packages through the whas.toml above the entry schema, and other
+/// schemas in the include paths
+#[test]
+fn test_import_search_paths() -> anyhow::Result<()> {
+    let include_paths = [PathBuf::from("./src/tests/schemas/imports/vocab")];
+    let schema = model::Schema::from_file_with_include_paths(
+        "./src/tests/schemas/imports/project/schemas/document.whas",
+        &include_paths,
+    )?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let group = doc.typing().grouptype(&schema).unwrap();
+    assert!(group.find_element("link", &schema).is_some());
+    assert!(group.find_element("term", &schema).is_some());
+
+    // without the include path, the import is only looked for next to the schema
+    let error = model::Schema::from_file("./src/tests/schemas/imports/project/schemas/document.whas")
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains("terms.whas"), "{:?}", error);
+
+    let error = model::Schema::from_file(
+        "./src/tests/schemas/imports/project/schemas/unknown-package.whas",
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        error.contains("unknown package '@vendor' in import \"@vendor/xlink\""),
+        "{}",
+        error
+    );
+    Ok(())
+}
+
+/// the modules of the standard library are bundled with the compiler
+#[test]
+fn test_import_std() -> anyhow::Result<()> {
+    let schema = model::Schema::from_file("./src/tests/schemas/imports/stdlib.whas")?;
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let group = doc.typing().grouptype(&schema).unwrap();
+
+    let metadata = group.find_element("metadata", &schema).unwrap();
+    let metadata = metadata.typing().grouptype(&schema).unwrap();
+    assert!(metadata.find_element("dc:creator", &schema).is_some());
+
+    let xml = schema.get_group_by_name("XmlAttributes").unwrap();
+    let attributes = xml.attributes().keys().collect::<Vec<_>>();
+    assert!(attributes.contains(&&"xml:lang".to_string()), "{:?}", attributes);
+
+    for name in ["XLink", "Math", "MathPair", "IsoDate", "CountryCode"] {
+        assert!(schema.all_type_names().contains(&&name.to_string()), "{}", name);
+    }
+
+    let error = model::Schema::from_file("./src/tests/schemas/imports/unknown-std.whas")
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("unknown module 'std:links', the standard library has std:dc, std:iso"),
+        "{}",
+        error
+    );
+    Ok(())
+}
+
+/// the vocabularies of the standard library are in their own namespaces, whichever prefix
+/// the documents bind to them
+#[test]
+fn test_std_namespaces() -> anyhow::Result<()> {
+    use crate::export::{Exporter, FontoSchemaExporter, XsdExporter};
+
+    let schema = model::Schema::from_file("./src/tests/schemas/imports/stdlib.whas")?;
+
+    let xml = |math: &str| {
+        format!(
+            r#"<doc xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <metadata>
+    <dc:title>Sums</dc:title>
+    <dc:creator>Ada</dc:creator>
+  </metadata>
+  <m:math xmlns:m="http://www.w3.org/1998/Math/MathML" display="block">
+    <m:mi>x</m:mi><m:mo>+</m:mo><m:mn>1</m:mn>
+  </m:math>
+  <math xmlns="http://www.w3.org/1998/Math/MathML">
+    <mfrac><mi>x</mi><mn>2</mn></mfrac>{}
+  </math>
+  <published>2024-05-31</published>
+</doc>"#,
+            math
+        )
+    };
+
+    assert!(schema.validate(&xml("<mspace/>")).is_ok());
+    let errors = schema.validate(&xml("<mspace>x</mspace>")).unwrap_err();
+    assert_eq!(errors[0].to_string(), "line 10: #mml:mspace cannot have any content");
+
+    // the elements are declared by the schemas of their namespaces, and a row of MathML
+    // is a choice between tokens that repeats
+    let xsd = XsdExporter::default().export_schema(&schema)?;
+    assert!(xsd.contains(r#"xmlns:mml="http://www.w3.org/1998/Math/MathML""#), "{}", xsd);
+    assert!(xsd.contains(r#"<xs:import namespace="http://purl.org/dc/elements/1.1/""#), "{}", xsd);
+    assert!(xsd.contains(r#"<xs:element ref="dc:title" minOccurs="0" maxOccurs="unbounded""#), "{}", xsd);
+    assert!(xsd.contains(r#"<xs:choice minOccurs="0" maxOccurs="unbounded">"#), "{}", xsd);
+    assert!(xsd.contains(r#"<xs:element ref="mml:mi" minOccurs="1" maxOccurs="1""#), "{}", xsd);
+    assert!(!xsd.contains(r#"name="mml:"#), "{}", xsd);
+
+    let fonto = serde_json::to_string(&FontoSchemaExporter::default().export_schema(&schema)?)?;
+    assert!(
+        fonto.contains(r#""localName":"mi","namespaceURI":"http://www.w3.org/1998/Math/MathML""#),
+        "{}",
+        fonto
+    );
+    Ok(())
+}
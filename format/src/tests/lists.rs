@@ -0,0 +1,146 @@
+use crate::export::{Exporter, FontoSchemaExporter, WhasExporter};
+use crate::formats::fonto;
+use crate::model::restriction::SimpleTypeRestriction;
+use crate::model::{PrimitiveType, SimpleType};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/lists.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+/// the restrictions on the list and its item type, for a list type with facets
+fn list_facets<'a>(
+    schema: &'a model::Schema,
+    type_name: &str,
+) -> (&'a SimpleTypeRestriction, &'a SimpleType) {
+    let SimpleType::Derived {
+        base, restrictions, ..
+    } = schema.get_simpletype_by_name(type_name).unwrap()
+    else {
+        panic!("{} has no list facets", type_name);
+    };
+
+    let SimpleType::List { item_type, .. } = base.resolve(schema) else {
+        panic!("{} is not a list", type_name);
+    };
+
+    (restrictions, item_type.resolve(schema))
+}
+
+#[test]
+fn test_compile_lists() -> Result<()> {
+    let schema = compile()?;
+
+    let (restrictions, item) = list_facets(&schema, "Coords");
+    assert_eq!(restrictions.length, Some(2));
+    assert_eq!(
+        item,
+        &SimpleType::Builtin {
+            name: PrimitiveType::Decimal
+        }
+    );
+
+    // the facets of the items are a type of their own
+    let (restrictions, item) = list_facets(&schema, "Tags");
+    assert_eq!(
+        (restrictions.min_length, restrictions.max_length),
+        (Some(1), Some(5))
+    );
+    assert_eq!(item.restrictions().unwrap().max_length, Some(20));
+
+    assert!(matches!(
+        schema.get_simpletype_by_name("Codes"),
+        Some(SimpleType::List { .. })
+    ));
+    Ok(())
+}
+
+/// IDRefs and NameTokens are lists too, their shorthand facets count items
+#[test]
+fn test_builtin_list_facets() -> Result<()> {
+    let schema = compile_str("Refs: [IDRef]<1..3>\n#doc { #refs: Refs }")?;
+    let refs = schema.get_simpletype_by_name("Refs").unwrap();
+
+    assert!(refs.is_list(&schema));
+    assert_eq!(refs.restrictions().unwrap().max_length, Some(3));
+    Ok(())
+}
+
+#[test]
+fn test_list_errors() {
+    let error = |whas: &str| compile_str(whas).unwrap_err().to_string();
+
+    assert!(
+        error("Item { #a: String }\nItems: [Item]\n#doc { #items: Items }")
+            .contains("List items must be simple types")
+    );
+    assert!(error("#doc { #nested: [[Int]] }").contains("cannot be lists themselves"));
+    assert!(error("#doc { #refs: [[IDRef]] }").contains("cannot be lists themselves"));
+    assert!(
+        error("#doc { #nums: [Int]<minInclusive: 1> }")
+            .contains("Lists only support the length, minLength and maxLength facets")
+    );
+
+    // the error names the facets of the list that are wrong, not its item type
+    assert!(
+        error("Bad: [Int]<2, minInclusive: 1, pattern: /[0-9 ]+/>\n#doc: Bad").starts_with(
+            "'Bad' cannot restrict '[Int]': pattern, minInclusive cannot restrict a list."
+        )
+    );
+}
+
+#[test]
+fn test_lists_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    for name in ["Coords", "Tags", "Codes"] {
+        assert!(
+            reparsed
+                .get_simpletype_by_name(name)
+                .unwrap()
+                .is_list(&reparsed),
+            "{}",
+            whas
+        );
+    }
+    assert_eq!(
+        list_facets(&reparsed, "Tags").0,
+        list_facets(&schema, "Tags").0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_lists() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+
+    // Coords is restricted on a list of decimals
+    let derived_lists = fonto_schema
+        .simple_types()
+        .iter()
+        .filter(|st| match st {
+            fonto::SimpleType::Derived { base, restrictions } => {
+                restrictions.length == Some(2)
+                    && matches!(
+                        fonto_schema.simple_types()[*base],
+                        fonto::SimpleType::List { .. }
+                    )
+            }
+            _ => false,
+        })
+        .count();
+
+    assert_eq!(derived_lists, 1);
+    Ok(())
+}
@@ -1,12 +1,30 @@
 use crate::sourced::{SchemaFileManager, SourcedSchemaFile};
 use crate::*;
 
+mod assertions;
 mod ast;
+mod block_attributes;
+mod codegen;
+mod comments;
 mod compiler;
+mod constraints;
+mod diagnostics;
+mod doc;
+mod dtd;
+mod element_refs;
+mod empty_content;
 mod fonto;
 mod grammar;
 mod imports;
+mod lists;
+mod project;
+mod restrictions;
+mod schematron;
+mod sources;
+mod splats;
+mod substitutions;
 mod types;
+mod xml_names;
 mod xsd;
 
 pub fn get_test_schema_ast() -> SourcedSchemaFile {
@@ -0,0 +1,103 @@
+use crate::project;
+use crate::sourced::Manifest;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// the manifest of the fixture project, writing its outputs to a temporary dir
+fn fixture(name: &str) -> Result<(Manifest, PathBuf)> {
+    let mut manifest = Manifest::from_file("./src/tests/schemas/project/whas.toml")?;
+    let out = std::env::temp_dir().join(format!("whas_test_project_{}", name));
+
+    for entry in &mut manifest.schemas {
+        entry.output_dir = out.join(&entry.output_dir);
+    }
+
+    Ok((manifest, out))
+}
+
+#[test]
+fn test_project_build() -> Result<()> {
+    let (manifest, out) = fixture("build")?;
+    let written = project::build(&manifest, &[])?;
+
+    assert_eq!(
+        written,
+        vec![
+            out.join("build/articles/articles.xsd"),
+            out.join("build/articles/fonto.schema.json"),
+            out.join("build/articles/articles.sch"),
+            out.join("build/books/books-schema.xsd"),
+            out.join("build/books/books.sch"),
+        ]
+    );
+
+    // only the roots are global elements, in the namespace of the entry
+    let xsd = std::fs::read_to_string(&written[0])?;
+    assert!(xsd.contains(r#"targetNamespace="http://example.com/articles""#), "{}", xsd);
+    assert!(xsd.contains(r#"<xs:element name="article""#), "{}", xsd);
+    assert!(!xsd.contains(r#"<xs:element name="note""#), "{}", xsd);
+
+    // XPaths do not use the default namespace, so they refer to the elements with a prefix
+    for xpath in [
+        r#"xmlns:tns="http://example.com/articles""#,
+        r#"<xs:selector xpath=".//tns:section"/>"#,
+        r#"<xs:field xpath="tns:title"/>"#,
+        r#"<xs:assert test="count(tns:section) &lt;= 20"/>"#,
+    ] {
+        assert!(xsd.contains(xpath), "missing '{}' in {}", xpath, xsd);
+    }
+
+    // and the same goes for the Fonto schema
+    let fonto: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&written[1])?)?;
+    let elements = fonto["elements"].as_array().unwrap();
+    let names = elements.iter().map(|el| el["localName"].as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["article"]);
+    assert_eq!(elements[0]["namespaceURI"], "http://example.com/articles");
+    let local = fonto["localElements"].as_array().unwrap();
+    assert!(local.iter().any(|el| el["localName"] == "section"), "{}", fonto);
+    assert!(
+        local.iter().all(|el| el["namespaceURI"] == "http://example.com/articles"),
+        "{}",
+        fonto
+    );
+
+    // and so do the contexts and assertions of Schematron
+    let sch = std::fs::read_to_string(&written[2])?;
+    for xpath in [
+        r#"<sch:ns prefix="tns" uri="http://example.com/articles"/>"#,
+        r#"<sch:rule context="/tns:article">"#,
+        r#"<sch:assert test="count(tns:section) &lt;= 20">"#,
+        r#"<sch:rule context="/tns:article//tns:section/tns:title">"#,
+    ] {
+        assert!(sch.contains(xpath), "missing '{}' in {}", xpath, sch);
+    }
+
+    // the shared module is found through the include paths of the manifest
+    let xsd = std::fs::read_to_string(&written[3])?;
+    assert!(xsd.contains(r#"<xs:complexType name="Section">"#), "{}", xsd);
+    Ok(())
+}
+
+#[test]
+fn test_project_errors() -> Result<()> {
+    let (mut manifest, _) = fixture("errors")?;
+    manifest.schemas[0].roots.push("chapter".to_string());
+    let error = project::build(&manifest, &[]).unwrap_err().to_string();
+    assert!(
+        error.contains("root element '#chapter' is not a top-level element of articles.whas"),
+        "{}",
+        error
+    );
+
+    let (mut manifest, _) = fixture("twice")?;
+    manifest.schemas[1].output_dir = manifest.schemas[0].output_dir.clone();
+    manifest.schemas[1].xsd.as_mut().unwrap().file = Some(PathBuf::from("articles.xsd"));
+    let error = project::build(&manifest, &[]).unwrap_err().to_string();
+    assert!(error.contains("articles.xsd is written twice"), "{}", error);
+
+    let error = toml::from_str::<Manifest>("[[schema]]\ninput = \"a.whas\"\nformat = \"xsd\"")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("unknown field `format`"), "{}", error);
+    Ok(())
+}
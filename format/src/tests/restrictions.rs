@@ -0,0 +1,213 @@
+use crate::export::{Exporter, FontoSchemaExporter, WhasExporter};
+use crate::formats::fonto;
+use crate::model::SimpleType;
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/restriction_chains.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+fn error(whas: &str) -> String {
+    compile_str(whas).unwrap_err().to_string()
+}
+
+/// name of the base type of a named simple type
+fn base_name(schema: &model::Schema, type_name: &str) -> Option<String> {
+    let SimpleType::Derived { base, .. } = schema.get_simpletype_by_name(type_name).unwrap() else {
+        panic!("{} is not derived", type_name);
+    };
+
+    schema.get_type_name_for_simpletype(base)
+}
+
+#[test]
+fn test_compile_restriction_chains() -> Result<()> {
+    let schema = compile()?;
+
+    assert_eq!(base_name(&schema, "UserId").as_deref(), Some("BaseId"));
+    assert_eq!(base_name(&schema, "ShortId").as_deref(), Some("BaseId"));
+    assert_eq!(base_name(&schema, "Small").as_deref(), Some("Size"));
+    assert_eq!(base_name(&schema, "Heading").as_deref(), Some("ShortTitle"));
+    assert_eq!(base_name(&schema, "ShortTitle").as_deref(), Some("Title"));
+
+    let user_id = schema.get_simpletype_by_name("UserId").unwrap();
+    let restrictions = user_id.restrictions().unwrap();
+    assert_eq!(restrictions.min_inclusive.as_deref(), Some("1000"));
+    assert_eq!(restrictions.max_inclusive.as_deref(), Some("9999"));
+
+    let small = schema.get_simpletype_by_name("Small").unwrap();
+    assert_eq!(
+        small.restrictions().unwrap().enumeration,
+        Some(vec!["s".to_string(), "m".to_string()])
+    );
+    Ok(())
+}
+
+/// a single literal restricts the base to one value, like a union of literals
+#[test]
+fn test_restriction_to_a_single_literal() -> Result<()> {
+    let schema = compile_str("Size: \"s\" | \"m\"\nSmall < Size: \"s\"\n#s: Small")?;
+
+    assert_eq!(base_name(&schema, "Small").as_deref(), Some("Size"));
+    assert_eq!(
+        schema.get_simpletype_by_name("Small").unwrap().restrictions().unwrap().enumeration,
+        Some(vec!["s".to_string()])
+    );
+
+    assert!(
+        error("Size: \"s\" | \"m\"\nBig < Size: \"xl\"\n#s: Big")
+            .starts_with("'Big' cannot restrict 'Size': enumeration value 'xl'")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_restrictions_cannot_widen() {
+    assert!(
+        error("Title: String<..40>\nLong < Title: String<maxLength: 80>\n#t: Long")
+            .contains("maxLength 80 is wider than the maxLength 40 of the base type")
+    );
+    assert!(
+        error("Title: String<..40>\nLong: Title<10..50>\n#t: Long")
+            .contains("maxLength 50 is wider than the maxLength 40 of the base type")
+    );
+    assert!(
+        error("Id: Int<1..99>\nOther < Id: Int<0..50>\n#i: Other")
+            .contains("minInclusive 0 is wider than the minInclusive 1 of the base type")
+    );
+    assert!(
+        error("Id: Int<minExclusive: 0>\nOther < Id: Int<0..>\n#i: Other")
+            .contains("minInclusive 0 is wider than the minExclusive 0 of the base type")
+    );
+    assert!(
+        error("Code: String<4>\nOther < Code: String<5>\n#c: Other")
+            .contains("length 5 is wider than the length 4 of the base type")
+    );
+    assert!(
+        error(
+            "T: String<whiteSpace: \"collapse\">\nU < T: String<whiteSpace: \"preserve\">\n#t: U"
+        )
+        .contains("whiteSpace preserve is wider than the whiteSpace collapse of the base type")
+    );
+
+    // the restrictions of the base of the base apply too
+    assert!(
+        error("A: Int<..100>\nB < A: Int<10..>\nC < B: Int<..200>\n#c: C")
+            .contains("maxInclusive 200 is wider than the maxInclusive 100 of the base type")
+    );
+}
+
+/// the errors name the derived type and the base type that it restricts
+#[test]
+fn test_restriction_errors_name_the_types() {
+    assert!(
+        error("Title: String<..40>\nLong < Title: String<maxLength: 80>\n#t: Long")
+            .starts_with("'Long' cannot restrict 'Title': maxLength 80 is wider")
+    );
+    assert!(
+        error("Title: String<..40>\nLong: Title<10..50>\n#t: Long")
+            .starts_with("'Long' cannot restrict 'Title': maxLength 50 is wider")
+    );
+    assert!(
+        error("Size: \"s\" | \"m\"\nBig < Size: \"m\" | \"xl\"\n#s: Big")
+            .starts_with("'Big' cannot restrict 'Size': enumeration value 'xl'")
+    );
+}
+
+#[test]
+fn test_enumerations_cannot_widen() {
+    assert!(
+        error("Size: \"s\" | \"m\"\nBig < Size: \"m\" | \"xl\"\n#s: Big")
+            .contains("enumeration value 'xl' is not one of the values of the base type: 's', 'm'")
+    );
+    assert!(
+        error("Size: String<enumeration: \"s\">\nBig: Size<enumeration: \"xl\">\n#s: Big")
+            .contains("enumeration value 'xl' is not one of the values of the base type: 's'")
+    );
+}
+
+#[test]
+fn test_simple_inheritance_errors() {
+    assert!(
+        error("Id: Int<1..99>\nOther < Id: String<5>\n#i: Other").contains(
+            "'Other' restricts 'String', which is not its base type 'Id' or the primitive of it"
+        )
+    );
+    assert!(
+        error("Item { #a: String }\nOther < Item: String<5>\n#i: Other")
+            .contains("Simple type 'Other' cannot inherit from complex type 'Item'")
+    );
+    assert!(
+        error("Id: Int\nOther < Id: Int | String\n#i: Other")
+            .contains("'Other' can only restrict 'Id' to literal values, not to a union of types")
+    );
+}
+
+/// restricted types are printed as facets on their base, which compile to the same chain
+#[test]
+fn test_restriction_chains_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    assert!(
+        whas.contains("UserId: BaseId<minInclusive: 1000, maxInclusive: 9999>\n"),
+        "{}",
+        whas
+    );
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    for name in ["UserId", "ShortId", "Small", "ShortTitle", "Heading"] {
+        assert_eq!(
+            base_name(&reparsed, name),
+            base_name(&schema, name),
+            "{}",
+            whas
+        );
+        assert_eq!(
+            reparsed
+                .get_simpletype_by_name(name)
+                .unwrap()
+                .restrictions(),
+            schema.get_simpletype_by_name(name).unwrap().restrictions()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_restriction_chains() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+    let simple_types = fonto_schema.simple_types();
+
+    // UserId is derived from BaseId, which is derived from the integer builtin
+    let user_id = simple_types
+        .iter()
+        .find_map(|st| match st {
+            fonto::SimpleType::Derived { base, restrictions }
+                if restrictions.min_inclusive.as_deref() == Some("1000") =>
+            {
+                Some(*base)
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    let fonto::SimpleType::Derived { base, restrictions } = &simple_types[user_id] else {
+        panic!("base of UserId is not derived");
+    };
+    assert_eq!(restrictions.max_inclusive.as_deref(), Some("99999"));
+    assert!(matches!(
+        simple_types[*base],
+        fonto::SimpleType::Builtin { .. }
+    ));
+    Ok(())
+}
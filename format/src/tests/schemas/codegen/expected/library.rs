@@ -0,0 +1,358 @@
+// generated from a WHAS schema. Do not edit by hand
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "i64", into = "i64")]
+pub struct Age(i64);
+
+impl Age {
+    pub fn new(value: i64) -> Result<Self, String> {
+        if !(value >= 0) {
+            return Err(format!("{:?}: value must be at least 0", value));
+        }
+        if !(value <= 150) {
+            return Err(format!("{:?}: value must be at most 150", value));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl TryFrom<i64> for Age {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Age> for i64 {
+    fn from(value: Age) -> Self {
+        value.0
+    }
+}
+
+impl std::str::FromStr for Age {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::new(value.parse::<i64>().map_err(|e| e.to_string())?)
+    }
+}
+
+impl std::fmt::Display for Age {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Label {
+    Int(i64),
+    String(String),
+}
+
+impl std::str::FromStr for Label {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(member) = value.parse::<i64>().map_err(|e| e.to_string()) {
+            return Ok(Self::Int(member));
+        }
+        Ok(Self::String(value.to_string()))
+    }
+}
+
+impl std::fmt::Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(member) => f.write_str(&member.to_string()),
+            Self::String(member) => f.write_str(&member.to_string()),
+        }
+    }
+}
+
+impl TryFrom<String> for Label {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Label> for String {
+    fn from(value: Label) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PortAuto {
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+impl std::str::FromStr for PortAuto {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            _ => Err(format!("{:?} is not a valid PortAuto", value)),
+        }
+    }
+}
+
+impl std::fmt::Display for PortAuto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Port {
+    Int(i64),
+    Auto(PortAuto),
+}
+
+impl std::str::FromStr for Port {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(member) = value.parse::<i64>().map_err(|e| e.to_string()) {
+            return Ok(Self::Int(member));
+        }
+        if let Ok(member) = value.parse::<PortAuto>().map_err(|e| e.to_string()) {
+            return Ok(Self::Auto(member));
+        }
+        Err(format!("{:?} is not a valid Port", value))
+    }
+}
+
+impl std::fmt::Display for Port {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(member) => f.write_str(&member.to_string()),
+            Self::Auto(member) => f.write_str(&member.to_string()),
+        }
+    }
+}
+
+impl TryFrom<String> for Port {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Port> for String {
+    fn from(value: Port) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PublishStatus {
+    #[serde(rename = "draft")]
+    Draft,
+    #[serde(rename = "review")]
+    Review,
+    #[serde(rename = "published")]
+    Published,
+}
+
+impl std::str::FromStr for PublishStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "draft" => Ok(Self::Draft),
+            "review" => Ok(Self::Review),
+            "published" => Ok(Self::Published),
+            _ => Err(format!("{:?} is not a valid PublishStatus", value)),
+        }
+    }
+}
+
+impl std::fmt::Display for PublishStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Draft => "draft",
+            Self::Review => "review",
+            Self::Published => "published",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Rating(f64);
+
+impl Rating {
+    pub fn new(value: f64) -> Result<Self, String> {
+        if !(value >= 0.0) {
+            return Err(format!("{:?}: value must be at least 0", value));
+        }
+        if !(value <= 5.0) {
+            return Err(format!("{:?}: value must be at most 5", value));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl TryFrom<f64> for Rating {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Rating> for f64 {
+    fn from(value: Rating) -> Self {
+        value.0
+    }
+}
+
+impl std::str::FromStr for Rating {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::new(value.parse::<f64>().map_err(|e| e.to_string())?)
+    }
+}
+
+impl std::fmt::Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.to_string())
+    }
+}
+
+pub type Tags = Vec<String>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Username(String);
+
+impl Username {
+    pub fn new(value: String) -> Result<Self, String> {
+        if !(value.chars().count() >= 3) {
+            return Err(format!("{:?}: length must be at least 3", value));
+        }
+        if !(value.chars().count() <= 20) {
+            return Err(format!("{:?}: length must be at most 20", value));
+        }
+        static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| regex::Regex::new(r"^(?:[a-z_]+)$").unwrap());
+        if !(pattern.is_match(&value)) {
+            return Err(format!("{:?}: value must match /[a-z_]+/", value));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> &String {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Username> for String {
+    fn from(value: Username) -> Self {
+        value.0
+    }
+}
+
+impl std::str::FromStr for Username {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::new(value.to_string())
+    }
+}
+
+impl std::fmt::Display for Username {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChapterChoice {
+    #[serde(rename = "para")]
+    Para(String),
+    #[serde(rename = "image")]
+    Image(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@status", default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PublishStatus>,
+    #[serde(rename = "title")]
+    pub title: String,
+    #[serde(rename = "author", default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Username>,
+    #[serde(rename = "age", default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<Age>,
+    #[serde(rename = "$value", default, skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<ChapterChoice>,
+    #[serde(rename = "chapter", default, skip_serializing_if = "Vec::is_empty")]
+    pub chapter: Vec<Chapter>,
+    #[serde(rename = "note", default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Box<Note>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    #[serde(rename = "chapter", default, skip_serializing_if = "Option::is_none")]
+    pub chapter: Option<Box<Chapter>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Book {
+    #[serde(rename = "port")]
+    pub port: Port,
+    #[serde(rename = "chapter", default, skip_serializing_if = "Vec::is_empty")]
+    pub chapter: Vec<Chapter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParagraphContent {
+    #[serde(rename = "em")]
+    Em(String),
+    #[serde(rename = "strong")]
+    Strong(String),
+    #[serde(rename = "$text")]
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paragraph {
+    #[serde(rename = "$value", default, skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<ParagraphContent>,
+}
+
+/// names of the elements that can be used as document element
+pub const ROOT_ELEMENTS: &[&str] = &["book", "paragraph"];
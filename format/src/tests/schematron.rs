@@ -0,0 +1,140 @@
+use crate::export::{Exporter, SchematronExporter, WhasExporter, XsdExporter};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/schematron.whas")
+}
+
+fn book(schema: &model::Schema) -> &model::Element {
+    schema
+        .elements()
+        .values()
+        .find(|el| el.name() == "book")
+        .unwrap()
+}
+
+#[test]
+fn test_compile_rules() -> Result<()> {
+    let schema = compile()?;
+    let rules = book(&schema).rules();
+
+    assert_eq!(rules.len(), 3);
+    assert!(rules[0].context().is_empty());
+    assert_eq!(rules[0].test(), "count(chapter) <= 20");
+    assert_eq!(rules[0].message(), "a book has at most 20 chapters");
+    assert_eq!(rules[1].context_xpath("/book", None), "/book//chapter/title");
+    assert_eq!(rules[2].test(), "not(ends-with(., '.'))");
+
+    // the identity constraints come first
+    assert_eq!(book(&schema).constraints().len(), 1);
+    Ok(())
+}
+
+/// rules are printed with delimiters that do not occur in them, so they parse again
+#[test]
+fn test_rules_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+
+    assert!(
+        whas.contains("    rule: `count(chapter) <= 20` `a book has at most 20 chapters`\n"),
+        "{}",
+        whas
+    );
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+    assert_eq!(book(&reparsed).rules(), book(&schema).rules());
+    Ok(())
+}
+
+/// every context has a pattern of its own, for the rules and for the assertions
+#[test]
+fn test_schematron_export_rules() -> Result<()> {
+    let schema = compile()?;
+    let sch = SchematronExporter::default().export_schema(&schema)?;
+
+    assert_eq!(sch.matches("<sch:pattern>").count(), 3, "{}", sch);
+    assert!(sch.contains(r#"<sch:rule context="/book">"#), "{}", sch);
+    assert!(
+        sch.contains(
+            r#"<sch:assert test="count(chapter) &lt;= 20">a book has at most 20 chapters</sch:assert>"#
+        ),
+        "{}",
+        sch
+    );
+    assert!(
+        sch.contains(r#"<sch:rule context="/book//chapter/title">"#),
+        "{}",
+        sch
+    );
+    assert!(sch.contains(r#"<sch:rule context="/book/chapter">"#), "{}", sch);
+    Ok(())
+}
+
+#[test]
+fn test_xsd_embedded_rules() -> Result<()> {
+    let schema = compile()?;
+
+    let plain = XsdExporter::default().export_schema(&schema)?;
+    assert!(!plain.contains("sch:"), "{}", plain);
+
+    let xsd = XsdExporter::default()
+        .with_embedded_schematron()
+        .export_schema(&schema)?;
+
+    assert!(
+        xsd.contains(r#"xmlns:sch="http://purl.oclc.org/dsdl/schematron""#),
+        "{}",
+        xsd
+    );
+
+    // the rules go into the annotation that holds the comments of the element
+    let appinfo = r#"    <xs:annotation>
+      <xs:documentation>the book itself</xs:documentation>
+      <xs:appinfo>
+        <sch:pattern>
+          <sch:rule context="/book">"#;
+    assert!(xsd.contains(appinfo), "{}", xsd);
+
+    // assertions are xs:assert already
+    assert!(
+        !xsd.contains("count(para) &lt;= 50</sch:assert>"),
+        "{}",
+        xsd
+    );
+    Ok(())
+}
+
+/// rules of local elements apply to the places of their declaration, not to other elements
+/// with the same name
+#[test]
+fn test_schematron_local_rule_contexts() -> Result<()> {
+    let ast = ast::SchemaFile::parse(
+        "#doc {\n    #period {\n        #from: Int\n        #to: Int\n    }\n        \
+         rule: `to >= from` \"periods end after they start\"\n    \
+         #note {\n        #period: String\n            \
+         rule: `string-length(.) > 0` \"periods in notes are not empty\"\n    }\n}\n",
+    )?;
+    let schema = compiler::compile(&ast.into())?;
+    let sch = SchematronExporter::default().export_schema(&schema)?;
+
+    let contexts = sch
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("<sch:rule context=\""))
+        .map(|line| line.trim_end_matches("\">"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(contexts, vec!["/doc/note/period", "/doc/period"], "{}", sch);
+
+    // embedded in the XSD, the rules get the same contexts
+    let xsd = XsdExporter::default()
+        .with_embedded_schematron()
+        .export_schema(&schema)?;
+
+    assert!(xsd.contains(r#"<sch:rule context="/doc/period">"#), "{}", xsd);
+    assert!(xsd.contains(r#"<sch:rule context="/doc/note/period">"#), "{}", xsd);
+    assert!(!xsd.contains(r#"<sch:rule context="period">"#), "{}", xsd);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::sourced::{ImportSearch, Manifest, MemorySources};
+use crate::{ast, model};
+use anyhow::Result;
+use std::path::PathBuf;
+
+fn sources() -> MemorySources {
+    MemorySources::new()
+        .with_file(
+            "main.whas",
+            "import {Meta} from \"./lib/meta\"\nimport \"./parts/*\"\nimport {XLink} from \"std:xlink\"\n\n#doc {\n    #meta: Meta\n    #part*: Part\n    #link*: XLink\n}\n",
+        )
+        .with_file("lib/meta.whas", "import \"../types.whas\"\n\nMeta {\n    #title: Title\n}\n")
+        .with_file("types.whas", "Title: String\n")
+        .with_file("parts/part.whas", "Part {\n    #p*: String\n}\n")
+}
+
+/// schemas and their imports are read from the sources, without any files on disk
+#[test]
+fn test_compile_from_memory() -> Result<()> {
+    let schema = model::Schema::from_sources("main.whas", sources(), ImportSearch::default())?;
+
+    for name in ["Meta", "Title", "Part", "XLink"] {
+        assert!(
+            schema.all_type_names().contains(&&name.to_string()),
+            "{}",
+            name
+        );
+    }
+    assert!(schema.get_element_root_by_name("doc").is_some());
+    Ok(())
+}
+
+/// buffers of an editor replace the files they were opened from
+#[test]
+fn test_memory_errors() {
+    let mut sources = sources();
+    sources.insert("types.whas", "Title String\n");
+
+    let error = format!(
+        "{:?}",
+        model::Schema::from_sources("main.whas", sources.clone(), ImportSearch::default())
+            .unwrap_err()
+    );
+    assert!(
+        error.contains("parsing schema from /types.whas"),
+        "{}",
+        error
+    );
+
+    sources.insert("lib/meta.whas", "import \"./missing.whas\"\n");
+    let error = format!(
+        "{:?}",
+        model::Schema::from_sources("main.whas", sources, ImportSearch::default()).unwrap_err()
+    );
+    assert!(
+        error.contains("file not found at paths: /lib/missing.whas"),
+        "{}",
+        error
+    );
+}
+
+/// the include paths and packages of the search are looked up in the sources as well
+#[test]
+fn test_import_search_in_memory() -> Result<()> {
+    let sources = MemorySources::new()
+        .with_file(
+            "app/main.whas",
+            "import {Term} from \"terms\"\nimport {Link} from \"@shared/links\"\n\n#doc {\n    #term*: Term\n    #link*: Link\n}\n",
+        )
+        .with_file("vocab/terms.whas", "import \"./words\"\n\nTerm: Word\n")
+        .with_file("vocab/words.whas", "Word: String\n")
+        .with_file("shared/links.whas", "Link {\n    @href: String\n}\n");
+
+    let manifest: Manifest = toml::from_str("[packages]\nshared = \"shared\"")?;
+    let search = ImportSearch::from_manifest(manifest, &[PathBuf::from("vocab")]);
+    let schema = model::Schema::from_sources("app/main.whas", sources.clone(), search)?;
+
+    for name in ["Term", "Word", "Link"] {
+        assert!(schema.all_type_names().contains(&&name.to_string()), "{}", name);
+    }
+
+    // without them, the import is only looked for next to the schema
+    let error = model::Schema::from_sources("app/main.whas", sources.clone(), ImportSearch::default())
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("file not found at paths: /app/terms"), "{}", error);
+
+    // reading a single schema checks its imports in the sources too
+    let terms = ast::SchemaFile::from_sources("vocab/terms.whas", &sources)?;
+    assert_eq!(terms.types_count(), 1);
+    let error = format!(
+        "{:?}",
+        ast::SchemaFile::from_sources("vocab/terms", &sources.with_file("vocab/words.whas", "Word"))
+            .unwrap_err()
+    );
+    assert!(error.contains("error reading schema: vocab/./words"), "{}", error);
+    assert!(error.contains("1 | Word"), "{}", error);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::export::{Exporter, XsdExporter};
+use crate::model::GroupItem;
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/nested_splats.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+/// names of the elements of the group, with those of the groups splatted into it
+fn element_names(schema: &model::Schema, group: &model::Group) -> Vec<String> {
+    group
+        .items()
+        .iter()
+        .flat_map(|item| match item {
+            GroupItem::Element(e) => vec![e.resolve(schema).name().clone()],
+            GroupItem::Group(g) => element_names(schema, g.resolve(schema)),
+        })
+        .collect()
+}
+
+#[test]
+fn test_nested_splats() -> Result<()> {
+    let schema = compile()?;
+    let inline = schema.get_group_by_name("Inline").unwrap();
+
+    assert_eq!(
+        element_names(&schema, inline),
+        vec!["b", "i", "keyword", "term", "xref", "data"]
+    );
+    assert!(inline.find_element("xref", &schema).is_some());
+    Ok(())
+}
+
+#[test]
+fn test_splat_derived_type() -> Result<()> {
+    let schema = compile()?;
+    let topic = schema.get_element_root_by_name("topic").unwrap();
+    let group = topic.typing().grouptype(&schema).unwrap();
+
+    // the items of the base type come first, the splatted group doesn't derive from it
+    assert_eq!(element_names(&schema, group), vec!["title", "p"]);
+    assert!(group.base_type().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_splat_cycles() {
+    let error = |whas: &str| compile_str(whas).unwrap_err().to_string();
+
+    assert!(
+        error("A { ...B }\nB { ...A }\n#doc { ...A }")
+            .contains("splatting 'B' creates a cycle: B > A > B"),
+    );
+    assert!(error("A { #x { ...A } }\n#doc { ...A }").contains("creates a cycle: A > #x > A"));
+    assert!(error("A { ...A }\n#doc { ...A }").contains("creates a cycle: A > A"));
+    assert!(
+        error("#doc { ...Missing }").contains("cannot splat 'Missing', because it is not defined")
+    );
+    assert!(
+        error("A: String\n#doc { ...A }").contains("cannot splat 'A', because it is not a block")
+    );
+}
+
+/// a splatted group can have elements of a type that splats the group in turn, since
+/// that type is compiled on its own
+#[test]
+fn test_splat_through_named_type() -> Result<()> {
+    let schema = compile_str(
+        "Content ?{\n    #b: String\n    #pair: Pair\n}\nPair {\n    ...Content\n    ...Content\n}\n#doc { ...Content }",
+    )?;
+    let pair = schema.get_group_by_name("Pair").unwrap();
+
+    assert_eq!(element_names(&schema, pair), vec!["b", "pair", "b", "pair"]);
+    Ok(())
+}
+
+/// elements of a recursive type refer to it by name instead of repeating its content
+#[test]
+fn test_recursive_type_xsd() -> Result<()> {
+    let schema =
+        compile_str("Section {\n    #title: String\n    #section*: Section\n}\n#doc: Section")?;
+    let xsd = XsdExporter::default().export_schema(&schema)?;
+
+    assert!(
+        xsd.contains(
+            r#"<xs:element name="section" minOccurs="0" maxOccurs="unbounded" type="Section"/>"#
+        ),
+        "{}",
+        xsd
+    );
+    Ok(())
+}
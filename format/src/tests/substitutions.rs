@@ -0,0 +1,137 @@
+use crate::export::{Exporter, FontoSchemaExporter, WhasExporter};
+use crate::formats::fonto;
+use crate::{ast, compiler, model};
+use anyhow::Result;
+use itertools::Itertools;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/substitution_groups.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+fn substitute_names(schema: &model::Schema, head: &str) -> Vec<String> {
+    schema
+        .get_substitutes(head)
+        .iter()
+        .map(|el| el.name().clone())
+        .sorted()
+        .collect()
+}
+
+#[test]
+fn test_compile_substitution_groups() -> Result<()> {
+    let schema = compile()?;
+
+    let p = schema.get_element_root_by_name("p").unwrap();
+    assert_eq!(p.substitutes().as_deref(), Some("block-element"));
+
+    // members of members are substitutes of the head as well
+    assert_eq!(
+        substitute_names(&schema, "block-element"),
+        vec!["fig", "note", "p"]
+    );
+    assert_eq!(substitute_names(&schema, "p"), vec!["note"]);
+    assert!(substitute_names(&schema, "fig").is_empty());
+
+    let head = schema.get_element_root_by_name("block-element").unwrap();
+    assert!(head.is_substitution_head(&schema) && head.is_abstract(&schema));
+    Ok(())
+}
+
+#[test]
+fn test_substitution_errors() {
+    let error = |whas: &str| compile_str(whas).unwrap_err().to_string();
+
+    assert!(
+        error("#p < #block: String")
+            .contains("#p substitutes #block, which is not a top-level element")
+    );
+    assert!(
+        error("#a < #b: String\n#b < #a: String").contains("substitution group of #a is circular")
+    );
+    assert!(error("#head: Int\n#p < #head: String").contains(
+        "#p cannot substitute #head, because its type 'String' does not derive from 'Int'"
+    ));
+    assert!(
+        error("Base { #a: String }\nOther { #b: String }\n#head: Base\n#p < #head: Other")
+            .contains("its type 'Other' does not derive from 'Base'")
+    );
+    assert!(
+        error("#head: String\n#doc { #p < #head: String }")
+            .contains("only top-level elements can be part of a substitution group")
+    );
+}
+
+#[test]
+fn test_validate_substitutes() -> Result<()> {
+    let schema = compile_str(
+        "Block: a{ #label?: String }\n\
+         Para < Block {\n    #text*: String\n    assert count(#text) < 2\n}\n\
+         #block: Block\n\
+         #p < #block: Para\n\
+         #doc { #block*: Block }",
+    )?;
+
+    assert!(schema.validate(&"<doc><p><text/></p></doc>".to_string()).is_ok());
+
+    // the member is validated by its own definition
+    let errors = schema
+        .validate(&"<doc><p><text/><text/></p></doc>".to_string())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("#p does not satisfy"));
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_substitutes() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+
+    // the choices are nested in the sequence of #section
+    let choices = fonto_schema
+        .content_models()
+        .iter()
+        .flat_map(|cm| match cm {
+            fonto::ContentModel::Sequence { items, .. } => items.as_slice(),
+            _ => &[],
+        })
+        .filter_map(|cm| match cm {
+            fonto::ContentModel::Choice { items, .. } => Some(
+                items
+                    .iter()
+                    .filter_map(|item| match item {
+                        fonto::ContentModel::Element { name, .. } => Some(name.as_str()),
+                        _ => None,
+                    })
+                    .collect_vec(),
+            ),
+            _ => None,
+        })
+        .collect_vec();
+
+    // the abstract head is left out of the choice
+    assert!(choices.contains(&vec!["p", "fig", "note"]), "{:?}", choices);
+    assert!(choices.contains(&vec!["code", "short-code"]), "{:?}", choices);
+    Ok(())
+}
+
+#[test]
+fn test_substitution_groups_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+    assert!(whas.contains("#note < #p: Note\n"), "{}", whas);
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    assert_eq!(
+        substitute_names(&reparsed, "block-element"),
+        substitute_names(&schema, "block-element")
+    );
+    Ok(())
+}
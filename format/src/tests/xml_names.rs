@@ -0,0 +1,146 @@
+use crate::export::{Exporter, FontoSchemaExporter, WhasExporter};
+use crate::{ast, compiler, model};
+use anyhow::Result;
+
+fn compile() -> Result<model::Schema> {
+    model::Schema::from_file("src/tests/schemas/xsd/xml_names.whas")
+}
+
+fn compile_str(whas: &str) -> Result<model::Schema> {
+    let ast = ast::SchemaFile::parse(whas).unwrap();
+    compiler::compile(&ast.into())
+}
+
+#[test]
+fn test_compile_xml_names() -> Result<()> {
+    let schema = compile()?;
+
+    let dm_ref = schema.get_element_root_by_name("dmRef").unwrap();
+    let names = dm_ref
+        .typing()
+        .grouptype(&schema)
+        .unwrap()
+        .find_element("größe", &schema)
+        .map(|el| el.name().clone());
+    assert_eq!(names.as_deref(), Some("größe"));
+
+    let attrs = dm_ref
+        .attributes()
+        .as_vec()
+        .iter()
+        .map(|attr| attr.resolve(&schema).name().clone())
+        .collect::<Vec<_>>();
+    assert!(attrs.contains(&"xlink:href".to_string()), "{:?}", attrs);
+    assert!(attrs.contains(&"xml:lang".to_string()), "{:?}", attrs);
+    Ok(())
+}
+
+/// a colon right before a type name is the typing, not a namespace prefix
+#[test]
+fn test_typing_without_space() -> Result<()> {
+    let schema = compile_str("@id:ID\n@xlink:href:URI\n#doc {\n    #title:String\n}")?;
+
+    let doc = schema.get_element_root_by_name("doc").unwrap();
+    let attrs = doc
+        .attributes()
+        .as_vec()
+        .iter()
+        .map(|attr| attr.resolve(&schema).name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(attrs, vec!["id", "xlink:href"]);
+
+    let title = doc
+        .typing()
+        .grouptype(&schema)
+        .unwrap()
+        .find_element("title", &schema)
+        .unwrap();
+    assert_eq!(
+        title.typing().simpletype(&schema).unwrap().primitive(&schema),
+        Some(model::PrimitiveType::String)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_xml_name_errors() {
+    let error = |whas: &str| compile_str(whas).unwrap_err().to_string();
+
+    assert!(error("#foo:title: String").contains(
+        "#foo:title has an unknown namespace prefix 'foo', only the xml, xlink, dc, mml prefixes are supported"
+    ));
+    assert!(error("@foo:bar: String\n#doc: String").contains(
+        "@foo:bar has an unknown namespace prefix 'foo', only the xml, xlink, dc, mml prefixes are supported"
+    ));
+}
+
+#[test]
+fn test_lint_naming_convention() -> Result<()> {
+    let schema =
+        compile_str("@xml:lang?: String\n@dataType: String\n#list-item { #subItem?: String }")?;
+
+    assert_eq!(
+        compiler::lint_naming_convention(&schema),
+        vec![
+            "#subItem does not follow the naming convention of lowercase names with dashes",
+            "@dataType does not follow the naming convention of lowercase names with dashes",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_validate_prefixed_attributes() -> Result<()> {
+    let schema = compile_str(
+        "@xml:lang: String\n#dmTitle {\n    #techName: String\n    assert @xml:lang != \"\"\n}",
+    )?;
+
+    let result = schema.validate(&r#"<dmTitle xml:lang="en"><techName/></dmTitle>"#.to_string());
+    assert!(result.is_ok(), "{:?}", result);
+
+    let errors = schema
+        .validate(&r#"<dmTitle xml:lang=""><techName/></dmTitle>"#.to_string())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("#dmTitle does not satisfy"));
+    Ok(())
+}
+
+#[test]
+fn test_fonto_export_prefixed_attributes() -> Result<()> {
+    let schema = compile()?;
+    let fonto_schema = FontoSchemaExporter::default().export_schema(&schema)?;
+    let json = serde_json::to_value(&fonto_schema)?;
+
+    let attr = |name: &str| {
+        json["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|attr| attr["localName"] == name)
+            .cloned()
+            .unwrap()
+    };
+
+    assert_eq!(attr("href")["namespaceURI"], model::XLINK_NAMESPACE);
+    assert_eq!(attr("lang")["namespaceURI"], model::XML_NAMESPACE);
+    assert!(attr("modelIdentCode")["namespaceURI"].is_null());
+    Ok(())
+}
+
+#[test]
+fn test_xml_names_round_trip() -> Result<()> {
+    let schema = compile()?;
+    let whas = WhasExporter::default().export_schema(&schema)?;
+    assert!(whas.contains("@xlink:href\n@xml:lang?\n#dmRef {\n"), "{}", whas);
+
+    let ast = ast::SchemaFile::parse(&whas).unwrap();
+    let reparsed = compiler::compile(&ast.into())?;
+
+    assert!(reparsed.get_element_root_by_name("dmRef").is_some());
+    assert_eq!(
+        compiler::lint_naming_convention(&reparsed),
+        compiler::lint_naming_convention(&schema)
+    );
+    Ok(())
+}
@@ -1,2 +1,442 @@
-// todo
-pub struct ValidationError {}
+use crate::model;
+use crate::model::{CompareOp, ConstraintField, ConstraintKind, Expr, IdentityConstraint};
+use derive_getters::Getters;
+use roxmltree::{Document, Node};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+
+/// a problem found in an XML instance while validating it against a schema
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct ValidationError {
+    /// line in the instance where the problem was found, starting at 1
+    line: u32,
+    message: String,
+}
+
+impl ValidationError {
+    fn at(node: Node, message: impl Into<String>) -> Self {
+        Self {
+            line: node.document().text_pos_at(node.range().start).row,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// walks an XML instance along the element definitions of the schema
+pub(crate) struct Validator<'a> {
+    schema: &'a model::Schema,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    pub(crate) fn new(schema: &'a model::Schema) -> Self {
+        Self {
+            schema,
+            errors: vec![],
+        }
+    }
+
+    pub(crate) fn validate(mut self, xml: &str) -> Result<(), Vec<ValidationError>> {
+        let doc = Document::parse(xml).map_err(|err| {
+            vec![ValidationError {
+                line: err.pos().row,
+                message: err.to_string(),
+            }]
+        })?;
+
+        let root = doc.root_element();
+        let root_name = qualified_name(root);
+
+        match self
+            .schema
+            .get_elements_root()
+            .into_iter()
+            .find(|el| *el.name() == root_name)
+        {
+            Some(element) => self.validate_element(element, root),
+            None => self.errors.push(ValidationError::at(
+                root,
+                format!("root element #{} is not defined in the schema", root_name),
+            )),
+        }
+
+        match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(self.errors),
+        }
+    }
+
+    fn validate_element(&mut self, element: &model::Element, node: Node) {
+        self.validate_constraints(element, node);
+
+        // children that are not defined in the schema are not followed
+        let Some(group) = element.typing().grouptype(self.schema) else {
+            return;
+        };
+
+        for assertion in group.merged_assertions(self.schema) {
+            if !self.evaluate(assertion.test(), element, node).truthy() {
+                self.errors.push(ValidationError::at(
+                    node,
+                    format!("#{} does not satisfy '{}'", element.name(), assertion),
+                ));
+            }
+        }
+
+        if group.is_empty_content(self.schema)
+            && node.children().any(|child| child.is_element() || child.is_text())
+        {
+            self.errors.push(ValidationError::at(
+                node,
+                format!("#{} cannot have any content", element.name()),
+            ));
+        }
+
+        for child in node.children().filter(Node::is_element) {
+            let name = qualified_name(child);
+            let name = name.as_str();
+
+            if let Some(child_def) = group
+                .find_element(name, self.schema)
+                // references carry no constraints or rules, those are on the top-level element
+                .map(|el| match el.global_ref() {
+                    true => self.schema.get_element_root_by_name(name).unwrap_or(el),
+                    false => el,
+                })
+                .or_else(|| self.find_substitute(group, name))
+            {
+                self.validate_element(child_def, child);
+            }
+        }
+    }
+
+    /// evaluate an assertion on an instance of the element
+    fn evaluate(&self, expr: &Expr, element: &model::Element, node: Node) -> Value {
+        let evaluate = |expr| self.evaluate(expr, element, node);
+
+        match expr {
+            Expr::If(condition, then) => {
+                Value::Bool(!evaluate(condition).truthy() || evaluate(then).truthy())
+            }
+            Expr::Or(exprs) => Value::Bool(exprs.iter().any(|expr| evaluate(expr).truthy())),
+            Expr::And(exprs) => Value::Bool(exprs.iter().all(|expr| evaluate(expr).truthy())),
+            Expr::Not(expr) => Value::Bool(!evaluate(expr).truthy()),
+            Expr::Compare(left, op, right) => {
+                let (left, right) = (evaluate(left).atoms(), evaluate(right).atoms());
+
+                // like XPath, the comparison holds when it holds for any of the values
+                Value::Bool(
+                    left.iter()
+                        .any(|left| right.iter().any(|right| left.compare(*op, right))),
+                )
+            }
+            Expr::Paren(expr) => evaluate(expr),
+            Expr::Count(name) => Value::Number(
+                node.children()
+                    .filter(|child| has_name(child, name))
+                    .count() as f64,
+            ),
+            Expr::Attribute(name) => {
+                let numeric = element
+                    .attributes()
+                    .deref()
+                    .get(name)
+                    .map(|attr| attr.resolve(self.schema))
+                    .or_else(|| {
+                        element
+                            .typing()
+                            .grouptype(self.schema)
+                            .and_then(|group| group.find_attribute(name, self.schema))
+                    })
+                    .is_some_and(|attr| self.is_numeric(attr.typing.resolve(self.schema)));
+
+                Value::Nodes(
+                    attribute(node, name)
+                        .map(|value| typed_atom(value.to_string(), numeric))
+                        .into_iter()
+                        .collect(),
+                )
+            }
+            Expr::Element(name) => {
+                let numeric = element
+                    .typing()
+                    .grouptype(self.schema)
+                    .and_then(|group| group.find_element(name, self.schema))
+                    .and_then(|child| child.typing().simpletype(self.schema))
+                    .is_some_and(|ty| self.is_numeric(ty));
+
+                Value::Nodes(
+                    node.children()
+                        .filter(|child| has_name(child, name))
+                        .map(|child| typed_atom(text_content(child), numeric))
+                        .collect(),
+                )
+            }
+            Expr::Number(number) => Value::Number(number.parse().unwrap_or(f64::NAN)),
+            Expr::Literal(value) => Value::Text(value.clone()),
+        }
+    }
+
+    /// values of a numeric type are compared as numbers, all others as text
+    fn is_numeric(&self, ty: &model::SimpleType) -> bool {
+        ty.primitive(self.schema)
+            .is_some_and(|primitive| primitive.is_numeric())
+    }
+
+    /// the top-level element with the name, when it is used in place of a head element of the group
+    fn find_substitute(&self, group: &model::Group, name: &str) -> Option<&'a model::Element> {
+        let member = self.schema.get_element_root_by_name(name)?;
+        let mut head = member.substitutes().as_deref();
+
+        while let Some(head_name) = head {
+            if group
+                .find_element(head_name, self.schema)
+                .is_some_and(|local| self.schema.get_substitution_head(local).is_some())
+            {
+                return Some(member);
+            }
+
+            head = self
+                .schema
+                .get_element_root_by_name(head_name)
+                .and_then(|head| head.substitutes().as_deref());
+        }
+
+        None
+    }
+
+    /// check the identity constraints of an element within the scope of one of its instances
+    fn validate_constraints(&mut self, element: &model::Element, scope: Node) {
+        let mut keys: HashMap<&str, HashSet<String>> = HashMap::new();
+
+        // key references are checked after all keys are collected
+        let (refs, constraints): (Vec<_>, Vec<_>) = element
+            .constraints()
+            .iter()
+            .partition(|constraint| matches!(constraint.kind(), ConstraintKind::KeyRef(_)));
+
+        for constraint in constraints {
+            let mut seen = HashSet::new();
+
+            for node in select(scope, constraint) {
+                match field_value(node, constraint) {
+                    Some(value) if !seen.insert(value.clone()) => {
+                        self.errors.push(ValidationError::at(
+                            node,
+                            format!(
+                                "duplicate value '{}' for '{}' within #{}",
+                                value,
+                                constraint,
+                                element.name()
+                            ),
+                        ))
+                    }
+                    Some(_) => {}
+                    None if matches!(constraint.kind(), ConstraintKind::Key(_)) => {
+                        self.errors.push(ValidationError::at(
+                            node,
+                            format!(
+                                "missing value for '{}' within #{}",
+                                constraint,
+                                element.name()
+                            ),
+                        ))
+                    }
+                    None => {}
+                }
+            }
+
+            if let ConstraintKind::Key(name) = constraint.kind() {
+                keys.insert(name, seen);
+            }
+        }
+
+        for constraint in refs {
+            let ConstraintKind::KeyRef(key) = constraint.kind() else {
+                continue;
+            };
+            let values = keys.get(key.as_str());
+
+            for node in select(scope, constraint) {
+                if let Some(value) = field_value(node, constraint)
+                    && !values.is_some_and(|values| values.contains(&value))
+                {
+                    self.errors.push(ValidationError::at(
+                        node,
+                        format!(
+                            "'{}' of {} does not refer to any '{}' within #{}",
+                            value,
+                            constraint.path(),
+                            key,
+                            element.name()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// the value of an attribute or element as a number when its type is numeric
+fn typed_atom(value: String, numeric: bool) -> Atom {
+    match value.trim().parse() {
+        Ok(number) if numeric => Atom::Number(number),
+        _ => Atom::Text(value),
+    }
+}
+
+/// the nodes that a constraint applies to, relative to the scope element
+fn select<'a, 'input>(
+    scope: Node<'a, 'input>,
+    constraint: &IdentityConstraint,
+) -> Vec<Node<'a, 'input>> {
+    let mut steps = constraint.selector().iter();
+
+    let Some(first) = steps.next() else {
+        return vec![];
+    };
+
+    let mut nodes = scope
+        .descendants()
+        .skip(1)
+        .filter(|node| has_name(node, first))
+        .collect::<Vec<_>>();
+
+    for step in steps {
+        nodes = nodes
+            .into_iter()
+            .flat_map(|node| node.children())
+            .filter(|node| has_name(node, step))
+            .collect();
+    }
+
+    nodes
+}
+
+/// the value of a selected node, from one of its attributes or the text of a child element
+fn field_value(node: Node, constraint: &IdentityConstraint) -> Option<String> {
+    match constraint.field() {
+        ConstraintField::Attribute(name) => attribute(node, name).map(str::to_string),
+        ConstraintField::Element(name) => node
+            .children()
+            .find(|child| has_name(child, name))
+            .map(text_content),
+    }
+}
+
+/// the name of an element as written in the schema, including the prefix of its namespace.
+/// Known namespaces have the prefix of the schema, whichever prefix the document binds
+fn qualified_name(node: Node) -> String {
+    let tag = node.tag_name();
+    let prefix = tag.namespace().and_then(|uri| {
+        model::known_prefix(uri).or_else(|| node.lookup_prefix(uri).filter(|p| !p.is_empty()))
+    });
+
+    match prefix {
+        Some(prefix) => format!("{}:{}", prefix, tag.name()),
+        None => tag.name().to_string(),
+    }
+}
+
+fn has_name(node: &Node, name: &str) -> bool {
+    node.is_element() && qualified_name(*node) == name
+}
+
+/// the value of an attribute by its name in the schema, which can be prefixed
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    match name.split_once(':') {
+        Some((prefix, local)) => model::namespace_uri(name)
+            .or_else(|| node.lookup_namespace_uri(Some(prefix)))
+            .and_then(|uri| node.attribute((uri, local))),
+        None => node.attribute(name),
+    }
+}
+
+/// the trimmed text of an element and its descendants
+fn text_content(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|text| text.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// result of an assertion expression
+#[derive(Debug)]
+enum Value {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    /// values of the attributes or child elements found, which can be none
+    Nodes(Vec<Atom>),
+}
+
+impl Value {
+    /// whether the value holds, like XPath's effective boolean value
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            Value::Number(number) => *number != 0.0 && !number.is_nan(),
+            Value::Text(text) => !text.is_empty(),
+            Value::Nodes(nodes) => !nodes.is_empty(),
+        }
+    }
+
+    fn atoms(&self) -> Vec<Atom> {
+        match self {
+            Value::Bool(value) => vec![Atom::Number(*value as u8 as f64)],
+            Value::Number(number) => vec![Atom::Number(*number)],
+            Value::Text(text) => vec![Atom::Text(text.clone())],
+            Value::Nodes(nodes) => nodes.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Atom {
+    Number(f64),
+    Text(String),
+}
+
+impl Atom {
+    fn number(&self) -> Option<f64> {
+        match self {
+            Atom::Number(number) => Some(*number),
+            Atom::Text(text) => text.trim().parse().ok(),
+        }
+    }
+
+    /// two texts are compared as is, like values of a string type are in XPath. Numbers are
+    /// compared by value, also with text that is written as a number
+    fn compare(&self, op: CompareOp, other: &Atom) -> bool {
+        let ordering = match (self, other) {
+            (Atom::Text(left), Atom::Text(right)) => Some(left.cmp(right)),
+            _ => match (self.number(), other.number()) {
+                (Some(left), Some(right)) => left.partial_cmp(&right),
+                _ => None,
+            },
+        };
+
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match op {
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+